@@ -46,7 +46,7 @@
 #![feature(placement_in)]
 #![feature(placement_new_protocol)]
 #![feature(shared)]
-#![feature(slice_patterns)]
+#![cfg_attr(stage0, feature(slice_patterns))]
 #![feature(specialization)]
 #![feature(staged_api)]
 #![feature(step_by)]
@@ -165,6 +165,7 @@ impl<T> [T] {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[must_use]
     pub fn len(&self) -> usize {
         core_slice::SliceExt::len(self)
     }
@@ -179,6 +180,7 @@ impl<T> [T] {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         core_slice::SliceExt::is_empty(self)
     }
@@ -193,6 +193,7 @@ impl str {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[must_use]
     pub fn len(&self) -> usize {
         core_str::StrExt::len(self)
     }
@@ -212,6 +213,7 @@ impl str {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         core_str::StrExt::is_empty(self)
     }
@@ -827,6 +827,7 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn capacity(&self) -> usize {
         self.vec.capacity()
     }
@@ -1184,6 +1185,7 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn len(&self) -> usize {
         self.vec.len()
     }
@@ -1205,6 +1207,7 @@ impl String {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
@@ -397,6 +397,7 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn capacity(&self) -> usize {
         self.buf.cap()
     }
@@ -854,6 +855,7 @@ impl<T> Vec<T> {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn len(&self) -> usize {
         self.len
     }
@@ -870,6 +872,7 @@ impl<T> Vec<T> {
     /// assert!(!v.is_empty());
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use]
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
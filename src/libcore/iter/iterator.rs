@@ -304,6 +304,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn chain<U>(self, other: U) -> Chain<Self, U::IntoIter> where
         Self: Sized, U: IntoIterator<Item=Self::Item>,
     {
@@ -379,6 +380,7 @@ pub trait Iterator {
     /// [`enumerate()`]: trait.Iterator.html#method.enumerate
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn zip<U>(self, other: U) -> Zip<Self, U::IntoIter> where
         Self: Sized, U: IntoIterator
     {
@@ -435,6 +437,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn map<B, F>(self, f: F) -> Map<Self, F> where
         Self: Sized, F: FnMut(Self::Item) -> B,
     {
@@ -503,6 +506,7 @@ pub trait Iterator {
     /// of these layers.
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn filter<P>(self, predicate: P) -> Filter<Self, P> where
         Self: Sized, P: FnMut(&Self::Item) -> bool,
     {
@@ -562,6 +566,7 @@ pub trait Iterator {
     /// There's an extra layer of `Some` in there.
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn filter_map<B, F>(self, f: F) -> FilterMap<Self, F> where
         Self: Sized, F: FnMut(Self::Item) -> Option<B>,
     {
@@ -609,6 +614,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn enumerate(self) -> Enumerate<Self> where Self: Sized {
         Enumerate { iter: self, count: 0 }
     }
@@ -653,6 +659,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn peekable(self) -> Peekable<Self> where Self: Sized {
         Peekable{iter: self, peeked: None}
     }
@@ -714,6 +721,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn skip_while<P>(self, predicate: P) -> SkipWhile<Self, P> where
         Self: Sized, P: FnMut(&Self::Item) -> bool,
     {
@@ -794,6 +802,7 @@ pub trait Iterator {
     /// some similar thing.
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn take_while<P>(self, predicate: P) -> TakeWhile<Self, P> where
         Self: Sized, P: FnMut(&Self::Item) -> bool,
     {
@@ -818,6 +827,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn skip(self, n: usize) -> Skip<Self> where Self: Sized {
         Skip{iter: self, n: n}
     }
@@ -850,6 +860,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn take(self, n: usize) -> Take<Self> where Self: Sized, {
         Take{iter: self, n: n}
     }
@@ -893,6 +904,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn scan<St, B, F>(self, initial_state: St, f: F) -> Scan<Self, St, F>
         where Self: Sized, F: FnMut(&mut St, Self::Item) -> Option<B>,
     {
@@ -927,6 +939,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn flat_map<U, F>(self, f: F) -> FlatMap<Self, U, F>
         where Self: Sized, U: IntoIterator, F: FnMut(Self::Item) -> U,
     {
@@ -986,6 +999,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn fuse(self) -> Fuse<Self> where Self: Sized {
         Fuse{iter: self, done: false}
     }
@@ -1039,6 +1053,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn inspect<F>(self, f: F) -> Inspect<Self, F> where
         Self: Sized, F: FnMut(&Self::Item),
     {
@@ -1714,6 +1729,7 @@ pub trait Iterator {
     /// ```
     #[inline]
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn rev(self) -> Rev<Self> where Self: Sized + DoubleEndedIterator {
         Rev{iter: self}
     }
@@ -1792,6 +1808,7 @@ pub trait Iterator {
     /// assert_eq!(v_map, vec![1, 2, 3]);
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn cloned<'a, T: 'a>(self) -> Cloned<Self>
         where Self: Sized + Iterator<Item=&'a T>, T: Clone
     {
@@ -1823,6 +1840,7 @@ pub trait Iterator {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
     fn cycle(self) -> Cycle<Self> where Self: Sized + Clone {
         Cycle{orig: self.clone(), iter: self}
     }
@@ -404,6 +404,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_add(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_add(other);
             if b {None} else {Some(a)}
@@ -422,6 +424,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_sub(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_sub(other);
             if b {None} else {Some(a)}
@@ -440,6 +444,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_mul(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_mul(other);
             if b {None} else {Some(a)}
@@ -459,6 +465,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_div(self, other: Self) -> Option<Self> {
             if other == 0 {
                 None
@@ -484,6 +492,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_rem(self, other: Self) -> Option<Self> {
             if other == 0 {
                 None
@@ -508,6 +518,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_neg(self) -> Option<Self> {
             let (a, b) = self.overflowing_neg();
             if b {None} else {Some(a)}
@@ -526,6 +538,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_shl(self, rhs: u32) -> Option<Self> {
             let (a, b) = self.overflowing_shl(rhs);
             if b {None} else {Some(a)}
@@ -544,6 +558,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_shr(self, rhs: u32) -> Option<Self> {
             let (a, b) = self.overflowing_shr(rhs);
             if b {None} else {Some(a)}
@@ -562,6 +578,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_add(self, other: Self) -> Self {
             match self.checked_add(other) {
                 Some(x) => x,
@@ -583,6 +601,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_sub(self, other: Self) -> Self {
             match self.checked_sub(other) {
                 Some(x) => x,
@@ -607,6 +627,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_mul(self, other: Self) -> Self {
             self.checked_mul(other).unwrap_or_else(|| {
                 if (self < 0 && other < 0) || (self > 0 && other > 0) {
@@ -630,6 +652,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_add(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_add(self, rhs)
@@ -649,6 +673,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_sub(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_sub(self, rhs)
@@ -668,6 +694,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_mul(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_mul(self, rhs)
@@ -698,6 +726,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_div(self, rhs: Self) -> Self {
             self.overflowing_div(rhs).0
         }
@@ -724,6 +754,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_rem(self, rhs: Self) -> Self {
             self.overflowing_rem(rhs).0
         }
@@ -747,6 +779,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_neg(self) -> Self {
             self.overflowing_neg().0
         }
@@ -772,6 +806,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_shl(self, rhs: u32) -> Self {
             self.overflowing_shl(rhs).0
         }
@@ -797,6 +833,8 @@ macro_rules! int_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_shr(self, rhs: u32) -> Self {
             self.overflowing_shr(rhs).0
         }
@@ -819,6 +857,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $add_with_overflow(self as $ActualT,
@@ -845,6 +885,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $sub_with_overflow(self as $ActualT,
@@ -869,6 +911,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $mul_with_overflow(self as $ActualT,
@@ -899,6 +943,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
             if self == Self::min_value() && rhs == -1 {
                 (self, true)
@@ -929,6 +975,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
             if self == Self::min_value() && rhs == -1 {
                 (0, true)
@@ -957,6 +1005,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_neg(self) -> (Self, bool) {
             if self == Self::min_value() {
                 (Self::min_value(), true)
@@ -983,6 +1033,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
             (self << (rhs & ($BITS - 1)), (rhs > ($BITS - 1)))
         }
@@ -1005,6 +1057,8 @@ macro_rules! int_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
             (self >> (rhs & ($BITS - 1)), (rhs > ($BITS - 1)))
         }
@@ -1472,6 +1526,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_add(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_add(other);
             if b {None} else {Some(a)}
@@ -1490,6 +1546,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_sub(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_sub(other);
             if b {None} else {Some(a)}
@@ -1508,6 +1566,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_mul(self, other: Self) -> Option<Self> {
             let (a, b) = self.overflowing_mul(other);
             if b {None} else {Some(a)}
@@ -1526,6 +1586,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_div(self, other: Self) -> Option<Self> {
             match other {
                 0 => None,
@@ -1546,6 +1608,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_rem(self, other: Self) -> Option<Self> {
             if other == 0 {
                 None
@@ -1569,6 +1633,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_neg(self) -> Option<Self> {
             let (a, b) = self.overflowing_neg();
             if b {None} else {Some(a)}
@@ -1587,6 +1653,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_shl(self, rhs: u32) -> Option<Self> {
             let (a, b) = self.overflowing_shl(rhs);
             if b {None} else {Some(a)}
@@ -1605,6 +1673,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_shr(self, rhs: u32) -> Option<Self> {
             let (a, b) = self.overflowing_shr(rhs);
             if b {None} else {Some(a)}
@@ -1623,6 +1693,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_add(self, other: Self) -> Self {
             match self.checked_add(other) {
                 Some(x) => x,
@@ -1643,6 +1715,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_sub(self, other: Self) -> Self {
             match self.checked_sub(other) {
                 Some(x) => x,
@@ -1665,6 +1739,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "wrapping", since = "1.7.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn saturating_mul(self, other: Self) -> Self {
             self.checked_mul(other).unwrap_or(Self::max_value())
         }
@@ -1682,6 +1758,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_add(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_add(self, rhs)
@@ -1701,6 +1779,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_sub(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_sub(self, rhs)
@@ -1720,6 +1800,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
         #[inline]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_mul(self, rhs: Self) -> Self {
             unsafe {
                 intrinsics::overflowing_mul(self, rhs)
@@ -1741,6 +1823,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_div(self, rhs: Self) -> Self {
             self / rhs
         }
@@ -1761,6 +1845,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_rem(self, rhs: Self) -> Self {
             self % rhs
         }
@@ -1787,6 +1873,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_neg(self) -> Self {
             self.overflowing_neg().0
         }
@@ -1812,6 +1900,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_shl(self, rhs: u32) -> Self {
             self.overflowing_shl(rhs).0
         }
@@ -1837,6 +1927,8 @@ macro_rules! uint_impl {
         /// ```
         #[stable(feature = "num_wrapping", since = "1.2.0")]
         #[inline(always)]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn wrapping_shr(self, rhs: u32) -> Self {
             self.overflowing_shr(rhs).0
         }
@@ -1859,6 +1951,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $add_with_overflow(self as $ActualT,
@@ -1885,6 +1979,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $sub_with_overflow(self as $ActualT,
@@ -1909,6 +2005,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
             unsafe {
                 let (a, b) = $mul_with_overflow(self as $ActualT,
@@ -1937,6 +2035,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
             (self / rhs, false)
         }
@@ -1961,6 +2061,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_rem(self, rhs: Self) -> (Self, bool) {
             (self % rhs, false)
         }
@@ -1982,6 +2084,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_neg(self) -> (Self, bool) {
             ((!self).wrapping_add(1), self != 0)
         }
@@ -2004,6 +2108,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_shl(self, rhs: u32) -> (Self, bool) {
             (self << (rhs & ($BITS - 1)), (rhs > ($BITS - 1)))
         }
@@ -2026,6 +2132,8 @@ macro_rules! uint_impl {
         /// ```
         #[inline]
         #[stable(feature = "wrapping", since = "1.7.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn overflowing_shr(self, rhs: u32) -> (Self, bool) {
             (self >> (rhs & ($BITS - 1)), (rhs > ($BITS - 1)))
         }
@@ -2118,6 +2226,8 @@ macro_rules! uint_impl {
         /// assert_eq!(200u8.checked_next_power_of_two(), None);
         /// ```
         #[stable(feature = "rust1", since = "1.0.0")]
+        #[must_use = "this returns the result of the operation, \
+                      without modifying the original"]
         pub fn checked_next_power_of_two(self) -> Option<Self> {
             let npot = self.next_power_of_two();
             if npot >= self {
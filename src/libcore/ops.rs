@@ -72,6 +72,8 @@ use fmt;
 use convert::From;
 use marker::{Sized, Unsize};
 use num::One;
+use option::Option::{self, Some, None};
+use result::Result::{self, Ok, Err};
 
 /// The `Drop` trait is used to run some code when a value goes out of scope.
 /// This is sometimes called a 'destructor'.
@@ -2155,3 +2157,134 @@ pub trait BoxPlace<Data: ?Sized> : Place<Data> {
     /// Creates a globally fresh place.
     fn make_place() -> Self;
 }
+
+/// A trait for types which can be used with the `?` operator.
+///
+/// `EXPR?` effectively desugars into:
+///
+/// ```rust,ignore
+/// match Carrier::translate(EXPR) {
+///     Ok(val) => val,
+///     Err(err) => return Carrier::from_error(From::from(err)),
+/// }
+/// ```
+///
+/// where the type of the `Carrier::translate` call is a `Result`. The
+/// operand of `?` and the return type of the enclosing function must
+/// both implement `Carrier`, though they need not be the same type:
+/// an `Option<T>` can be propagated out of a function returning
+/// `Result<U, E>` if `E: From<()>`, for example.
+///
+/// # Examples
+///
+/// A `Poll`-style enum can be used with `?` by implementing `Carrier`:
+///
+/// ```
+/// #![feature(question_mark, question_mark_carrier)]
+///
+/// use std::ops::Carrier;
+///
+/// enum Poll<T, E> {
+///     Ready(T),
+///     NotReady,
+///     Failed(E),
+/// }
+///
+/// impl<T, E> Carrier for Poll<T, E> {
+///     type Success = Option<T>;
+///     type Error = E;
+///
+///     fn from_success(value: Option<T>) -> Self {
+///         match value {
+///             Some(value) => Poll::Ready(value),
+///             None => Poll::NotReady,
+///         }
+///     }
+///
+///     fn from_error(error: E) -> Self {
+///         Poll::Failed(error)
+///     }
+///
+///     fn translate<C>(self) -> C where C: Carrier<Success=Option<T>, Error=E> {
+///         match self {
+///             Poll::Ready(value) => C::from_success(Some(value)),
+///             Poll::NotReady => C::from_success(None),
+///             Poll::Failed(error) => C::from_error(error),
+///         }
+///     }
+/// }
+///
+/// fn double(p: Poll<u32, String>) -> Poll<u32, String> {
+///     match p? {
+///         Some(value) => Poll::Ready(value * 2),
+///         None => Poll::NotReady,
+///     }
+/// }
+///
+/// # fn main() {}
+/// ```
+#[unstable(feature = "question_mark_carrier", issue = "31436")]
+#[rustc_on_unimplemented = "the `?` operator can only be used with types that implement \
+                            `Carrier` (such as `Result` and `Option`), both for its operand \
+                            and for the return type of the enclosing function, but `{Self}` \
+                            does not implement it"]
+pub trait Carrier {
+    /// The type of the value produced by `?` when it does not return early.
+    type Success;
+
+    /// The type of the value propagated by `?` when it returns early.
+    type Error;
+
+    /// Creates a carrier holding a successful value.
+    fn from_success(value: Self::Success) -> Self;
+
+    /// Creates a carrier holding an error value.
+    fn from_error(error: Self::Error) -> Self;
+
+    /// Converts this carrier into another one with the same success and
+    /// error types.
+    fn translate<T>(self) -> T where T: Carrier<Success=Self::Success, Error=Self::Error>;
+}
+
+#[unstable(feature = "question_mark_carrier", issue = "31436")]
+impl<U, V> Carrier for Result<U, V> {
+    type Success = U;
+    type Error = V;
+
+    fn from_success(value: U) -> Self {
+        Ok(value)
+    }
+
+    fn from_error(error: V) -> Self {
+        Err(error)
+    }
+
+    fn translate<T>(self) -> T where T: Carrier<Success=U, Error=V> {
+        match self {
+            Ok(value) => T::from_success(value),
+            Err(error) => T::from_error(error),
+        }
+    }
+}
+
+/// `None` carries no information, so `?` on an `Option` propagates `()`.
+#[unstable(feature = "question_mark_carrier", issue = "31436")]
+impl<U> Carrier for Option<U> {
+    type Success = U;
+    type Error = ();
+
+    fn from_success(value: U) -> Self {
+        Some(value)
+    }
+
+    fn from_error(_: ()) -> Self {
+        None
+    }
+
+    fn translate<T>(self) -> T where T: Carrier<Success=U, Error=()> {
+        match self {
+            Some(value) => T::from_success(value),
+            None => T::from_error(()),
+        }
+    }
+}
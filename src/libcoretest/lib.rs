@@ -28,7 +28,7 @@
 #![feature(peekable_is_empty)]
 #![feature(rand)]
 #![feature(raw)]
-#![feature(slice_patterns)]
+#![cfg_attr(stage0, feature(slice_patterns))]
 #![feature(step_by)]
 #![feature(test)]
 #![feature(unboxed_closures)]
@@ -144,6 +144,9 @@ pub struct Parser<'a> {
     cur: iter::Peekable<str::CharIndices<'a>>,
     /// Error messages accumulated during parsing
     pub errors: Vec<string::String>,
+    /// The byte range of each argument, from `{` to `}` inclusive, in the
+    /// order the arguments were parsed
+    pub arg_places: Vec<(usize, usize)>,
 }
 
 impl<'a> Iterator for Parser<'a> {
@@ -159,6 +162,8 @@ impl<'a> Iterator for Parser<'a> {
                     } else {
                         let ret = Some(NextArgument(self.argument()));
                         self.must_consume('}');
+                        let end = self.cur.peek().map_or(self.input.len(), |&(i, _)| i);
+                        self.arg_places.push((pos, end));
                         ret
                     }
                 }
@@ -186,6 +191,7 @@ impl<'a> Parser<'a> {
             input: s,
             cur: s.char_indices().peekable(),
             errors: vec![],
+            arg_places: vec![],
         }
     }
 
@@ -668,4 +674,10 @@ mod tests {
                }),
                String(" efg")]);
     }
+    #[test]
+    fn arg_places() {
+        let mut p = Parser::new("a {} {{ {0:>5} é {foo}");
+        while let Some(_) = p.next() {}
+        assert_eq!(p.arg_places, [(2, 4), (8, 14), (18, 23)]);
+    }
 }
@@ -0,0 +1,214 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A support library for macro authors when defining new macros.
+//!
+//! This library, provided by the standard distribution, provides the types
+//! consumed in the interfaces of procedurally defined macro definitions.
+//! Currently the primary use of this crate is to provide the ability to
+//! define new custom derive modes through `#[proc_macro_derive]`:
+//!
+//! ```ignore
+//! #![crate_type = "proc-macro"]
+//! #![feature(proc_macro, proc_macro_lib)]
+//!
+//! extern crate proc_macro;
+//!
+//! use proc_macro::TokenStream;
+//!
+//! #[proc_macro_derive(Double)]
+//! pub fn derive_double(input: TokenStream) -> TokenStream {
+//!     let source = input.to_string();
+//!     // ... inspect `source` and build the impl ...
+//!     "impl Double for Foo { fn double(&self) -> Foo { ... } }".parse().unwrap()
+//! }
+//! ```
+//!
+//! Note that this crate is intentionally very bare-bones. The main type,
+//! `TokenStream`, only supports `fmt::Display` and `FromStr`
+//! implementations, indicating that it can only go to and come from a
+//! string. In particular no compiler-internal AST is exposed, so macros
+//! written against this interface do not break whenever the compiler's
+//! internals change.
+
+#![crate_name = "proc_macro"]
+#![unstable(feature = "proc_macro_lib", issue = "35900")]
+#![crate_type = "rlib"]
+#![crate_type = "dylib"]
+#![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk-v2.png",
+       html_favicon_url = "https://doc.rust-lang.org/favicon.ico",
+       html_root_url = "https://doc.rust-lang.org/nightly/")]
+#![cfg_attr(not(stage0), deny(warnings))]
+#![deny(missing_docs)]
+
+#![feature(staged_api)]
+
+use std::fmt;
+use std::str::FromStr;
+
+/// The main type provided by this crate, representing an abstract stream of
+/// tokens.
+///
+/// This is both the input and output of `#[proc_macro_derive]`
+/// definitions. Currently this type can be converted to and from a string,
+/// and that is all. The string form of the input of a custom derive is the
+/// item the derive was applied to, with the `#[derive]` attribute itself
+/// removed; the output is a sequence of items that is added next to it.
+pub struct TokenStream {
+    source: String,
+}
+
+/// Error returned from `TokenStream::from_str`.
+#[derive(Debug)]
+pub struct LexError {
+    _inner: (),
+}
+
+impl FromStr for TokenStream {
+    type Err = LexError;
+
+    /// Creates a token stream from source text.
+    ///
+    /// Only the delimiters of the source are checked here: an unbalanced
+    /// `(`, `[` or `{`, or an unterminated string literal or block comment,
+    /// is reported as a `LexError`. The tokens themselves are only checked
+    /// once the stream has been handed back to the compiler.
+    fn from_str(src: &str) -> Result<TokenStream, LexError> {
+        if delimiters_balanced(src) {
+            Ok(TokenStream { source: src.to_string() })
+        } else {
+            Err(LexError { _inner: () })
+        }
+    }
+}
+
+impl fmt::Display for TokenStream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.source.fmt(f)
+    }
+}
+
+fn delimiters_balanced(src: &str) -> bool {
+    let mut open = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | '[' | '{' => open.push(c),
+            ')' | ']' | '}' => {
+                let expected = match c {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if open.pop() != Some(expected) {
+                    return false;
+                }
+            }
+            '"' => {
+                // a string literal; raw strings are not special-cased, which
+                // is fine as long as they contain no `"` themselves
+                loop {
+                    match chars.next() {
+                        Some('\\') => { chars.next(); }
+                        Some('"') => break,
+                        Some(_) => {}
+                        None => return false,
+                    }
+                }
+            }
+            '\'' => {
+                // either a char literal or a lifetime; only the former can
+                // contain delimiters
+                match chars.next() {
+                    Some('\\') => {
+                        loop {
+                            match chars.next() {
+                                Some('\'') => break,
+                                Some(_) => {}
+                                None => return false,
+                            }
+                        }
+                    }
+                    Some(_) => {
+                        if chars.peek() == Some(&'\'') {
+                            chars.next();
+                        }
+                    }
+                    None => return false,
+                }
+            }
+            '/' => {
+                match chars.peek() {
+                    Some(&'/') => {
+                        while let Some(c) = chars.next() {
+                            if c == '\n' {
+                                break
+                            }
+                        }
+                    }
+                    Some(&'*') => {
+                        chars.next();
+                        let mut depth = 1;
+                        let mut prev = ' ';
+                        while depth > 0 {
+                            let c = match chars.next() {
+                                Some(c) => c,
+                                None => return false,
+                            };
+                            if prev == '/' && c == '*' {
+                                depth += 1;
+                                prev = ' ';
+                            } else if prev == '*' && c == '/' {
+                                depth -= 1;
+                                prev = ' ';
+                            } else {
+                                prev = c;
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    open.is_empty()
+}
+
+/// Permanently unstable internal implementation details of this crate. This
+/// should not be used.
+///
+/// This is the interface between the compiler and the registrar function
+/// it generates for each `proc-macro` crate. It is kept in a separate module
+/// so that the `#[unstable]` directive below naturally applies to all of its
+/// contents.
+#[unstable(feature = "proc_macro_internals", issue = "27812")]
+#[doc(hidden)]
+pub mod __internal {
+    use TokenStream;
+
+    /// The interface through which a `proc-macro` crate hands its derive
+    /// modes to the compiler.
+    ///
+    /// Every `proc-macro` crate contains a compiler-generated registrar
+    /// function taking a `&mut Registry`, which calls
+    /// `register_custom_derive` once for each `#[proc_macro_derive]`
+    /// function in the crate.
+    pub trait Registry {
+        /// Registers `expand` as the implementation of
+        /// `#[derive(trait_name)]`.
+        fn register_custom_derive(&mut self,
+                                  trait_name: &str,
+                                  expand: fn(TokenStream) -> TokenStream);
+    }
+
+    /// The type of the registrar function generated for `proc-macro` crates.
+    pub type DeriveRegistrarFun = fn(&mut Registry);
+}
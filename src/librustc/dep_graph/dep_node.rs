@@ -32,6 +32,12 @@ pub enum DepNode<D: Clone + Debug> {
     // Represents the HIR node with the given node-id
     Hir(D),
 
+    // Represents a file or an environment variable read by a macro such
+    // as `include_str!` or `env!`, identified by a hash of its path or
+    // name. The `Hir` node of the item the macro was expanded in reads
+    // it, so a change to it only invalidates that item.
+    MacroInput(u64),
+
     // Represents different phases in the compiler.
     CrateReader,
     CollectLanguageItems,
@@ -40,6 +46,7 @@ pub enum DepNode<D: Clone + Debug> {
     RegionResolveCrate,
     CheckLoops,
     PluginRegistrar,
+    DeriveRegistrar,
     StabilityIndex,
     CollectItem(D),
     Coherence,
@@ -147,6 +154,7 @@ impl<D: Clone + Debug> DepNode<D> {
 
         match *self {
             Krate => Some(Krate),
+            MacroInput(id) => Some(MacroInput(id)),
             CrateReader => Some(CrateReader),
             CollectLanguageItems => Some(CollectLanguageItems),
             CheckStaticRecursion => Some(CheckStaticRecursion),
@@ -154,6 +162,7 @@ impl<D: Clone + Debug> DepNode<D> {
             RegionResolveCrate => Some(RegionResolveCrate),
             CheckLoops => Some(CheckLoops),
             PluginRegistrar => Some(PluginRegistrar),
+            DeriveRegistrar => Some(DeriveRegistrar),
             StabilityIndex => Some(StabilityIndex),
             Coherence => Some(Coherence),
             EffectCheck => Some(EffectCheck),
@@ -876,6 +876,7 @@ pub fn lower_macro_def(lctx: &LoweringContext, m: &MacroDef) -> hir::MacroDef {
         use_locally: m.use_locally,
         allow_internal_unstable: m.allow_internal_unstable,
         body: m.body.clone().into(),
+        scope: m.scope.clone(),
     }
 }
 
@@ -1386,6 +1387,7 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
                     alignstack,
                     dialect,
                     expn_id,
+                    ref operand_classes,
                 }) => hir::ExprInlineAsm(hir::InlineAsm {
                 inputs: inputs.iter().map(|&(ref c, _)| c.clone()).collect(),
                 outputs: outputs.iter()
@@ -1404,6 +1406,7 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
                 alignstack: alignstack,
                 dialect: dialect,
                 expn_id: expn_id,
+                operand_classes: operand_classes.clone().into(),
             }, outputs.iter().map(|out| lower_expr(lctx, &out.expr)).collect(),
                inputs.iter().map(|&(_, ref input)| lower_expr(lctx, input)).collect()),
             ExprKind::Struct(ref path, ref fields, ref maybe_expr) => {
@@ -1682,10 +1685,10 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
                 // to:
                 //
                 // {
-                //     match <expr> {
+                //     match { Carrier::translate( { <expr> } ) } {
                 //         Ok(val) => val,
                 //         Err(err) => {
-                //             return Err(From::from(err))
+                //             return Carrier::from_error(From::from(err))
                 //         }
                 //     }
                 // }
@@ -1694,6 +1697,22 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
                     // expand <expr>
                     let sub_expr = lower_expr(lctx, sub_expr);
 
+                    // { Carrier::translate( { <expr> } ) }
+                    let discr = {
+                        let sub_expr = signal_block_expr(lctx,
+                                                         hir_vec![],
+                                                         sub_expr,
+                                                         e.span,
+                                                         hir::PopUnstableBlock,
+                                                         None);
+
+                        let path = std_path(lctx, &["ops", "Carrier", "translate"]);
+                        let path = path_global(e.span, path);
+                        let translate = expr_path(lctx, path, None);
+
+                        expr_call(lctx, e.span, translate, hir_vec![sub_expr], None)
+                    };
+
                     // Ok(val) => val
                     let ok_arm = {
                         let val_ident = lctx.str_to_ident("val");
@@ -1704,7 +1723,7 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
                         arm(hir_vec![ok_pat], val_expr)
                     };
 
-                    // Err(err) => return Err(From::from(err))
+                    // Err(err) => return Carrier::from_error(From::from(err))
                     let err_arm = {
                         let err_ident = lctx.str_to_ident("err");
                         let from_expr = {
@@ -1715,26 +1734,38 @@ pub fn lower_expr(lctx: &LoweringContext, e: &Expr) -> P<hir::Expr> {
 
                             expr_call(lctx, e.span, from, hir_vec![err_expr], None)
                         };
-                        let err_expr = {
-                            let path = std_path(lctx, &["result", "Result", "Err"]);
+                        let from_err_expr = {
+                            let path = std_path(lctx, &["ops", "Carrier", "from_error"]);
                             let path = path_global(e.span, path);
-                            let err_ctor = expr_path(lctx, path, None);
-                            expr_call(lctx, e.span, err_ctor, hir_vec![from_expr], None)
+                            let from_err = expr_path(lctx, path, None);
+                            expr_call(lctx, e.span, from_err, hir_vec![from_expr], None)
                         };
                         let err_pat = pat_err(lctx, e.span,
                                               pat_ident(lctx, e.span, err_ident));
                         let ret_expr = expr(lctx, e.span,
-                                            hir::Expr_::ExprRet(Some(err_expr)), None);
+                                            hir::Expr_::ExprRet(Some(from_err_expr)), None);
 
                         arm(hir_vec![err_pat], ret_expr)
                     };
 
-                    expr_match(lctx, e.span, sub_expr, hir_vec![err_arm, ok_arm],
-                               hir::MatchSource::TryDesugar, None)
+                    let match_expr = expr_match(lctx, e.span, discr, hir_vec![err_arm, ok_arm],
+                                                hir::MatchSource::TryDesugar, None);
+
+                    signal_block_expr(lctx,
+                                      hir_vec![],
+                                      match_expr,
+                                      e.span,
+                                      hir::PushUnstableBlock,
+                                      None)
                 })
             }
 
             ExprKind::Mac(_) => panic!("Shouldn't exist here"),
+            ExprKind::Err => {
+                // `phase_2_configure_and_expand` stops before lowering a crate
+                // recovered from syntax errors.
+                span_bug!(e.span, "error node from parser recovery in lowering")
+            }
         },
         span: e.span,
         attrs: e.attrs.clone(),
@@ -39,8 +39,8 @@ use util::nodemap::{NodeMap, FnvHashSet};
 
 use syntax::codemap::{self, Span, Spanned, DUMMY_SP, ExpnId};
 use syntax::abi::Abi;
-use syntax::ast::{Name, NodeId, DUMMY_NODE_ID, TokenTree, AsmDialect};
-use syntax::ast::{Attribute, Lit, StrStyle, FloatTy, IntTy, UintTy, MetaItem};
+use syntax::ast::{Name, NodeId, DUMMY_NODE_ID, TokenTree, AsmDialect, AsmOperandClass};
+use syntax::ast::{Attribute, Lit, StrStyle, FloatTy, IntTy, UintTy, MetaItem, MacroScope};
 use syntax::attr::{ThinAttributes, ThinAttributesExt};
 use syntax::parse::token::InternedString;
 use syntax::ptr::P;
@@ -496,6 +496,7 @@ pub struct MacroDef {
     pub use_locally: bool,
     pub allow_internal_unstable: bool,
     pub body: HirVec<TokenTree>,
+    pub scope: Option<MacroScope>,
 }
 
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
@@ -1184,6 +1185,7 @@ pub struct InlineAsm {
     pub alignstack: bool,
     pub dialect: AsmDialect,
     pub expn_id: ExpnId,
+    pub operand_classes: HirVec<AsmOperandClass>,
 }
 
 /// represents an argument in a function header
@@ -326,11 +326,27 @@ pub fn visibility_qualified(vis: &hir::Visibility, s: &str) -> String {
     match *vis {
         hir::Public => format!("pub {}", s),
         hir::Visibility::Crate => format!("pub(crate) {}", s),
-        hir::Visibility::Restricted { ref path, .. } => format!("pub({}) {}", path, s),
+        hir::Visibility::Restricted { ref path, .. } => {
+            format!("{} {}", restricted_visibility_to_string(path), s)
+        }
         hir::Inherited => s.to_string(),
     }
 }
 
+/// Prints `pub(self)` and `pub(super)` in their short form and any other
+/// restriction as `pub(in path)`.
+fn restricted_visibility_to_string(path: &hir::Path) -> String {
+    let is_keyword = !path.global && path.segments.len() == 1 && {
+        let name = path.segments[0].identifier.name;
+        name == token::keywords::SelfValue.to_name() || name == token::keywords::Super.to_name()
+    };
+    if is_keyword {
+        format!("pub({})", path)
+    } else {
+        format!("pub(in {})", path)
+    }
+}
+
 fn needs_parentheses(expr: &hir::Expr) -> bool {
     match expr.node {
         hir::ExprAssign(..) |
@@ -902,7 +918,7 @@ impl<'a> State<'a> {
             hir::Public => self.word_nbsp("pub"),
             hir::Visibility::Crate => self.word_nbsp("pub(crate)"),
             hir::Visibility::Restricted { ref path, .. } =>
-                self.word_nbsp(&format!("pub({})", path)),
+                self.word_nbsp(&restricted_visibility_to_string(path)),
             hir::Inherited => Ok(()),
         }
     }
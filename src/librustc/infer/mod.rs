@@ -1019,6 +1019,17 @@ impl<'a, 'tcx> InferCtxt<'a, 'tcx> {
         ty::ReVar(self.region_vars.new_region_var(origin))
     }
 
+    pub fn num_region_vars(&self) -> usize {
+        self.region_vars.num_vars() as usize
+    }
+
+    /// See `RegionVarBindings::take_and_reset_constraints`.
+    pub fn take_and_reset_region_constraints(&self)
+                                             -> FnvHashMap<region_inference::Constraint,
+                                                           SubregionOrigin<'tcx>> {
+        self.region_vars.take_and_reset_constraints()
+    }
+
     pub fn region_vars_for_defs(&self,
                                 span: Span,
                                 defs: &[ty::RegionParameterDef])
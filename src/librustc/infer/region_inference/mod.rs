@@ -33,6 +33,7 @@ use util::nodemap::{FnvHashMap, FnvHashSet};
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering::{self, Less, Greater, Equal};
 use std::fmt;
+use std::mem;
 use std::u32;
 use syntax::ast;
 
@@ -342,6 +343,15 @@ impl<'a, 'tcx> RegionVarBindings<'a, 'tcx> {
             .rollback_to(snapshot.region_snapshot);
     }
 
+    /// Removes and returns all the constraints recorded so far. This is
+    /// used by the MIR-based region inference (`-Z nll`), which relates
+    /// types one statement at a time and tags each resulting constraint
+    /// with the MIR point it arose at, instead of solving them here.
+    pub fn take_and_reset_constraints(&self) -> FnvHashMap<Constraint, SubregionOrigin<'tcx>> {
+        assert!(!self.in_snapshot());
+        mem::replace(&mut *self.constraints.borrow_mut(), FnvHashMap())
+    }
+
     pub fn num_vars(&self) -> u32 {
         let len = self.var_origins.borrow().len();
         // enforce no overflow
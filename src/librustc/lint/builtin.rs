@@ -34,6 +34,12 @@ declare_lint! {
     "extern crates that are never used"
 }
 
+declare_lint! {
+    pub UNUSED_EXTERN_OPTIONS,
+    Allow,
+    "`--extern` crates passed on the command line that are never used"
+}
+
 declare_lint! {
     pub UNUSED_QUALIFICATIONS,
     Allow,
@@ -185,6 +191,12 @@ declare_lint! {
     "detects super or self keywords at the beginning of global path"
 }
 
+declare_lint! {
+    pub UNEXPECTED_CFGS,
+    Warn,
+    "detects cfg names and values that aren't expected by `--check-cfg`"
+}
+
 /// Does nothing as a lint pass, but registers some `Lint`s
 /// which are used by other parts of the compiler.
 #[derive(Copy, Clone)]
@@ -195,6 +207,7 @@ impl LintPass for HardwiredLints {
         lint_array!(
             UNUSED_IMPORTS,
             UNUSED_EXTERN_CRATES,
+            UNUSED_EXTERN_OPTIONS,
             UNUSED_QUALIFICATIONS,
             UNKNOWN_LINTS,
             UNUSED_VARIABLES,
@@ -220,7 +233,8 @@ impl LintPass for HardwiredLints {
             TRANSMUTE_FROM_FN_ITEM_TYPES,
             OVERLAPPING_INHERENT_IMPLS,
             RENAMED_AND_REMOVED_LINTS,
-            SUPER_OR_SELF_IN_GLOBAL_PATH
+            SUPER_OR_SELF_IN_GLOBAL_PATH,
+            UNEXPECTED_CFGS
         )
     }
 }
@@ -29,7 +29,7 @@ use dep_graph::DepNode;
 use middle::privacy::AccessLevels;
 use ty::TyCtxt;
 use session::{config, early_error, Session};
-use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass};
+use lint::{Level, LevelSource, Lint, LintId, LintArray, LintPass, EarlyLint};
 use lint::{EarlyLintPassObject, LateLintPass, LateLintPassObject};
 use lint::{Default, CommandLine, Node, Allow, Warn, Deny, Forbid};
 use lint::builtin;
@@ -41,8 +41,9 @@ use std::default::Default as StdDefault;
 use std::mem;
 use syntax::attr::{self, AttrMetaMethods};
 use syntax::codemap::Span;
-use syntax::errors::DiagnosticBuilder;
+use syntax::errors::{self, DiagnosticBuilder};
 use syntax::parse::token::InternedString;
+use syntax::util::lev_distance::find_best_match_for_str;
 use syntax::ast;
 use syntax::attr::ThinAttributesExt;
 use hir;
@@ -536,6 +537,24 @@ pub trait LintContext: Sized {
         self.lookup(lint, Some(span), msg)
     }
 
+    /// Emit a lint that was buffered in the session, with its notes and help.
+    fn early_lint(&self, early_lint: EarlyLint) {
+        let lint = early_lint.id.lint;
+        let mut err = self.lookup(lint, Some(early_lint.span), &early_lint.msg);
+        if self.current_level(lint) != Level::Allow {
+            for (level, span, msg) in early_lint.children {
+                match (level, span) {
+                    (errors::Level::Note, Some(span)) => err.span_note(span, &msg),
+                    (errors::Level::Note, None) => err.note(&msg),
+                    (errors::Level::Help, Some(span)) => err.span_help(span, &msg),
+                    (errors::Level::Help, None) => err.help(&msg),
+                    _ => bug!("unexpected sub-diagnostic level {:?}", level),
+                };
+            }
+        }
+        err.emit();
+    }
+
     /// Emit a lint and note at the appropriate level, for a particular span.
     fn span_lint_note(&self, lint: &'static Lint, span: Span, msg: &str,
                       note_span: Span, note: &str) {
@@ -1078,8 +1097,8 @@ impl<'a, 'tcx> IdVisitingOperation for LateContext<'a, 'tcx> {
             None => {}
             Some(lints) => {
                 debug!("LateContext::visit_id: id={:?} lints={:?}", id, lints);
-                for (lint_id, span, msg) in lints {
-                    self.span_lint(lint_id.lint, span, &msg[..])
+                for early_lint in lints {
+                    self.early_lint(early_lint);
                 }
             }
         }
@@ -1120,8 +1139,9 @@ impl LateLintPass for GatherNodeLevels {
 
 enum CheckLintNameResult {
     Ok,
-    // Lint doesn't exist
-    NoLint,
+    // Lint doesn't exist. This is the name of a similarly named lint or
+    // lint group, if there is one.
+    NoLint(Option<String>),
     // The lint is either renamed or removed. This is the warning
     // message.
     Warning(String)
@@ -1150,7 +1170,14 @@ fn check_lint_name(lint_cx: &LintStore,
         None => {
             match lint_cx.lint_groups.get(lint_name) {
                 None => {
-                    CheckLintNameResult::NoLint
+                    let names = lint_cx.by_name.iter().filter_map(|(name, target)| {
+                        match *target {
+                            Id(_) => Some(&name[..]),
+                            Renamed(..) | Removed(..) => None,
+                        }
+                    }).chain(lint_cx.lint_groups.keys().map(|&name| name));
+                    CheckLintNameResult::NoLint(find_best_match_for_str(names, lint_name, None)
+                                                    .map(|name| name.to_string()))
                 }
                 Some(_) => {
                     /* lint group exists */
@@ -1181,11 +1208,17 @@ fn check_lint_name_attribute(cx: &LateContext, attr: &ast::Attribute) {
                         cx.span_lint(builtin::RENAMED_AND_REMOVED_LINTS,
                                      span, msg);
                     }
-                    CheckLintNameResult::NoLint => {
+                    CheckLintNameResult::NoLint(None) => {
                         cx.span_lint(builtin::UNKNOWN_LINTS, span,
                                      &format!("unknown lint: `{}`",
                                               lint_name));
                     }
+                    CheckLintNameResult::NoLint(Some(ref suggestion)) => {
+                        cx.span_lint_note(builtin::UNKNOWN_LINTS, span,
+                                          &format!("unknown lint: `{}`", lint_name),
+                                          span,
+                                          &format!("did you mean `{}`?", suggestion));
+                    }
                 }
             }
         }
@@ -1200,8 +1233,12 @@ fn check_lint_name_cmdline(sess: &Session, lint_cx: &LintStore,
         CheckLintNameResult::Warning(ref msg) => {
             Some(sess.struct_warn(msg))
         },
-        CheckLintNameResult::NoLint => {
-            Some(sess.struct_err(&format!("unknown lint: `{}`", lint_name)))
+        CheckLintNameResult::NoLint(ref suggestion) => {
+            let mut db = sess.struct_err(&format!("unknown lint: `{}`", lint_name));
+            if let Some(ref suggestion) = *suggestion {
+                db.help(&format!("did you mean `{}`?", suggestion));
+            }
+            Some(db)
         }
     };
 
@@ -1248,10 +1285,10 @@ pub fn check_crate(tcx: &TyCtxt, access_levels: &AccessLevels) {
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (id, v) in tcx.sess.lints.borrow().iter() {
-        for &(lint, span, ref msg) in v {
-            span_bug!(span,
+        for early_lint in v {
+            span_bug!(early_lint.span,
                       "unprocessed lint {} at {}: {}",
-                      lint.as_str(), tcx.map.node_to_string(*id), *msg)
+                      early_lint.id.as_str(), tcx.map.node_to_string(*id), early_lint.msg)
         }
     }
 
@@ -1268,8 +1305,8 @@ pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
     cx.with_lint_attrs(&krate.attrs, |cx| {
         // Lints may be assigned to the whole crate.
         if let Some(lints) = cx.sess.lints.borrow_mut().remove(&ast::CRATE_NODE_ID) {
-            for (lint_id, span, msg) in lints {
-                cx.span_lint(lint_id.lint, span, &msg[..])
+            for early_lint in lints {
+                cx.early_lint(early_lint);
             }
         }
 
@@ -1288,8 +1325,10 @@ pub fn check_ast_crate(sess: &Session, krate: &ast::Crate) {
     // If we missed any lints added to the session, then there's a bug somewhere
     // in the iteration code.
     for (_, v) in sess.lints.borrow().iter() {
-        for &(lint, span, ref msg) in v {
-            span_bug!(span, "unprocessed lint {}: {}", lint.as_str(), *msg)
+        for early_lint in v {
+            span_bug!(early_lint.span,
+                      "unprocessed lint {}: {}",
+                      early_lint.id.as_str(), early_lint.msg)
         }
     }
 }
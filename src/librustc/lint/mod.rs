@@ -34,6 +34,7 @@ pub use self::LintSource::*;
 use std::hash;
 use std::ascii::AsciiExt;
 use syntax::codemap::Span;
+use syntax::errors;
 use hir::intravisit::FnKind;
 use syntax::visit as ast_visit;
 use syntax::ast;
@@ -271,6 +272,23 @@ impl LintId {
     }
 }
 
+/// A lint added to the session before lint levels are known. It is emitted
+/// by the lint checking pass together with its notes and help messages.
+#[derive(Clone, PartialEq, Debug)]
+pub struct EarlyLint {
+    pub id: LintId,
+    pub span: Span,
+    pub msg: String,
+    /// Sub-diagnostics (notes or help) to attach when the lint is emitted.
+    pub children: Vec<(errors::Level, Option<Span>, String)>,
+}
+
+impl EarlyLint {
+    pub fn new(lint: &'static Lint, span: Span, msg: String) -> EarlyLint {
+        EarlyLint { id: LintId::of(lint), span: span, msg: msg, children: vec![] }
+    }
+}
+
 /// Setting for how to handle a lint.
 #[derive(Clone, Copy, PartialEq, PartialOrd, Eq, Ord, Debug)]
 pub enum Level {
@@ -271,6 +271,8 @@ pub trait CrateStore<'tcx> : Any {
     fn used_crates(&self, prefer: LinkagePreference) -> Vec<(ast::CrateNum, Option<PathBuf>)>;
     fn used_crate_source(&self, cnum: ast::CrateNum) -> CrateSource;
     fn extern_mod_stmt_cnum(&self, emod_id: ast::NodeId) -> Option<ast::CrateNum>;
+    /// Whether one of the macros or plugins of the crate named `name` was used.
+    fn is_used_extension_crate(&self, name: &str) -> bool;
     fn encode_metadata(&self,
                        tcx: &TyCtxt<'tcx>,
                        reexports: &def::ExportMap,
@@ -469,6 +471,7 @@ impl<'tcx> CrateStore<'tcx> for DummyCrateStore {
         { vec![] }
     fn used_crate_source(&self, cnum: ast::CrateNum) -> CrateSource { bug!("used_crate_source") }
     fn extern_mod_stmt_cnum(&self, emod_id: ast::NodeId) -> Option<ast::CrateNum> { None }
+    fn is_used_extension_crate(&self, name: &str) -> bool { false }
     fn encode_metadata(&self,
                        tcx: &TyCtxt<'tcx>,
                        reexports: &def::ExportMap,
@@ -18,6 +18,7 @@ use hir::{self, pat_util, PatKind};
 use hir::intravisit::{self, Visitor};
 
 use middle::privacy;
+use session::config;
 use ty::{self, TyCtxt};
 use hir::def::Def;
 use hir::def_id::{DefId};
@@ -47,6 +48,7 @@ struct MarkSymbolVisitor<'a, 'tcx: 'a> {
     worklist: Vec<ast::NodeId>,
     tcx: &'a TyCtxt<'tcx>,
     live_symbols: Box<HashSet<ast::NodeId>>,
+    any_library: bool,
     struct_has_extern_repr: bool,
     ignore_non_const_paths: bool,
     inherited_pub_visibility: bool,
@@ -55,11 +57,13 @@ struct MarkSymbolVisitor<'a, 'tcx: 'a> {
 
 impl<'a, 'tcx> MarkSymbolVisitor<'a, 'tcx> {
     fn new(tcx: &'a TyCtxt<'tcx>,
+           any_library: bool,
            worklist: Vec<ast::NodeId>) -> MarkSymbolVisitor<'a, 'tcx> {
         MarkSymbolVisitor {
             worklist: worklist,
             tcx: tcx,
             live_symbols: box HashSet::new(),
+            any_library: any_library,
             struct_has_extern_repr: false,
             ignore_non_const_paths: false,
             inherited_pub_visibility: false,
@@ -221,8 +225,11 @@ impl<'a, 'tcx, 'v> Visitor<'v> for MarkSymbolVisitor<'a, 'tcx> {
                         _: &hir::Generics, _: ast::NodeId, _: codemap::Span) {
         let has_extern_repr = self.struct_has_extern_repr;
         let inherited_pub_visibility = self.inherited_pub_visibility;
+        // `pub` fields of live types may be used by other crates, unless we're
+        // building an executable.
+        let any_library = self.any_library;
         let live_fields = def.fields().iter().filter(|f| {
-            has_extern_repr || inherited_pub_visibility || f.vis == hir::Public
+            has_extern_repr || any_library && (inherited_pub_visibility || f.vis == hir::Public)
         });
         self.live_symbols.extend(live_fields.map(|f| f.id));
 
@@ -308,6 +315,10 @@ fn has_allow_dead_code_or_lang_attr(attrs: &[ast::Attribute]) -> bool {
     false
 }
 
+fn exports_symbol(attrs: &[ast::Attribute]) -> bool {
+    attr::contains_name(attrs, "no_mangle") || attr::contains_name(attrs, "export_name")
+}
+
 // This visitor seeds items that
 //   1) We want to explicitly consider as live:
 //     * Item annotated with #[allow(dead_code)]
@@ -318,6 +329,12 @@ fn has_allow_dead_code_or_lang_attr(attrs: &[ast::Attribute]) -> bool {
 //           warning for both `f` and `g`.
 //     * Item annotated with #[lang=".."]
 //         - This is because lang items are always callable from elsewhere.
+//     * Item annotated with #[no_mangle] or #[export_name=".."]
+//         - These export a symbol, so they can be used from outside the
+//           crate even in an executable.
+//     * `pub` item in an `extern` block
+//         - These declare the interface to foreign code and are kept even
+//           when the crate doesn't call them.
 //   or
 //   2) We are not sure to be live or not
 //     * Implementation of a trait method
@@ -328,10 +345,15 @@ struct LifeSeeder {
 impl<'v> Visitor<'v> for LifeSeeder {
     fn visit_item(&mut self, item: &hir::Item) {
         let allow_dead_code = has_allow_dead_code_or_lang_attr(&item.attrs);
-        if allow_dead_code {
+        if allow_dead_code || exports_symbol(&item.attrs) {
             self.worklist.push(item.id);
         }
         match item.node {
+            hir::ItemForeignMod(ref foreign_mod) => {
+                self.worklist.extend(foreign_mod.items.iter()
+                                                      .filter(|fi| fi.vis == hir::Public)
+                                                      .map(|fi| fi.id));
+            }
             hir::ItemEnum(ref enum_def, _) if allow_dead_code => {
                 self.worklist.extend(enum_def.variants.iter()
                                                       .map(|variant| variant.node.data.id()));
@@ -352,7 +374,8 @@ impl<'v> Visitor<'v> for LifeSeeder {
             hir::ItemImpl(_, _, _, ref opt_trait, _, ref impl_items) => {
                 for impl_item in impl_items {
                     if opt_trait.is_some() ||
-                            has_allow_dead_code_or_lang_attr(&impl_item.attrs) {
+                            has_allow_dead_code_or_lang_attr(&impl_item.attrs) ||
+                            exports_symbol(&impl_item.attrs) {
                         self.worklist.push(impl_item.id);
                     }
                 }
@@ -389,8 +412,18 @@ fn find_live(tcx: &TyCtxt,
              access_levels: &privacy::AccessLevels,
              krate: &hir::Crate)
              -> Box<HashSet<ast::NodeId>> {
+    // Nothing in an executable can be used by other crates, so an item being
+    // `pub` (or reexported) doesn't make it live there. A test harness is an
+    // executable too, but it's built from a crate whose API is meant to be
+    // used by others.
+    let any_library = tcx.sess.opts.test || tcx.sess.crate_types.borrow().iter().any(|ty| {
+        *ty != config::CrateTypeExecutable
+    });
+    let no_access_levels = privacy::AccessLevels::default();
+    let access_levels = if any_library { access_levels } else { &no_access_levels };
+
     let worklist = create_and_seed_worklist(tcx, access_levels, krate);
-    let mut symbol_visitor = MarkSymbolVisitor::new(tcx, worklist);
+    let mut symbol_visitor = MarkSymbolVisitor::new(tcx, any_library, worklist);
     symbol_visitor.mark_live_symbols();
     symbol_visitor.live_symbols
 }
@@ -141,8 +141,15 @@ fn calculate_type(sess: &session::Session,
             }
         }
 
+        // Procedural macro crates are loaded into the compiler itself, so
+        // they always link dynamically to the same libstd and libproc_macro
+        // the compiler uses; values like `TokenStream` are passed back and
+        // forth between the two.
+        //
         // Everything else falls through below
-        config::CrateTypeExecutable | config::CrateTypeDylib => {},
+        config::CrateTypeExecutable |
+        config::CrateTypeDylib |
+        config::CrateTypeProcMacro => {},
     }
 
     let mut formats = FnvHashMap();
@@ -1013,10 +1013,18 @@ impl<'d,'t,'a,'tcx> ExprUseVisitor<'d,'t,'a,'tcx> {
             } else {
                 match pat.node {
                     PatKind::Vec(_, Some(ref slice_pat), _) => {
-                        // The `slice_pat` here creates a slice into
-                        // the original vector.  This is effectively a
-                        // borrow of the elements of the vector being
-                        // matched.
+                        // A bare `..` binds nothing, so it neither
+                        // borrows nor moves the elements it skips.
+                        // This is what allows `[first, .., last]` to
+                        // move out of an array.
+                        if let PatKind::Wild = slice_pat.node {
+                            return;
+                        }
+
+                        // Otherwise, the `slice_pat` here creates a
+                        // slice into the original vector. This is
+                        // effectively a borrow of the elements of the
+                        // vector being matched.
 
                         let (slice_cmt, slice_mutbl, slice_r) =
                             return_if_err!(mc.cat_slice_pattern(cmt_pat, &slice_pat));
@@ -1024,23 +1032,12 @@ impl<'d,'t,'a,'tcx> ExprUseVisitor<'d,'t,'a,'tcx> {
                         // Note: We declare here that the borrow
                         // occurs upon entering the `[...]`
                         // pattern. This implies that something like
-                        // `[a; b]` where `a` is a move is illegal,
+                        // `[a, b..]` where `a` is a move is illegal,
                         // because the borrow is already in effect.
-                        // In fact such a move would be safe-ish, but
-                        // it effectively *requires* that we use the
-                        // nulling out semantics to indicate when a
-                        // value has been moved, which we are trying
-                        // to move away from.  Otherwise, how can we
-                        // indicate that the first element in the
-                        // vector has been moved?  Eventually, we
-                        // could perhaps modify this rule to permit
-                        // `[..a, b]` where `b` is a move, because in
-                        // that case we can adjust the length of the
-                        // original vec accordingly, but we'd have to
-                        // make trans do the right thing, and it would
-                        // only work for `Box<[T]>`s. It seems simpler
-                        // to just require that people call
-                        // `vec.pop()` or `vec.unshift()`.
+                        // Moves of individual array elements are
+                        // tracked precisely by borrowck, but a slice
+                        // cannot be carved out of an array whose
+                        // elements have been moved away.
                         let slice_bk = ty::BorrowKind::from_mutbl(slice_mutbl);
                         delegate.borrow(pat.id, pat.span,
                                         slice_cmt, slice_r,
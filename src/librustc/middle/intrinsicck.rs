@@ -14,10 +14,12 @@ use hir::def_id::DefId;
 use infer::{InferCtxt, new_infer_ctxt};
 use traits::ProjectionMode;
 use ty::{self, Ty, TyCtxt};
-use ty::layout::{LayoutError, Pointer, SizeSkeleton};
+use ty::layout::{self, Layout, LayoutError, Pointer, SizeSkeleton};
 
 use syntax::abi::Abi::RustIntrinsic;
 use syntax::ast;
+use syntax::ast::AsmOperandClass;
+use syntax::ptr::P;
 use syntax::codemap::Span;
 use hir::intravisit::{self, Visitor, FnKind};
 use hir;
@@ -110,6 +112,117 @@ impl<'a, 'tcx> ExprVisitor<'a, 'tcx> {
                   from, skeleton_string(from, sk_from),
                   to, skeleton_string(to, sk_to));
     }
+
+    /// How a value of `ty` would be held by an `asm_operands!` operand, or
+    /// `None` if it can't be held in a register at all.
+    fn asm_type(&self, ty: Ty<'tcx>) -> Option<AsmType> {
+        if let ty::TyChar = ty.sty {
+            return None;
+        }
+        let dl = &self.infcx.tcx.data_layout;
+        match ty.layout(self.infcx) {
+            Ok(&Layout::Scalar { value: layout::Int(layout::I1), .. }) => None,
+            Ok(&Layout::Scalar { value: layout::F32, .. }) |
+            Ok(&Layout::Scalar { value: layout::F64, .. }) => Some(AsmType::Float),
+            Ok(&Layout::Scalar { value, .. }) => Some(AsmType::Int(value.size(dl).bits())),
+            Ok(&Layout::Vector { element, count }) => {
+                Some(AsmType::Vector(element.size(dl).bits() * count))
+            }
+            _ => None,
+        }
+    }
+
+    /// Checks the types of the operands of `asm_operands!`, the outputs and
+    /// then the inputs, against the classes they were given.
+    fn check_asm_operands(&self,
+                          ia: &hir::InlineAsm,
+                          outputs: &[P<hir::Expr>],
+                          inputs: &[P<hir::Expr>]) {
+        let tcx = self.infcx.tcx;
+        let arch = &tcx.sess.target.target.arch[..];
+        let exprs = outputs.iter().map(|out| (out, tcx.node_id_to_type(out.id)))
+            .chain(inputs.iter().map(|input| (input, tcx.expr_ty_adjusted(input))));
+        for (class, (expr, ty)) in ia.operand_classes.iter().zip(exprs) {
+            match *class {
+                AsmOperandClass::Const => {
+                    if !ty.is_integral() {
+                        tcx.sess.struct_span_err(expr.span,
+                            &format!("type `{}` cannot be used as a `const` operand", ty))
+                            .note("`const` operands must be integers")
+                            .emit();
+                    }
+                }
+                AsmOperandClass::Reg(ref name) => {
+                    match asm_class_holds(arch, name, self.asm_type(ty)) {
+                        Some((true, _)) => {}
+                        Some((false, holds)) => {
+                            tcx.sess.struct_span_err(expr.span,
+                                &format!("type `{}` cannot be used with register class `{}`",
+                                         ty, name))
+                                .note(&format!("register class `{}` can hold {}", name, holds))
+                                .emit();
+                        }
+                        None => {
+                            span_bug!(expr.span, "unknown register class `{}` on `{}`",
+                                      name, arch);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// How the value of an `asm_operands!` operand is held, from its layout.
+#[derive(Clone, Copy, PartialEq)]
+enum AsmType {
+    /// An integer or a thin pointer, with its size in bits.
+    Int(u64),
+    Float,
+    /// A SIMD vector, with its size in bits.
+    Vector(u64),
+}
+
+/// Whether the register class `class` on `arch` can hold values held as
+/// `ty`, with a description of what the class can hold. Gives `None` for
+/// classes `asm_operands!` doesn't know on `arch`.
+fn asm_class_holds(arch: &str, class: &str, ty: Option<AsmType>)
+                   -> Option<(bool, &'static str)> {
+    use self::AsmType::*;
+    Some(match (arch, class) {
+        ("x86_64", "reg") | ("x86_64", "reg_abcd") => {
+            (match ty {
+                Some(Int(16)) | Some(Int(32)) | Some(Int(64)) | Some(Float) => true,
+                _ => false,
+            }, "16-, 32- and 64-bit integers, `f32`, `f64` and thin pointers")
+        }
+        ("x86_64", "reg_byte") => (ty == Some(Int(8)), "8-bit integers"),
+        ("x86_64", "xmm_reg") => {
+            (match ty {
+                Some(Float) | Some(Vector(128)) => true,
+                _ => false,
+            }, "`f32`, `f64` and 128-bit SIMD vectors")
+        }
+        ("x86_64", "ymm_reg") => {
+            (match ty {
+                Some(Float) | Some(Vector(128)) | Some(Vector(256)) => true,
+                _ => false,
+            }, "`f32`, `f64` and 128- and 256-bit SIMD vectors")
+        }
+        ("aarch64", "reg") => {
+            (match ty {
+                Some(Int(_)) | Some(Float) => true,
+                _ => false,
+            }, "integers, `f32`, `f64` and thin pointers")
+        }
+        ("aarch64", "vreg") | ("aarch64", "vreg_low16") => {
+            (match ty {
+                Some(Float) | Some(Vector(64)) | Some(Vector(128)) => true,
+                _ => false,
+            }, "`f32`, `f64` and 64- and 128-bit SIMD vectors")
+        }
+        _ => return None,
+    })
 }
 
 impl<'a, 'tcx, 'v> Visitor<'v> for ItemVisitor<'a, 'tcx> {
@@ -161,6 +274,9 @@ impl<'a, 'tcx, 'v> Visitor<'v> for ItemVisitor<'a, 'tcx> {
 
 impl<'a, 'tcx, 'v> Visitor<'v> for ExprVisitor<'a, 'tcx> {
     fn visit_expr(&mut self, expr: &hir::Expr) {
+        if let hir::ExprInlineAsm(ref ia, ref outputs, ref inputs) = expr.node {
+            self.check_asm_operands(ia, outputs, inputs);
+        }
         if let hir::ExprPath(..) = expr.node {
             match self.infcx.tcx.resolve_expr(expr) {
                 Def::Fn(did) if self.def_id_is_transmute(did) => {
@@ -139,8 +139,9 @@ pub enum FieldName {
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum InteriorOffsetKind {
-    Index,            // e.g. `array_expr[index_expr]`
-    Pattern,          // e.g. `fn foo([_, a, _, _]: [A; 4]) { ... }`
+    Index,                // e.g. `array_expr[index_expr]`
+    Pattern,              // e.g. `fn foo([a, ..b]: &[A]) { ... }`
+    ConstantIndex(usize), // e.g. `fn foo([_, a, _, _]: [A; 4]) { ... }`
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
@@ -1310,17 +1311,36 @@ impl<'t, 'a,'tcx> MemCategorizationContext<'t, 'a, 'tcx> {
           PatKind::Vec(ref before, ref slice, ref after) => {
               let context = InteriorOffsetKind::Pattern;
               let vec_cmt = self.deref_vec(pat, cmt, context)?;
-              let elt_cmt = self.cat_index(pat, vec_cmt, context)?;
-              for before_pat in before {
-                  self.cat_pattern_(elt_cmt.clone(), &before_pat, op)?;
+              let elt_cmt = self.cat_index(pat, vec_cmt.clone(), context)?;
+
+              // The elements of a fixed-size array sit at known positions,
+              // so each of them gets its own cmt. This lets borrowck tell
+              // apart moves out of different elements.
+              let array_len = match vec_cmt.ty.sty {
+                  ty::TyArray(_, len) => Some(len),
+                  _ => None
+              };
+              let elt_cmt_at = |position: usize| -> McResult<cmt<'tcx>> {
+                  match array_len {
+                      Some(_) => {
+                          let context = InteriorOffsetKind::ConstantIndex(position);
+                          self.cat_index(pat, vec_cmt.clone(), context)
+                      }
+                      None => Ok(elt_cmt.clone())
+                  }
+              };
+
+              for (i, before_pat) in before.iter().enumerate() {
+                  self.cat_pattern_(elt_cmt_at(i)?, &before_pat, op)?;
               }
               if let Some(ref slice_pat) = *slice {
                   let slice_ty = self.pat_ty(&slice_pat)?;
                   let slice_cmt = self.cat_rvalue_node(pat.id(), pat.span(), slice_ty);
                   self.cat_pattern_(slice_cmt, &slice_pat, op)?;
               }
-              for after_pat in after {
-                  self.cat_pattern_(elt_cmt.clone(), &after_pat, op)?;
+              let after_start = array_len.map_or(0, |len| len - after.len());
+              for (i, after_pat) in after.iter().enumerate() {
+                  self.cat_pattern_(elt_cmt_at(after_start + i)?, &after_pat, op)?;
               }
           }
 
@@ -1516,10 +1536,9 @@ impl<'tcx> cmt_<'tcx> {
                                                         OtherElement)) => {
                 "indexed content".to_string()
             }
-            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::Pattern,
-                                                        VecElement)) |
-            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::Pattern,
-                                                        OtherElement)) => {
+            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::Pattern, _)) |
+            Categorization::Interior(_, InteriorElement(InteriorOffsetKind::ConstantIndex(_),
+                                                        _)) => {
                 "pattern-bound indexed content".to_string()
             }
             Categorization::Upvar(ref var) => {
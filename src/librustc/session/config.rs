@@ -32,7 +32,7 @@ use syntax::parse::token::InternedString;
 use syntax::feature_gate::UnstableFeatures;
 
 use getopts;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fmt;
 use std::path::PathBuf;
@@ -130,6 +130,8 @@ pub struct Options {
     // will be added to the crate AST node.  This should not be used for
     // anything except building the full crate config prior to parsing.
     pub cfg: ast::CrateConfig,
+    // The cfg names and values the crate is expected to use.
+    pub check_cfg: CheckCfg,
     pub test: bool,
     pub parse_only: bool,
     pub no_trans: bool,
@@ -156,6 +158,50 @@ pub struct Options {
     pub unstable_features: UnstableFeatures
 }
 
+/// The cfg names and values a crate is expected to use, from `--check-cfg`.
+/// Any others are reported by the `unexpected_cfgs` lint.
+#[derive(Clone, Default)]
+pub struct CheckCfg {
+    /// The expected names, if names are checked.
+    pub names: Option<HashSet<String>>,
+    /// The expected values of the names whose values are checked.
+    pub values: HashMap<String, HashSet<String>>,
+}
+
+/// The cfg names that are expected whatever the target.
+const WELL_KNOWN_CFGS: &'static [&'static str] = &[
+    "debug_assertions", "target_arch", "target_endian", "target_env", "target_family",
+    "target_os", "target_pointer_width", "target_thread_local", "target_vendor", "test",
+    "unix", "windows",
+];
+
+impl CheckCfg {
+    /// Tells what is unexpected about the cfg `name`, with `value` if any,
+    /// in a crate configured with `cfg`. The cfgs in `cfg` are always
+    /// expected.
+    pub fn check(&self, cfg: &ast::CrateConfig, name: &str, value: Option<&str>)
+                 -> Option<String> {
+        let is_set = cfg.iter().any(|mi| {
+            mi.name() == name && mi.value_str().as_ref().map(|v| &v[..]) == value
+        });
+        if is_set {
+            return None;
+        }
+        if let Some(ref names) = self.names {
+            if !names.contains(name) && !self.values.contains_key(name) &&
+               !WELL_KNOWN_CFGS.contains(&name) && !cfg.iter().any(|mi| mi.name() == name) {
+                return Some(format!("unexpected `cfg` condition name `{}`", name));
+            }
+        }
+        match (self.values.get(name), value) {
+            (Some(values), Some(value)) if !values.contains(value) => {
+                Some(format!("unexpected `cfg` condition value `{}` for `{}`", value, name))
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub enum PrintRequest {
     FileNames,
@@ -251,6 +297,7 @@ pub fn basic_options() -> Options {
         maybe_sysroot: None,
         target_triple: host_triple().to_string(),
         cfg: Vec::new(),
+        check_cfg: CheckCfg::default(),
         test: false,
         parse_only: false,
         no_trans: false,
@@ -298,6 +345,7 @@ pub enum CrateType {
     CrateTypeDylib,
     CrateTypeRlib,
     CrateTypeStaticlib,
+    CrateTypeProcMacro,
 }
 
 #[derive(Clone)]
@@ -615,10 +663,14 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
         "print the AST as JSON and halt"),
     ast_json_noexpand: bool = (false, parse_bool,
         "print the pre-expansion AST as JSON and halt"),
+    unused_externs_json: Option<String> = (None, parse_opt_string,
+        "write the `--extern` crates that are never used as JSON to this file"),
     ls: bool = (false, parse_bool,
         "list the symbols defined by a library crate"),
     save_analysis: bool = (false, parse_bool,
         "write syntax and type analysis information in addition to normal output"),
+    save_analysis_json: bool = (false, parse_bool,
+        "write syntax and type analysis information as JSON in addition to normal output"),
     print_move_fragments: bool = (false, parse_bool,
         "print out move-fragment data for every fn"),
     flowgraph_print_loans: bool = (false, parse_bool,
@@ -668,12 +720,16 @@ options! {DebuggingOptions, DebuggingSetter, basic_debugging_options,
           "keep the AST after lowering it to HIR"),
     show_span: Option<String> = (None, parse_opt_string,
           "show spans for compiler debugging (expr|pat|ty)"),
+    pretty_width: Option<usize> = (None, parse_opt_uint,
+          "the maximum line width of `--unpretty formatted` output (default: 100)"),
     print_trans_items: Option<String> = (None, parse_opt_string,
           "print the result of the translation item collection pass"),
     mir_opt_level: Option<usize> = (None, parse_opt_uint,
           "set the MIR optimization level (0-3)"),
     dump_mir: Option<String> = (None, parse_opt_string,
           "dump MIR state at various points in translation"),
+    nll: bool = (false, parse_bool,
+          "check borrows with regions computed from MIR liveness (non-lexical lifetimes)"),
     orbit: bool = (false, parse_bool,
           "get MIR where it belongs - everywhere; most importantly, in orbit"),
 }
@@ -897,7 +953,7 @@ pub fn rustc_short_optgroups() -> Vec<RustcOptGroup> {
                              assumed.", "[KIND=]NAME"),
         opt::multi_s("", "crate-type", "Comma separated list of types of crates
                                     for the compiler to emit",
-                   "[bin|lib|rlib|dylib|staticlib]"),
+                   "[bin|lib|rlib|dylib|staticlib|proc-macro]"),
         opt::opt_s("", "crate-name", "Specify the name of the crate being built",
                "NAME"),
         opt::multi_s("", "emit", "Comma separated list of types of output for \
@@ -961,6 +1017,12 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
                            `hir,typed` (HIR with types for each node).",
                           "TYPE"),
 
+        opt::multi("", "check-cfg", "Check the cfg names and values used by the crate:
+                                 `names(a, b)` expects the names `a` and `b`,
+                                 `values(a = \"x\")` expects `a` to be `x`
+                                 or another value given for `a`",
+                   "SPEC"),
+
         // new options here should **not** use the `_ubnr` functions, all new
         // unstable options should use the short variants to indicate that they
         // are truly unstable. All `_ubnr` flags are just that way because they
@@ -972,6 +1034,50 @@ pub fn rustc_optgroups() -> Vec<RustcOptGroup> {
     opts
 }
 
+// Convert strings provided as --check-cfg [spec] into the expected cfgs
+pub fn parse_check_cfg(specs: Vec<String>) -> CheckCfg {
+    let mut check_cfg = CheckCfg::default();
+    for cfg in parse_cfgspecs(specs) {
+        let mis = match cfg.node {
+            ast::MetaItemKind::List(ref kind, ref mis) if *kind == "names" ||
+                                                          *kind == "values" => {
+                if *kind == "names" && check_cfg.names.is_none() {
+                    check_cfg.names = Some(HashSet::new());
+                }
+                mis
+            }
+            _ => early_error(ErrorOutputType::default(), "invalid --check-cfg argument: \
+                                                          expected `names(...)` or \
+                                                          `values(...)`"),
+        };
+        for mi in mis {
+            match (&cfg.name()[..], &mi.node) {
+                ("names", &ast::MetaItemKind::Word(ref name)) => {
+                    check_cfg.names.as_mut().unwrap().insert(name.to_string());
+                }
+                ("values", &ast::MetaItemKind::Word(ref name)) => {
+                    check_cfg.values.entry(name.to_string()).or_insert(HashSet::new());
+                }
+                ("values", &ast::MetaItemKind::NameValue(ref name, _)) => {
+                    let value = match mi.value_str() {
+                        Some(value) => value.to_string(),
+                        None => early_error(ErrorOutputType::default(),
+                                            "invalid --check-cfg argument: values must be \
+                                             strings"),
+                    };
+                    check_cfg.values.entry(name.to_string())
+                                    .or_insert(HashSet::new())
+                                    .insert(value);
+                }
+                _ => early_error(ErrorOutputType::default(),
+                                 "invalid --check-cfg argument: expected `names(a, b)` or \
+                                  `values(a = \"x\")`"),
+            }
+        }
+    }
+    check_cfg
+}
+
 // Convert strings provided as --cfg [cfgspec] into a crate_cfg
 pub fn parse_cfgspecs(cfgspecs: Vec<String> ) -> ast::CrateConfig {
     cfgspecs.into_iter().map(|s| {
@@ -1180,6 +1286,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
     }).collect();
 
     let cfg = parse_cfgspecs(matches.opt_strs("cfg"));
+    let check_cfg = parse_check_cfg(matches.opt_strs("check-cfg"));
     let test = matches.opt_present("test");
 
     let prints = matches.opt_strs("print").into_iter().map(|s| {
@@ -1232,6 +1339,7 @@ pub fn build_session_options(matches: &getopts::Matches) -> Options {
         maybe_sysroot: sysroot_opt,
         target_triple: target,
         cfg: cfg,
+        check_cfg: check_cfg,
         test: test,
         parse_only: parse_only,
         no_trans: no_trans,
@@ -1278,6 +1386,7 @@ pub fn parse_crate_types_from_list(list_list: Vec<String>) -> Result<Vec<CrateTy
                 "staticlib" => CrateTypeStaticlib,
                 "dylib"     => CrateTypeDylib,
                 "bin"       => CrateTypeExecutable,
+                "proc-macro" => CrateTypeProcMacro,
                 _ => {
                     return Err(format!("unknown crate type: `{}`",
                                        part));
@@ -1364,7 +1473,8 @@ impl fmt::Display for CrateType {
             CrateTypeExecutable => "bin".fmt(f),
             CrateTypeDylib => "dylib".fmt(f),
             CrateTypeRlib => "rlib".fmt(f),
-            CrateTypeStaticlib => "staticlib".fmt(f)
+            CrateTypeStaticlib => "staticlib".fmt(f),
+            CrateTypeProcMacro => "proc-macro".fmt(f),
         }
     }
 }
@@ -1373,6 +1483,7 @@ impl fmt::Display for CrateType {
 mod tests {
     use middle::cstore::DummyCrateStore;
     use session::config::{build_configuration, build_session_options};
+    use session::config::{parse_cfgspecs, parse_check_cfg};
     use session::build_session;
 
     use std::rc::Rc;
@@ -1460,4 +1571,17 @@ mod tests {
             assert!(sess.diagnostic().can_emit_warnings);
         }
     }
+
+    #[test]
+    fn test_check_cfg() {
+        let check_cfg = parse_check_cfg(vec!["names(foo)".to_string(),
+                                             "values(feature = \"a\")".to_string()]);
+        let cfg = parse_cfgspecs(vec!["feature = \"b\"".to_string()]);
+        assert!(check_cfg.check(&cfg, "foo", None).is_none());
+        assert!(check_cfg.check(&cfg, "unix", None).is_none());
+        assert!(check_cfg.check(&cfg, "fooo", None).is_some());
+        assert!(check_cfg.check(&cfg, "feature", Some("a")).is_none());
+        assert!(check_cfg.check(&cfg, "feature", Some("b")).is_none());
+        assert!(check_cfg.check(&cfg, "feature", Some("c")).is_some());
+    }
 }
@@ -57,6 +57,7 @@ pub struct Session {
     pub entry_fn: RefCell<Option<(NodeId, Span)>>,
     pub entry_type: Cell<Option<config::EntryFnType>>,
     pub plugin_registrar_fn: Cell<Option<ast::NodeId>>,
+    pub derive_registrar_fn: Cell<Option<ast::NodeId>>,
     pub default_sysroot: Option<PathBuf>,
     // The name of the root source file of the crate, in the local file system.
     // The path is always expected to be absolute. `None` means that there is no
@@ -64,7 +65,7 @@ pub struct Session {
     pub local_crate_source_file: Option<PathBuf>,
     pub working_dir: PathBuf,
     pub lint_store: RefCell<lint::LintStore>,
-    pub lints: RefCell<NodeMap<Vec<(lint::LintId, Span, String)>>>,
+    pub lints: RefCell<NodeMap<Vec<lint::EarlyLint>>>,
     pub plugin_llvm_passes: RefCell<Vec<String>>,
     pub mir_passes: RefCell<mir_pass::Passes>,
     pub plugin_attributes: RefCell<Vec<(String, AttributeType)>>,
@@ -95,6 +96,11 @@ pub struct Session {
     /// macro name and defintion span in the source crate.
     pub imported_macro_spans: RefCell<HashMap<Span, (String, Span)>>,
 
+    /// The `cfg` conditions that `--check-cfg` did not expect, found during
+    /// configuration. They become lints once node ids have been assigned, so
+    /// that they can be allowed on the item containing them.
+    pub unexpected_cfgs: RefCell<Vec<(Span, String)>>,
+
     next_node_id: Cell<ast::NodeId>,
 }
 
@@ -242,19 +248,21 @@ impl Session {
                     id: ast::NodeId,
                     sp: Span,
                     msg: String) {
-        let lint_id = lint::LintId::of(lint);
+        self.add_early_lint(id, lint::EarlyLint::new(lint, sp, msg));
+    }
+    /// Like `add_lint`, but the lint may carry notes and help messages.
+    pub fn add_early_lint(&self, id: ast::NodeId, early_lint: lint::EarlyLint) {
         let mut lints = self.lints.borrow_mut();
         match lints.get_mut(&id) {
             Some(arr) => {
-                let tuple = (lint_id, sp, msg);
-                if !arr.contains(&tuple) {
-                    arr.push(tuple);
+                if !arr.contains(&early_lint) {
+                    arr.push(early_lint);
                 }
                 return;
             }
             None => {}
         }
-        lints.insert(id, vec!((lint_id, sp, msg)));
+        lints.insert(id, vec!(early_lint));
     }
     pub fn reserve_node_ids(&self, count: ast::NodeId) -> ast::NodeId {
         let id = self.next_node_id.get();
@@ -291,6 +299,9 @@ impl Session {
     pub fn print_llvm_passes(&self) -> bool {
         self.opts.debugging_opts.print_llvm_passes
     }
+    pub fn save_analysis(&self) -> bool {
+        self.opts.debugging_opts.save_analysis || self.opts.debugging_opts.save_analysis_json
+    }
     pub fn lto(&self) -> bool {
         self.opts.cg.lto
     }
@@ -475,6 +486,7 @@ pub fn build_session_(sopts: config::Options,
         entry_fn: RefCell::new(None),
         entry_type: Cell::new(None),
         plugin_registrar_fn: Cell::new(None),
+        derive_registrar_fn: Cell::new(None),
         default_sysroot: default_sysroot,
         local_crate_source_file: local_crate_source_file,
         working_dir: env::current_dir().unwrap(),
@@ -492,6 +504,7 @@ pub fn build_session_(sopts: config::Options,
         injected_allocator: Cell::new(None),
         available_macros: RefCell::new(HashSet::new()),
         imported_macro_spans: RefCell::new(HashMap::new()),
+        unexpected_cfgs: RefCell::new(Vec::new()),
     };
 
     init_llvm(&sess);
@@ -93,6 +93,9 @@ struct CheckLoanCtxt<'a, 'tcx: 'a> {
     move_data: &'a move_data::FlowedMoveData<'a, 'tcx>,
     all_loans: &'a [Loan<'tcx>],
     param_env: &'a ty::ParameterEnvironment<'a, 'tcx>,
+    // False when the MIR borrow checker checks loans for conflicts
+    // instead (`-Z nll`).
+    check_conflicts: bool,
 }
 
 impl<'a, 'tcx> euv::Delegate<'tcx> for CheckLoanCtxt<'a, 'tcx> {
@@ -199,7 +202,8 @@ pub fn check_loans<'a, 'b, 'c, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
                                      all_loans: &[Loan<'tcx>],
                                      fn_id: ast::NodeId,
                                      decl: &hir::FnDecl,
-                                     body: &hir::Block) {
+                                     body: &hir::Block,
+                                     check_conflicts: bool) {
     debug!("check_loans(body id={})", body.id);
 
     let param_env = ty::ParameterEnvironment::for_item(bccx.tcx, fn_id);
@@ -213,7 +217,8 @@ pub fn check_loans<'a, 'b, 'c, 'tcx>(bccx: &BorrowckCtxt<'a, 'tcx>,
         dfcx_loans: dfcx_loans,
         move_data: move_data,
         all_loans: all_loans,
-        param_env: &infcx.parameter_environment
+        param_env: &infcx.parameter_environment,
+        check_conflicts: check_conflicts,
     };
 
     {
@@ -246,6 +251,9 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
         //! are issued for future scopes and thus they may have been
         //! *issued* but not yet be in effect.
 
+        if !self.check_conflicts {
+            return true;
+        }
         self.dfcx_loans.each_bit_on_entry(node, |loan_index| {
             let loan = &self.all_loans[loan_index];
             op(loan)
@@ -285,7 +293,10 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
         //     let x = &mut a.b.c; // Restricts a, a.b, and a.b.c
         //     let y = a;          // Conflicts with restriction
 
-        let loan_path = owned_ptr_base_path(loan_path);
+        // Loans do not distinguish the elements of an array, see
+        // `LoanPath::without_element_positions`.
+        let loan_path = LoanPath::without_element_positions(loan_path);
+        let loan_path = owned_ptr_base_path(&loan_path);
         let cont = self.each_in_scope_loan(scope, |loan| {
             let mut ret = true;
             for restr_path in &loan.restricted_paths {
@@ -366,6 +377,10 @@ impl<'a, 'tcx> CheckLoanCtxt<'a, 'tcx> {
 
         debug!("check_for_conflicting_loans(node={:?})", node);
 
+        if !self.check_conflicts {
+            return;
+        }
+
         let new_loan_indices = self.loans_generated_by(node);
         debug!("new_loan_indices = {:?}", new_loan_indices);
 
@@ -397,9 +397,14 @@ impl<'a, 'tcx> GatherLoanCtxt<'a, 'tcx> {
                     self.mark_loan_path_as_mutated(&loan_path);
                 }
 
+                // Loans do not distinguish the elements of an array.
+                let restricted_paths = restricted_paths.iter()
+                    .map(|lp| LoanPath::without_element_positions(lp))
+                    .collect();
+
                 Loan {
                     index: self.all_loans.len(),
-                    loan_path: loan_path,
+                    loan_path: LoanPath::without_element_positions(&loan_path),
                     kind: req_kind,
                     gen_scope: gen_scope,
                     kill_scope: kill_scope,
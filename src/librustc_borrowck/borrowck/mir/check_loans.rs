@@ -0,0 +1,342 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Checks for conflicting borrows under `-Z nll`.
+//!
+//! The AST borrow checker keeps a loan in effect until the end of the
+//! lexical scope of its region. Here the regions computed from MIR
+//! liveness by `rustc_mir::transform::nll` are used instead: a loan is
+//! in effect at the points of its region that can be reached from the
+//! borrow, so it ends once nothing that may still use the reference is
+//! live. Accesses to the borrowed path at those points are checked
+//! against the loan. Under `-Z nll` this replaces the conflict checks of
+//! `check_loans`; the other checks of the AST borrow checker still apply.
+
+use borrowck::BorrowckCtxt;
+
+use syntax::ast;
+use syntax::codemap::Span;
+
+use rustc::mir::repr::{BorrowKind, Lvalue, LvalueElem, Mir, ProjectionElem};
+use rustc::mir::repr::{Rvalue, StatementKind};
+use rustc::mir::visit::{LvalueContext, Visitor};
+use rustc::ty::{self, TyCtxt};
+use rustc::util::nodemap::FnvHashSet;
+use rustc_mir::build::Location;
+use rustc_mir::transform::nll;
+
+/// A borrow, with the points at which it is in effect.
+struct Loan<'tcx> {
+    lvalue: Lvalue<'tcx>,
+    kind: BorrowKind,
+    span: Span,
+    points: FnvHashSet<Location>,
+}
+
+/// How an access uses the lvalue it accesses.
+#[derive(Copy, Clone, PartialEq)]
+enum AccessKind {
+    Read,
+    Borrow(BorrowKind),
+    Move,
+    Write,
+    Drop,
+}
+
+pub fn check_loans<'a, 'tcx>(bcx: &BorrowckCtxt<'a, 'tcx>, id: ast::NodeId, mir: &Mir<'tcx>) {
+    let regions = nll::compute_regions(bcx.tcx, id, mir);
+    let loans = gather_loans(&regions);
+    debug!("check_loans({}): {} loans", id, loans.len());
+    if loans.is_empty() {
+        return;
+    }
+
+    let param_env = ty::ParameterEnvironment::for_item(bcx.tcx, id);
+    let checker = LoanChecker {
+        bcx: bcx,
+        mir: mir,
+        param_env: &param_env,
+    };
+    let mut reported = FnvHashSet();
+    for block in mir.all_basic_blocks() {
+        let data = mir.basic_block_data(block);
+        for statement_index in 0..data.statements.len() + 1 {
+            let location = Location { block: block, statement_index: statement_index };
+            for (lvalue, kind, span) in checker.accesses(location) {
+                for loan in loans.iter().filter(|loan| loan.points.contains(&location)) {
+                    if checker.conflicts(loan, &lvalue, kind) && reported.insert(span) {
+                        checker.report(loan, &lvalue, kind, span);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Collects the borrows of `regions.mir`, each with the points of its
+/// region that can be reached from the borrow.
+fn gather_loans<'tcx>(regions: &nll::NllRegions<'tcx>) -> Vec<Loan<'tcx>> {
+    let mir = &regions.mir;
+    let mut loans = vec![];
+    for block in mir.all_basic_blocks() {
+        for (index, statement) in mir.basic_block_data(block).statements.iter().enumerate() {
+            match statement.kind {
+                StatementKind::Assign(_, Rvalue::Ref(ty::ReVar(v), kind, ref lvalue)) => {
+                    // The loan takes effect once the statement is done.
+                    let start = Location { block: block, statement_index: index + 1 };
+                    loans.push(Loan {
+                        lvalue: lvalue.clone(),
+                        kind: kind,
+                        span: statement.span,
+                        points: reachable_points(mir, regions.regioncx.region_value(v), start),
+                    });
+                }
+                StatementKind::Assign(..) => {}
+            }
+        }
+    }
+    loans
+}
+
+/// The points of `region` that can be reached from `start` without
+/// leaving `region`.
+fn reachable_points(mir: &Mir, region: &FnvHashSet<Location>, start: Location)
+                    -> FnvHashSet<Location> {
+    let mut points = FnvHashSet();
+    let mut stack = vec![start];
+    while let Some(point) = stack.pop() {
+        if !region.contains(&point) || !points.insert(point) {
+            continue;
+        }
+        let data = mir.basic_block_data(point.block);
+        if point.statement_index < data.statements.len() {
+            stack.push(Location {
+                block: point.block,
+                statement_index: point.statement_index + 1,
+            });
+        } else {
+            stack.extend(data.terminator().successors().iter().map(|&block| {
+                Location { block: block, statement_index: 0 }
+            }));
+        }
+    }
+    points
+}
+
+/// The lvalues accessed by a statement or terminator. Bases of
+/// projections are not listed separately.
+struct Accesses<'tcx> {
+    accesses: Vec<(Lvalue<'tcx>, LvalueContext)>,
+}
+
+impl<'tcx> Visitor<'tcx> for Accesses<'tcx> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        match context {
+            LvalueContext::Projection => {}
+            _ => self.accesses.push((lvalue.clone(), context)),
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
+
+struct LoanChecker<'b, 'a: 'b, 'tcx: 'a> {
+    bcx: &'b BorrowckCtxt<'a, 'tcx>,
+    mir: &'b Mir<'tcx>,
+    param_env: &'b ty::ParameterEnvironment<'a, 'tcx>,
+}
+
+impl<'b, 'a: 'b, 'tcx: 'a> LoanChecker<'b, 'a, 'tcx> {
+    fn tcx(&self) -> &'a TyCtxt<'tcx> {
+        self.bcx.tcx
+    }
+
+    fn accesses(&self, location: Location) -> Vec<(Lvalue<'tcx>, AccessKind, Span)> {
+        let data = self.mir.basic_block_data(location.block);
+        let mut visitor = Accesses { accesses: vec![] };
+        let span = if location.statement_index < data.statements.len() {
+            let statement = &data.statements[location.statement_index];
+            visitor.visit_statement(location.block, statement);
+            statement.span
+        } else {
+            let terminator = data.terminator();
+            visitor.visit_terminator(location.block, terminator);
+            terminator.span
+        };
+
+        visitor.accesses.into_iter().map(|(lvalue, context)| {
+            let kind = match context {
+                LvalueContext::Store | LvalueContext::Call => AccessKind::Write,
+                LvalueContext::Drop => AccessKind::Drop,
+                LvalueContext::Borrow { kind, .. } => AccessKind::Borrow(kind),
+                LvalueContext::Consume => {
+                    let ty = self.mir.lvalue_ty(self.tcx(), &lvalue).to_ty(self.tcx());
+                    if ty.moves_by_default(self.param_env, span) {
+                        AccessKind::Move
+                    } else {
+                        AccessKind::Read
+                    }
+                }
+                LvalueContext::Inspect |
+                LvalueContext::Slice { .. } |
+                LvalueContext::Projection => AccessKind::Read,
+            };
+            (lvalue, kind, span)
+        }).collect()
+    }
+
+    /// Whether accessing `lvalue` in the way `kind` says is forbidden
+    /// while `loan` is in effect.
+    fn conflicts(&self, loan: &Loan<'tcx>, lvalue: &Lvalue<'tcx>, kind: AccessKind) -> bool {
+        let loan_path = prefixes(&loan.lvalue);
+        let path = prefixes(lvalue);
+        if loan_path[0] != path[0] {
+            return false;
+        }
+        let common = loan_path.iter().zip(&path).skip(1).take_while(|&(a, b)| {
+            may_overlap(last_elem(a), last_elem(b))
+        }).count() + 1;
+        if common < loan_path.len() && common < path.len() {
+            // Disjoint fields of the same value.
+            return false;
+        }
+
+        match kind {
+            AccessKind::Read | AccessKind::Borrow(BorrowKind::Shared) => {
+                loan.kind != BorrowKind::Shared
+            }
+            AccessKind::Write | AccessKind::Drop if path.len() < loan_path.len() => {
+                // Overwriting or dropping a reference leaves what it points
+                // to alone.
+                !loan_path[path.len()..].iter().any(|prefix| self.is_deref_of_ref(prefix))
+            }
+            AccessKind::Borrow(_) | AccessKind::Move | AccessKind::Write | AccessKind::Drop => {
+                true
+            }
+        }
+    }
+
+    /// Whether `lvalue` dereferences a reference or a raw pointer, as
+    /// opposed to a `Box`.
+    fn is_deref_of_ref(&self, lvalue: &Lvalue<'tcx>) -> bool {
+        match *lvalue {
+            Lvalue::Projection(ref proj) => match proj.elem {
+                ProjectionElem::Deref => {
+                    let ty = self.mir.lvalue_ty(self.tcx(), &proj.base).to_ty(self.tcx());
+                    match ty.sty {
+                        ty::TyRef(..) | ty::TyRawPtr(..) => true,
+                        _ => false,
+                    }
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn report(&self, loan: &Loan<'tcx>, lvalue: &Lvalue<'tcx>, kind: AccessKind, span: Span) {
+        let desc = self.describe_lvalue(lvalue);
+        let mut err = match (kind, loan.kind) {
+            (AccessKind::Borrow(BorrowKind::Shared), _) => {
+                struct_span_err!(self.bcx, span, E0502,
+                                 "cannot borrow `{}` as immutable because it is also \
+                                  borrowed as mutable", desc)
+            }
+            (AccessKind::Borrow(_), BorrowKind::Shared) => {
+                struct_span_err!(self.bcx, span, E0502,
+                                 "cannot borrow `{}` as mutable because it is also \
+                                  borrowed as immutable", desc)
+            }
+            (AccessKind::Borrow(_), _) => {
+                struct_span_err!(self.bcx, span, E0499,
+                                 "cannot borrow `{}` as mutable more than once at a time",
+                                 desc)
+            }
+            (AccessKind::Read, _) => {
+                struct_span_err!(self.bcx, span, E0503,
+                                 "cannot use `{}` because it was mutably borrowed", desc)
+            }
+            (AccessKind::Move, _) => {
+                struct_span_err!(self.bcx, span, E0505,
+                                 "cannot move out of `{}` because it is borrowed", desc)
+            }
+            (AccessKind::Write, _) => {
+                struct_span_err!(self.bcx, span, E0506,
+                                 "cannot assign to `{}` because it is borrowed", desc)
+            }
+            (AccessKind::Drop, _) => {
+                self.bcx.struct_span_err(span,
+                                         &format!("`{}` is dropped while it is still borrowed",
+                                                  desc))
+            }
+        };
+        err.span_note(loan.span,
+                      &format!("borrow of `{}` occurs here", self.describe_lvalue(&loan.lvalue)));
+        err.emit();
+    }
+
+    fn describe_lvalue(&self, lvalue: &Lvalue<'tcx>) -> String {
+        match *lvalue {
+            Lvalue::Var(index) => self.mir.var_decls[index as usize].name.to_string(),
+            Lvalue::Arg(index) => self.mir.arg_decls[index as usize].debug_name.to_string(),
+            Lvalue::Temp(_) => "temporary value".to_string(),
+            Lvalue::Static(def_id) => self.tcx().item_path_str(def_id),
+            Lvalue::ReturnPointer => "return value".to_string(),
+            Lvalue::Projection(ref proj) => {
+                let base = self.describe_lvalue(&proj.base);
+                match proj.elem {
+                    ProjectionElem::Deref => format!("*{}", base),
+                    ProjectionElem::Field(field, _) => {
+                        let ty = self.mir.lvalue_ty(self.tcx(), &proj.base).to_ty(self.tcx());
+                        match ty.sty {
+                            ty::TyStruct(adt, _) => {
+                                let name = adt.struct_variant().fields[field.index()].name;
+                                format!("{}.{}", base, name)
+                            }
+                            _ => format!("{}.{}", base, field.index()),
+                        }
+                    }
+                    ProjectionElem::Index(_) | ProjectionElem::ConstantIndex { .. } => {
+                        format!("{}[..]", base)
+                    }
+                    ProjectionElem::Downcast(..) => base,
+                }
+            }
+        }
+    }
+}
+
+/// `lvalue` and the lvalues it is projected from, starting with the
+/// local, static or return pointer at its base.
+fn prefixes<'a, 'tcx>(mut lvalue: &'a Lvalue<'tcx>) -> Vec<&'a Lvalue<'tcx>> {
+    let mut result = vec![lvalue];
+    while let Lvalue::Projection(ref proj) = *lvalue {
+        lvalue = &proj.base;
+        result.push(lvalue);
+    }
+    result.reverse();
+    result
+}
+
+fn last_elem<'a, 'tcx>(lvalue: &'a Lvalue<'tcx>) -> Option<&'a LvalueElem<'tcx>> {
+    match *lvalue {
+        Lvalue::Projection(ref proj) => Some(&proj.elem),
+        _ => None,
+    }
+}
+
+/// Whether two projections of the same lvalue may refer to overlapping
+/// memory. Indices are not compared.
+fn may_overlap(a: Option<&LvalueElem>, b: Option<&LvalueElem>) -> bool {
+    match (a, b) {
+        (Some(&ProjectionElem::Field(a, _)), Some(&ProjectionElem::Field(b, _))) => a == b,
+        (Some(&ProjectionElem::Downcast(_, a)), Some(&ProjectionElem::Downcast(_, b))) => a == b,
+        _ => true,
+    }
+}
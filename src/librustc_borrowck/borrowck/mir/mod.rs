@@ -19,6 +19,7 @@ use rustc::hir::intravisit::{FnKind};
 use rustc::mir::repr::{BasicBlock, BasicBlockData, Mir, Statement, Terminator};
 
 mod abs_domain;
+pub mod check_loans;
 mod dataflow;
 mod gather_moves;
 mod graphviz;
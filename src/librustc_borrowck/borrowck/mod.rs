@@ -173,6 +173,14 @@ fn borrowck_fn(this: &mut BorrowckCtxt,
         });
     }
 
+    // Under `-Z nll`, loans are checked for conflicts on the MIR, where
+    // they last for non-lexical regions, rather than by `check_loans`.
+    let nll_mir = if this.tcx.sess.opts.debugging_opts.nll {
+        this.mir_map.and_then(|mir_map| mir_map.map.get(&id))
+    } else {
+        None
+    };
+
     let cfg = cfg::CFG::new(this.tcx, body);
     let AnalysisData { all_loans,
                        loans: loan_dfcx,
@@ -193,7 +201,12 @@ fn borrowck_fn(this: &mut BorrowckCtxt,
                              &all_loans[..],
                              id,
                              decl,
-                             body);
+                             body,
+                             nll_mir.is_none());
+
+    if let Some(mir) = nll_mir {
+        mir::check_loans::check_loans(this, id, mir);
+    }
 
     intravisit::walk_fn(this, fk, decl, body, sp);
 }
@@ -379,12 +392,13 @@ const DOWNCAST_PRINTED_OPERATOR: &'static str = " as ";
 
 // A local, "cleaned" version of `mc::InteriorKind` that drops
 // information that is not relevant to loan-path analysis. (In
-// particular, the distinction between how precisely an array-element
-// is tracked is irrelevant here.)
+// particular, only the position of an array-element bound by a
+// pattern on a fixed-size array is kept; `None` stands for some
+// element that cannot be determined statically, as in `a[i]`.)
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum InteriorKind {
     InteriorField(mc::FieldName),
-    InteriorElement(mc::ElementKind),
+    InteriorElement(mc::ElementKind, Option<usize>),
 }
 
 trait ToInteriorKind { fn cleaned(self) -> InteriorKind; }
@@ -392,7 +406,10 @@ impl ToInteriorKind for mc::InteriorKind {
     fn cleaned(self) -> InteriorKind {
         match self {
             mc::InteriorField(name) => InteriorField(name),
-            mc::InteriorElement(_, elem_kind) => InteriorElement(elem_kind),
+            mc::InteriorElement(mc::InteriorOffsetKind::ConstantIndex(i), elem_kind) => {
+                InteriorElement(elem_kind, Some(i))
+            }
+            mc::InteriorElement(_, elem_kind) => InteriorElement(elem_kind, None),
         }
     }
 }
@@ -427,6 +444,29 @@ pub fn closure_to_block(closure_id: ast::NodeId,
 }
 
 impl<'tcx> LoanPath<'tcx> {
+    /// Returns this path with every array-element position forgotten.
+    ///
+    /// Only moves keep track of which element of an array they are out of;
+    /// loans, and the uses checked against them, treat `a[0]` as `a[..]` so
+    /// that a borrow of one element still conflicts with indexing `a`.
+    pub fn without_element_positions(lp: &LoanPath<'tcx>) -> Rc<LoanPath<'tcx>> {
+        let kind = match lp.kind {
+            LpVar(id) => LpVar(id),
+            LpUpvar(id) => LpUpvar(id),
+            LpDowncast(ref base, variant_def_id) => {
+                LpDowncast(LoanPath::without_element_positions(base), variant_def_id)
+            }
+            LpExtend(ref base, mc, LpInterior(opt_variant_id, InteriorElement(kind, _))) => {
+                LpExtend(LoanPath::without_element_positions(base), mc,
+                         LpInterior(opt_variant_id, InteriorElement(kind, None)))
+            }
+            LpExtend(ref base, mc, elem) => {
+                LpExtend(LoanPath::without_element_positions(base), mc, elem)
+            }
+        };
+        Rc::new(LoanPath::new(kind, lp.ty))
+    }
+
     pub fn kill_scope(&self, tcx: &TyCtxt<'tcx>) -> region::CodeExtent {
         match self.kind {
             LpVar(local_id) => tcx.region_maps.var_scope(local_id),
@@ -1123,7 +1163,12 @@ impl<'a, 'tcx> BorrowckCtxt<'a, 'tcx> {
                 }
             }
 
-            LpExtend(ref lp_base, _, LpInterior(_, InteriorElement(..))) => {
+            LpExtend(ref lp_base, _, LpInterior(_, InteriorElement(_, Some(i)))) => {
+                self.append_autoderefd_loan_path_to_string(&lp_base, out);
+                out.push_str(&format!("[{}]", i));
+            }
+
+            LpExtend(ref lp_base, _, LpInterior(_, InteriorElement(_, None))) => {
                 self.append_autoderefd_loan_path_to_string(&lp_base, out);
                 out.push_str("[..]");
             }
@@ -1209,7 +1254,8 @@ impl<'tcx> fmt::Debug for InteriorKind {
         match *self {
             InteriorField(mc::NamedField(fld)) => write!(f, "{}", fld),
             InteriorField(mc::PositionalField(i)) => write!(f, "#{}", i),
-            InteriorElement(..) => write!(f, "[]"),
+            InteriorElement(_, Some(i)) => write!(f, "[{}]", i),
+            InteriorElement(_, None) => write!(f, "[]"),
         }
     }
 }
@@ -196,13 +196,11 @@ fn loan_path_is_precise(loan_path: &LoanPath) -> bool {
         LpVar(_) | LpUpvar(_) => {
             true
         }
-        LpExtend(_, _, LpInterior(_, InteriorKind::InteriorElement(..))) => {
+        LpExtend(_, _, LpInterior(_, InteriorKind::InteriorElement(_, None))) => {
             // Paths involving element accesses a[i] do not refer to a unique
             // location, as there is no accurate tracking of the indices.
-            //
-            // (Paths involving element accesses via slice pattern bindings
-            // can in principle be tracked precisely, but that is future
-            // work. For now, continue claiming that they are imprecise.)
+            // Elements bound by a pattern on a fixed-size array do know
+            // their position and are handled like fields below.
             false
         }
         LpDowncast(ref lp_base, _) |
@@ -680,9 +678,11 @@ impl<'a, 'tcx> FlowedMoveData<'a, 'tcx> {
         // 2. Move of `a.b.c`, use of `a.b.c.d`
         // 3. Move of `a.b.c`, use of `a` or `a.b`
         //
+        // 4. Move of `a[1]` (out of a pattern), use of `a[..]` (some `a[i]`)
+        //
         // OK scenario:
         //
-        // 4. move of `a.b.c`, use of `a.b.d`
+        // 5. move of `a.b.c`, use of `a.b.d`
 
         let base_indices = self.move_data.existing_base_paths(loan_path);
         if base_indices.is_empty() {
@@ -691,6 +691,14 @@ impl<'a, 'tcx> FlowedMoveData<'a, 'tcx> {
 
         let opt_loan_path_index = self.move_data.existing_move_path(loan_path);
 
+        // For scenario 4, the array whose elements `loan_path` may refer to.
+        let opt_array_path_index = match loan_path.kind {
+            LpExtend(ref base, _, LpInterior(_, InteriorKind::InteriorElement(_, None))) => {
+                self.move_data.existing_move_path(base)
+            }
+            _ => None
+        };
+
         let mut ret = true;
 
         self.dfcx_moves.each_bit_on_entry(id, |index| {
@@ -717,6 +725,22 @@ impl<'a, 'tcx> FlowedMoveData<'a, 'tcx> {
                     });
                     if !cont { ret = false; }
                 }
+                if let Some(array_path_index) = opt_array_path_index {
+                    let cont = self.move_data.each_base_path(moved_path, |p| {
+                        let is_element = self.move_data.path_parent(p) == array_path_index;
+                        let lp = self.move_data.path_loan_path(p);
+                        match lp.kind {
+                            LpExtend(_, _, LpInterior(_, InteriorKind::InteriorElement(..)))
+                                    if is_element => {
+                                // Scenario 4: some element of the array was moved
+                                f(the_move,
+                                  &self.move_data.path_loan_path(moved_path))
+                            }
+                            _ => true
+                        }
+                    });
+                    if !cont { ret = false; }
+                }
             }
             ret
         })
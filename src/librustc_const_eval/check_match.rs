@@ -14,6 +14,7 @@ use self::WitnessPreference::*;
 
 use rustc::dep_graph::DepNode;
 use rustc::middle::const_val::ConstVal;
+use rustc_const_math::{ConstInt, ConstIsize, ConstUsize};
 use ::{eval_const_expr, eval_const_expr_partial, compare_const_vals};
 use ::{const_expr_to_pat, lookup_const_by_id};
 use ::EvalHint::ExprTypeChecked;
@@ -130,10 +131,35 @@ pub enum Constructor {
 #[derive(Clone, PartialEq)]
 enum Usefulness {
     Useful,
-    UsefulWithWitness(Vec<P<Pat>>),
+    UsefulWithWitness(Vec<Witness>),
     NotUseful
 }
 
+/// A vector of patterns, one for each column of the matrix it was computed
+/// against, that is matched by none of the matrix's rows.
+#[derive(Clone, PartialEq)]
+struct Witness(Vec<P<Pat>>);
+
+/// The maximum number of witnesses collected while checking a single match.
+/// Past this the search stops, as nobody reads a list that long anyway.
+const MAX_WITNESSES: usize = 32;
+
+impl Witness {
+    /// Folds the first `arity` patterns of the witness back into a single
+    /// pattern built from `ctor`, undoing one specialization step.
+    fn apply_constructor<'a, 'tcx>(self, cx: &MatchCheckCtxt<'a, 'tcx>, ctor: &Constructor,
+                                   arity: usize, left_ty: Ty<'tcx>) -> Witness {
+        let Witness(pats) = self;
+        let head = {
+            let subpats = pats[..arity].iter().map(|p| &**p).collect();
+            construct_witness(cx, ctor, subpats, left_ty)
+        };
+        let mut result = vec![head];
+        result.extend(pats.into_iter().skip(arity));
+        Witness(result)
+    }
+}
+
 #[derive(Copy, Clone)]
 enum WitnessPreference {
     ConstructWitness,
@@ -304,6 +330,7 @@ fn check_arms(cx: &MatchCheckCtxt,
     let mut seen = Matrix(vec![]);
     let mut printed_if_let_err = false;
     for &(ref pats, guard) in arms {
+        let mut arm_reachable = false;
         for pat in pats {
             let v = vec![&**pat];
 
@@ -341,7 +368,16 @@ fn check_arms(cx: &MatchCheckCtxt,
                         },
 
                         hir::MatchSource::Normal => {
-                            span_err!(cx.tcx.sess, pat.span, E0001, "unreachable pattern")
+                            let mut err = struct_span_err!(cx.tcx.sess, pat.span, E0001,
+                                                           "unreachable pattern");
+                            if arm_reachable {
+                                // The arm itself is fine, only this alternative of
+                                // its `|` pattern can never match.
+                                err.span_note(pat.span,
+                                              "this alternative of the `|` pattern is already \
+                                               covered by the alternatives and arms before it");
+                            }
+                            err.emit();
                         },
 
                         hir::MatchSource::TryDesugar => {
@@ -349,7 +385,7 @@ fn check_arms(cx: &MatchCheckCtxt,
                         },
                     }
                 }
-                Useful => (),
+                Useful => arm_reachable = true,
                 UsefulWithWitness(_) => bug!()
             }
             if guard.is_none() {
@@ -371,11 +407,7 @@ fn raw_pat<'a>(p: &'a Pat) -> &'a Pat {
 fn check_exhaustive(cx: &MatchCheckCtxt, sp: Span, matrix: &Matrix, source: hir::MatchSource) {
     match is_useful(cx, matrix, &[DUMMY_WILD_PAT], ConstructWitness) {
         UsefulWithWitness(pats) => {
-            let witnesses = if pats.is_empty() {
-                vec![DUMMY_WILD_PAT]
-            } else {
-                pats.iter().map(|w| &**w ).collect()
-            };
+            let witnesses: Vec<&Pat> = pats.iter().map(|&Witness(ref w)| &*w[0]).collect();
             match source {
                 hir::MatchSource::ForLoopDesugar => {
                     // `witnesses[0]` has the form `Some(<head>)`, peel off the `Some`
@@ -403,9 +435,14 @@ fn check_exhaustive(cx: &MatchCheckCtxt, sp: Span, matrix: &Matrix, source: hir:
                             let (tail, head) = pattern_strings.split_last().unwrap();
                             format!("`{}`", head.join("`, `") + "` and `" + tail)
                         },
-                        _ => {
+                        n => {
                             let (head, tail) = pattern_strings.split_at(LIMIT);
-                            format!("`{}` and {} more", head.join("`, `"), tail.len())
+                            if n < MAX_WITNESSES {
+                                format!("`{}` and {} more", head.join("`, `"), tail.len())
+                            } else {
+                                // The search for witnesses was cut short.
+                                format!("`{}` and {} or more", head.join("`, `"), tail.len())
+                            }
                         }
                     };
                     span_err!(cx.tcx.sess, sp, E0004,
@@ -425,8 +462,27 @@ fn check_exhaustive(cx: &MatchCheckCtxt, sp: Span, matrix: &Matrix, source: hir:
 fn const_val_to_expr(value: &ConstVal) -> P<hir::Expr> {
     let node = match value {
         &ConstVal::Bool(b) => ast::LitKind::Bool(b),
+        &ConstVal::Char(c) => ast::LitKind::Char(c),
+        &ConstVal::Integral(i) if i.is_negative() => {
+            // Negative literals are a negation applied to the magnitude.
+            let magnitude = (!i.to_u64_unchecked()).wrapping_add(1);
+            let lit = ast::LitKind::Int(magnitude, ast::LitIntType::Unsuffixed);
+            return P(hir::Expr {
+                id: 0,
+                node: hir::ExprUnary(hir::UnNeg, lit_to_expr(lit)),
+                span: DUMMY_SP,
+                attrs: None,
+            });
+        }
+        &ConstVal::Integral(i) => {
+            ast::LitKind::Int(i.to_u64_unchecked(), ast::LitIntType::Unsuffixed)
+        }
         _ => bug!()
     };
+    lit_to_expr(node)
+}
+
+fn lit_to_expr(node: ast::LitKind) -> P<hir::Expr> {
     P(hir::Expr {
         id: 0,
         node: hir::ExprLit(P(Spanned { node: node, span: DUMMY_SP })),
@@ -603,6 +659,9 @@ fn construct_witness<'a,'tcx>(cx: &MatchCheckCtxt<'a,'tcx>, ctor: &Constructor,
         _ => {
             match *ctor {
                 ConstantValue(ref v) => PatKind::Lit(const_val_to_expr(v)),
+                ConstantRange(ref lo, ref hi) => {
+                    PatKind::Range(const_val_to_expr(lo), const_val_to_expr(hi))
+                }
                 _ => PatKind::Wild,
             }
         }
@@ -631,22 +690,38 @@ fn missing_constructors(cx: &MatchCheckCtxt, &Matrix(ref rows): &Matrix,
     let used_constructors: Vec<Constructor> = rows.iter()
         .flat_map(|row| pat_constructors(cx, row[0], left_ty, max_slice_length))
         .collect();
-    all_constructors(cx, left_ty, max_slice_length)
+    let all_constructors = all_constructors(cx, left_ty, max_slice_length);
+    split_int_constructors(cx, all_constructors, rows, left_ty)
         .into_iter()
-        .filter(|c| !used_constructors.contains(c))
+        .filter(|c| !used_constructors.iter().any(|used| constructor_covered_by(left_ty, c, used)))
         .collect()
 }
 
+/// Whether every value matched by `ctor` is also matched by `used`. Apart
+/// from integral ranges, constructors only ever cover themselves.
+fn constructor_covered_by(ty: Ty, ctor: &Constructor, used: &Constructor) -> bool {
+    match (int_constructor_range(ty, ctor), int_constructor_range(ty, used)) {
+        (Some((lo, hi)), Some((used_lo, used_hi))) => used_lo <= lo && hi <= used_hi,
+        _ => ctor == used
+    }
+}
+
 /// This determines the set of all possible constructors of a pattern matching
 /// values of type `left_ty`. For vectors, this would normally be an infinite set
 /// but is instead bounded by the maximum fixed length of slice patterns in
-/// the column of patterns being analyzed.
-fn all_constructors(_cx: &MatchCheckCtxt, left_ty: Ty,
+/// the column of patterns being analyzed. Integral types and `char` are
+/// represented by ranges spanning all of their values.
+fn all_constructors(cx: &MatchCheckCtxt, left_ty: Ty,
                     max_slice_length: usize) -> Vec<Constructor> {
     match left_ty.sty {
         ty::TyBool =>
             [true, false].iter().map(|b| ConstantValue(ConstVal::Bool(*b))).collect(),
 
+        ty::TyInt(_) | ty::TyUint(_) | ty::TyChar =>
+            int_type_ranges(cx, left_ty).into_iter().map(|(lo, hi)| {
+                int_range_constructor(cx, left_ty, lo, hi)
+            }).collect(),
+
         ty::TyRef(_, ty::TypeAndMut { ty, .. }) => match ty.sty {
             ty::TySlice(_) =>
                 (0..max_slice_length+1).map(|length| Slice(length)).collect(),
@@ -658,6 +733,151 @@ fn all_constructors(_cx: &MatchCheckCtxt, left_ty: Ty,
     }
 }
 
+// Values of integral types and `char` are mapped to `u64`s in a way that
+// preserves their order, so that ranges of all those types can be compared
+// and split uniformly. Signed values have their sign bit flipped.
+const SIGN_BIT: u64 = 1 << 63;
+
+fn int_bit_width(cx: &MatchCheckCtxt, ty: Ty) -> Option<(bool, u32)> {
+    let int_ty = match ty.sty {
+        ty::TyInt(ast::IntTy::Is) => cx.tcx.sess.target.int_type,
+        ty::TyInt(int_ty) => int_ty,
+        ty::TyUint(uint_ty) => {
+            let uint_ty = match uint_ty {
+                ast::UintTy::Us => cx.tcx.sess.target.uint_type,
+                uint_ty => uint_ty
+            };
+            return Some((false, match uint_ty {
+                ast::UintTy::U8 => 8,
+                ast::UintTy::U16 => 16,
+                ast::UintTy::U32 => 32,
+                _ => 64
+            }));
+        }
+        _ => return None
+    };
+    Some((true, match int_ty {
+        ast::IntTy::I8 => 8,
+        ast::IntTy::I16 => 16,
+        ast::IntTy::I32 => 32,
+        _ => 64
+    }))
+}
+
+/// The encoded ranges covering every value of an integral or `char` type.
+fn int_type_ranges(cx: &MatchCheckCtxt, ty: Ty) -> Vec<(u64, u64)> {
+    match int_bit_width(cx, ty) {
+        Some((true, bits)) => {
+            let min = -1i64 << (bits - 1);
+            vec![((min as u64) ^ SIGN_BIT, (!min as u64) ^ SIGN_BIT)]
+        }
+        Some((false, 64)) => vec![(0, !0)],
+        Some((false, bits)) => vec![(0, (1 << bits) - 1)],
+        // Surrogate code points are not valid `char`s.
+        None => vec![(0, 0xD7FF), (0xE000, 0x10FFFF)]
+    }
+}
+
+fn encode_int(ty: Ty, value: &ConstVal) -> Option<u64> {
+    match (&ty.sty, value) {
+        (&ty::TyInt(_), &ConstVal::Integral(i)) => Some(i.to_u64_unchecked() ^ SIGN_BIT),
+        (&ty::TyUint(_), &ConstVal::Integral(i)) => Some(i.to_u64_unchecked()),
+        (&ty::TyChar, &ConstVal::Char(c)) => Some(c as u64),
+        _ => None
+    }
+}
+
+fn decode_int(cx: &MatchCheckCtxt, ty: Ty, value: u64) -> ConstVal {
+    let target = &cx.tcx.sess.target;
+    match ty.sty {
+        ty::TyInt(int_ty) => {
+            let v = (value ^ SIGN_BIT) as i64;
+            ConstVal::Integral(match int_ty {
+                ast::IntTy::I8 => ConstInt::I8(v as i8),
+                ast::IntTy::I16 => ConstInt::I16(v as i16),
+                ast::IntTy::I32 => ConstInt::I32(v as i32),
+                ast::IntTy::I64 => ConstInt::I64(v),
+                ast::IntTy::Is => ConstInt::Isize(ConstIsize::new(v, target.int_type).unwrap()),
+            })
+        }
+        ty::TyUint(uint_ty) => {
+            let v = value;
+            ConstVal::Integral(match uint_ty {
+                ast::UintTy::U8 => ConstInt::U8(v as u8),
+                ast::UintTy::U16 => ConstInt::U16(v as u16),
+                ast::UintTy::U32 => ConstInt::U32(v as u32),
+                ast::UintTy::U64 => ConstInt::U64(v),
+                ast::UintTy::Us => ConstInt::Usize(ConstUsize::new(v, target.uint_type).unwrap()),
+            })
+        }
+        ty::TyChar => ConstVal::Char(::std::char::from_u32(value as u32).unwrap()),
+        _ => bug!("decode_int: unexpected type {:?}", ty)
+    }
+}
+
+/// The encoded bounds of a value or range constructor of an integral or
+/// `char` type.
+fn int_constructor_range(ty: Ty, ctor: &Constructor) -> Option<(u64, u64)> {
+    match *ctor {
+        ConstantValue(ref value) => encode_int(ty, value).map(|v| (v, v)),
+        ConstantRange(ref lo, ref hi) => match (encode_int(ty, lo), encode_int(ty, hi)) {
+            (Some(lo), Some(hi)) => Some((lo, hi)),
+            _ => None
+        },
+        _ => None
+    }
+}
+
+fn int_range_constructor(cx: &MatchCheckCtxt, ty: Ty, lo: u64, hi: u64) -> Constructor {
+    if lo == hi {
+        ConstantValue(decode_int(cx, ty, lo))
+    } else {
+        ConstantRange(decode_int(cx, ty, lo), decode_int(cx, ty, hi))
+    }
+}
+
+/// Splits the integral range constructors in `ctors` at the boundaries of
+/// the values and ranges in the first column of `rows`. Each resulting
+/// constructor is then either entirely covered by, or entirely disjoint from,
+/// the first pattern of every row, so specializing by it is exact.
+fn split_int_constructors(cx: &MatchCheckCtxt, ctors: Vec<Constructor>,
+                          rows: &[Vec<&Pat>], left_ty: Ty) -> Vec<Constructor> {
+    match left_ty.sty {
+        ty::TyInt(_) | ty::TyUint(_) | ty::TyChar => {}
+        _ => return ctors
+    }
+
+    let mut borders = vec![];
+    for row in rows {
+        for ctor in pat_constructors(cx, row[0], left_ty, 0) {
+            if let Some((lo, hi)) = int_constructor_range(left_ty, &ctor) {
+                borders.push(lo);
+                if hi != !0 {
+                    borders.push(hi + 1);
+                }
+            }
+        }
+    }
+    borders.sort();
+    borders.dedup();
+
+    let mut result = vec![];
+    for ctor in ctors {
+        match int_constructor_range(left_ty, &ctor) {
+            Some((lo, hi)) => {
+                let mut start = lo;
+                for &border in borders.iter().filter(|&&b| lo < b && b <= hi) {
+                    result.push(int_range_constructor(cx, left_ty, start, border - 1));
+                    start = border;
+                }
+                result.push(int_range_constructor(cx, left_ty, start, hi));
+            }
+            None => result.push(ctor)
+        }
+    }
+    result
+}
+
 // Algorithm from http://moscova.inria.fr/~maranget/papers/warn/index.html
 //
 // Whether a vector `v` of patterns is 'useful' in relation to a set of such
@@ -667,7 +887,8 @@ fn all_constructors(_cx: &MatchCheckCtxt, left_ty: Ty,
 // This is used both for reachability checking (if a pattern isn't useful in
 // relation to preceding patterns, it is not reachable) and exhaustiveness
 // checking (if a wildcard pattern is useful in relation to a matrix, the
-// matrix isn't exhaustive).
+// matrix isn't exhaustive). In the latter case every witness found is
+// collected, up to `MAX_WITNESSES`.
 
 // Note: is_useful doesn't work on empty types, as the paper notes.
 // So it assumes that v is non-empty.
@@ -680,7 +901,9 @@ fn is_useful(cx: &MatchCheckCtxt,
     debug!("{:?}", matrix);
     if rows.is_empty() {
         return match witness {
-            ConstructWitness => UsefulWithWitness(vec!()),
+            ConstructWitness => UsefulWithWitness(vec![
+                Witness(v.iter().map(|_| P(DUMMY_WILD_PAT.clone())).collect())
+            ]),
             LeaveOutWitness => Useful
         };
     }
@@ -715,23 +938,11 @@ fn is_useful(cx: &MatchCheckCtxt,
     if constructors.is_empty() {
         let constructors = missing_constructors(cx, matrix, left_ty, max_slice_length);
         if constructors.is_empty() {
-            all_constructors(cx, left_ty, max_slice_length).into_iter().map(|c| {
-                match is_useful_specialized(cx, matrix, v, c.clone(), left_ty, witness) {
-                    UsefulWithWitness(pats) => UsefulWithWitness({
-                        let arity = constructor_arity(cx, &c, left_ty);
-                        let mut result = {
-                            let pat_slice = &pats[..];
-                            let subpats: Vec<_> = (0..arity).map(|i| {
-                                pat_slice.get(i).map_or(DUMMY_WILD_PAT, |p| &**p)
-                            }).collect();
-                            vec![construct_witness(cx, &c, subpats, left_ty)]
-                        };
-                        result.extend(pats.into_iter().skip(arity));
-                        result
-                    }),
-                    result => result
-                }
-            }).find(|result| result != &NotUseful).unwrap_or(NotUseful)
+            let constructors = split_int_constructors(cx,
+                                                      all_constructors(cx, left_ty,
+                                                                       max_slice_length),
+                                                      rows, left_ty);
+            is_useful_for_constructors(cx, matrix, v, constructors, left_ty, witness)
         } else {
             let matrix = rows.iter().filter_map(|r| {
                 if pat_is_binding_or_wild(&cx.tcx.def_map.borrow(), raw_pat(r[0])) {
@@ -741,22 +952,71 @@ fn is_useful(cx: &MatchCheckCtxt,
                 }
             }).collect();
             match is_useful(cx, &matrix, &v[1..], witness) {
-                UsefulWithWitness(pats) => {
-                    let mut new_pats: Vec<_> = constructors.into_iter().map(|constructor| {
-                        let arity = constructor_arity(cx, &constructor, left_ty);
-                        let wild_pats = vec![DUMMY_WILD_PAT; arity];
-                        construct_witness(cx, &constructor, wild_pats, left_ty)
-                    }).collect();
-                    new_pats.extend(pats);
-                    UsefulWithWitness(new_pats)
+                UsefulWithWitness(witnesses) => {
+                    // If no row names a constructor in this column, listing
+                    // every one of them is just noise; a wildcard says it all.
+                    let column_is_wild = rows.iter().all(|r| {
+                        pat_constructors(cx, r[0], left_ty, max_slice_length).is_empty()
+                    });
+                    let heads: Vec<P<Pat>> = if column_is_wild {
+                        vec![P(DUMMY_WILD_PAT.clone())]
+                    } else {
+                        constructors.into_iter().map(|constructor| {
+                            let arity = constructor_arity(cx, &constructor, left_ty);
+                            let wild_pats = vec![DUMMY_WILD_PAT; arity];
+                            construct_witness(cx, &constructor, wild_pats, left_ty)
+                        }).collect()
+                    };
+                    let mut new_witnesses = vec![];
+                    'heads: for head in heads {
+                        for &Witness(ref pats) in &witnesses {
+                            if new_witnesses.len() == MAX_WITNESSES {
+                                break 'heads;
+                            }
+                            let mut new_pats = vec![head.clone()];
+                            new_pats.extend(pats.iter().cloned());
+                            new_witnesses.push(Witness(new_pats));
+                        }
+                    }
+                    UsefulWithWitness(new_witnesses)
                 },
                 result => result
             }
         }
     } else {
-        constructors.into_iter().map(|c|
-            is_useful_specialized(cx, matrix, v, c.clone(), left_ty, witness)
-        ).find(|result| result != &NotUseful).unwrap_or(NotUseful)
+        let constructors = split_int_constructors(cx, constructors, rows, left_ty);
+        is_useful_for_constructors(cx, matrix, v, constructors, left_ty, witness)
+    }
+}
+
+/// Checks whether `v` is useful when specialized by any of `constructors`.
+/// Without witnesses the search stops at the first useful constructor;
+/// otherwise the witnesses found for all of them are gathered.
+fn is_useful_for_constructors<'a, 'tcx>(cx: &MatchCheckCtxt<'a, 'tcx>, matrix: &Matrix,
+                                        v: &[&Pat], constructors: Vec<Constructor>,
+                                        left_ty: Ty<'tcx>, witness: WitnessPreference)
+                                        -> Usefulness {
+    let mut witnesses = vec![];
+    for c in constructors {
+        match is_useful_specialized(cx, matrix, v, c.clone(), left_ty, witness) {
+            UsefulWithWitness(pats) => {
+                let arity = constructor_arity(cx, &c, left_ty);
+                witnesses.extend(pats.into_iter().map(|w| {
+                    w.apply_constructor(cx, &c, arity, left_ty)
+                }));
+                if witnesses.len() >= MAX_WITNESSES {
+                    witnesses.truncate(MAX_WITNESSES);
+                    break;
+                }
+            }
+            Useful => return Useful,
+            NotUseful => {}
+        }
+    }
+    if witnesses.is_empty() {
+        NotUseful
+    } else {
+        UsefulWithWitness(witnesses)
     }
 }
 
@@ -1056,7 +1316,7 @@ fn is_refutable<A, F>(cx: &MatchCheckCtxt, pat: &Pat, refutable: F) -> Option<A>
 {
     let pats = Matrix(vec!(vec!(pat)));
     match is_useful(cx, &pats, &[DUMMY_WILD_PAT], ConstructWitness) {
-        UsefulWithWitness(pats) => Some(refutable(&pats[0])),
+        UsefulWithWitness(pats) => Some(refutable(&pats[0].0[0])),
         NotUseful => None,
         Useful => bug!()
     }
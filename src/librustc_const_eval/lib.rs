@@ -26,7 +26,7 @@
 #![feature(rustc_private)]
 #![feature(staged_api)]
 #![feature(rustc_diagnostic_macros)]
-#![feature(slice_patterns)]
+#![cfg_attr(stage0, feature(slice_patterns))]
 #![feature(iter_arith)]
 #![feature(question_mark)]
 #![feature(box_patterns)]
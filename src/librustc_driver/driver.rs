@@ -40,19 +40,23 @@ use rustc::hir::lowering::{lower_crate, LoweringContext};
 use rustc_passes::{no_asm, loops, consts, const_fn, rvalues, static_recursion};
 use rustc_const_eval::check_match;
 use super::Compilation;
+use derive_registrar;
 
 use serialize::json;
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::ffi::{OsString, OsStr};
 use std::fs;
 use std::io::{self, Write};
+use std::mem;
 use std::path::{Path, PathBuf};
 use syntax::ast::{self, NodeIdAssigner};
 use syntax::attr::{self, AttrMetaMethods};
+use syntax::codemap::Span;
 use syntax::diagnostics;
+use syntax::feature_gate::GatedCfgAttr;
 use syntax::fold::Folder;
 use syntax::parse::{self, PResult, token};
 use syntax::util::node_count::NodeCounter;
@@ -141,8 +145,7 @@ pub fn compile_input(sess: &Session,
                                                            dep_graph));
 
         // Discard MTWT tables that aren't required past lowering to HIR.
-        if !sess.opts.debugging_opts.keep_mtwt_tables &&
-           !sess.opts.debugging_opts.save_analysis {
+        if !sess.opts.debugging_opts.keep_mtwt_tables && !sess.save_analysis() {
             syntax::ext::mtwt::clear_tables();
         }
 
@@ -179,8 +182,7 @@ pub fn compile_input(sess: &Session,
              "early lint checks",
              || lint::check_ast_crate(sess, &expanded_crate));
 
-        let opt_crate = if sess.opts.debugging_opts.keep_ast ||
-                           sess.opts.debugging_opts.save_analysis {
+        let opt_crate = if sess.opts.debugging_opts.keep_ast || sess.save_analysis() {
             Some(&expanded_crate)
         } else {
             drop(expanded_crate);
@@ -545,9 +547,9 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         syntax::std_inject::maybe_inject_crates_ref(krate, sess.opts.alt_std_name.clone())
     });
 
-    let macros = time(time_passes,
-                      "macro loading",
-                      || macro_import::read_macro_defs(sess, &cstore, &krate, crate_name));
+    let (macros, custom_derives) = time(time_passes, "macro loading", || {
+        macro_import::read_macro_defs(sess, &cstore, &krate, crate_name)
+    });
 
     let mut addl_plugins = Some(addl_plugins);
     let registrars = time(time_passes, "plugin loading", || {
@@ -576,8 +578,9 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         }
     });
 
-    let Registry { syntax_exts, early_lint_passes, late_lint_passes, lint_groups,
+    let Registry { mut syntax_exts, early_lint_passes, late_lint_passes, lint_groups,
                    llvm_passes, attributes, mir_passes, .. } = registry;
+    syntax_exts.extend(custom_derives);
 
     sess.track_errors(|| {
         let mut ls = sess.lint_store.borrow_mut();
@@ -625,10 +628,12 @@ pub fn phase_2_configure_and_expand(sess: &Session,
             recursion_limit: sess.recursion_limit.get(),
             trace_mac: sess.opts.debugging_opts.trace_macros,
         };
+        let mut macro_resolver = resolve::DeclMacroResolver::new();
         let mut ecx = syntax::ext::base::ExtCtxt::new(&sess.parse_sess,
                                                       krate.config.clone(),
                                                       cfg,
-                                                      &mut feature_gated_cfgs);
+                                                      &mut feature_gated_cfgs,
+                                                      &mut macro_resolver);
         syntax_ext::register_builtins(&mut ecx.syntax_env);
         let (ret, macro_names) = syntax::ext::expand::expand_crate(ecx,
                                                                    macros,
@@ -641,6 +646,10 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         ret
     });
 
+    // Crates loaded for their macros are only used if one of those macros
+    // was actually expanded.
+    cstore.mark_expanded_macros(sess.codemap());
+
     // Needs to go *after* expansion to be able to check the results
     // of macro expansion.  This runs before #[cfg] to try to catch as
     // much as possible (e.g. help the programmer avoid platform
@@ -672,6 +681,12 @@ pub fn phase_2_configure_and_expand(sess: &Session,
             feature_gated_cfgs.dedup();
             for cfg in &feature_gated_cfgs {
                 cfg.check_and_emit(sess.diagnostic(), &features, sess.codemap());
+                if let GatedCfgAttr::UsedCfg(span, ref name, ref value) = *cfg {
+                    let value = value.as_ref().map(|v| &v[..]);
+                    if let Some(msg) = sess.opts.check_cfg.check(&krate.config, name, value) {
+                        sess.unexpected_cfgs.borrow_mut().push((span, msg));
+                    }
+                }
             }
         });
 
@@ -682,6 +697,17 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         syntax::test::modify_for_testing(&sess.parse_sess, &sess.opts.cfg, krate, sess.diagnostic())
     });
 
+    krate = time(time_passes, "maybe creating a proc-macro registrar", || {
+        let crate_types = sess.crate_types.borrow();
+        let is_proc_macro_crate = crate_types.contains(&config::CrateTypeProcMacro);
+        syntax_ext::proc_macro_registrar::modify(&sess.parse_sess,
+                                                 krate,
+                                                 is_proc_macro_crate,
+                                                 crate_types.len(),
+                                                 sess.diagnostic(),
+                                                 &sess.features.borrow())
+    });
+
     krate = time(time_passes,
                  "prelude injection",
                  || syntax::std_inject::maybe_inject_prelude(&sess.parse_sess, krate));
@@ -716,6 +742,12 @@ pub fn phase_2_configure_and_expand(sess: &Session,
         println!("Post-expansion node count: {}", count_nodes(&krate));
     }
 
+    // A crate recovered from syntax errors has had items or expressions left
+    // out. The errors have been reported; the rest of it can't be analysed.
+    if sess.parse_sess.ast_is_incomplete() {
+        return Err(sess.err_count());
+    }
+
     Ok(krate)
 }
 
@@ -735,6 +767,14 @@ pub fn assign_node_ids(sess: &Session, krate: ast::Crate) -> ast::Crate {
                      "assigning node ids",
                      || NodeIdAssigner { sess: sess }.fold_crate(krate));
 
+    // Now that items have ids, the unexpected `cfg`s can be reported at the
+    // innermost item containing them, where they can be allowed.
+    let unexpected_cfgs = mem::replace(&mut *sess.unexpected_cfgs.borrow_mut(), vec![]);
+    for (span, msg) in unexpected_cfgs {
+        let id = innermost_item_containing(&krate, span);
+        sess.add_lint(lint::builtin::UNEXPECTED_CFGS, id, span, msg);
+    }
+
     if sess.opts.debugging_opts.ast_json {
         println!("{}", json::as_json(&krate));
     }
@@ -742,6 +782,53 @@ pub fn assign_node_ids(sess: &Session, krate: ast::Crate) -> ast::Crate {
     krate
 }
 
+/// Returns the id of the innermost item, trait item or impl item whose source
+/// contains `span`, or the crate's id if there is none.
+fn innermost_item_containing(krate: &ast::Crate, span: Span) -> ast::NodeId {
+    struct ItemFinder {
+        span: Span,
+        id: ast::NodeId,
+    }
+
+    impl ItemFinder {
+        fn contains(&self, outer: Span) -> bool {
+            outer.lo <= self.span.lo && self.span.hi <= outer.hi
+        }
+    }
+
+    impl<'v> visit::Visitor<'v> for ItemFinder {
+        fn visit_item(&mut self, item: &ast::Item) {
+            // The contents of a module in its own file are outside its span.
+            let inner = match item.node {
+                ast::ItemKind::Mod(ref m) => self.contains(m.inner),
+                _ => false,
+            };
+            if inner || self.contains(item.span) {
+                self.id = item.id;
+                visit::walk_item(self, item);
+            }
+        }
+
+        fn visit_trait_item(&mut self, trait_item: &ast::TraitItem) {
+            if self.contains(trait_item.span) {
+                self.id = trait_item.id;
+                visit::walk_trait_item(self, trait_item);
+            }
+        }
+
+        fn visit_impl_item(&mut self, impl_item: &ast::ImplItem) {
+            if self.contains(impl_item.span) {
+                self.id = impl_item.id;
+                visit::walk_impl_item(self, impl_item);
+            }
+        }
+    }
+
+    let mut finder = ItemFinder { span: span, id: ast::CRATE_NODE_ID };
+    visit::walk_crate(&mut finder, krate);
+    finder.id
+}
+
 /// Run the resolution, typechecking, region checking and other
 /// miscellaneous analysis passes on the crate. Return various
 /// structures carrying the results of the analysis.
@@ -779,11 +866,17 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
         freevars,
         export_map,
         trait_map,
+        traits_in_scope,
         glob_map,
+        unused_externs,
     } = time(sess.time_passes(),
              "name resolution",
              || resolve::resolve_crate(sess, &hir_map, make_glob_map));
 
+    if let Some(ref path) = sess.opts.debugging_opts.unused_externs_json {
+        write_unused_externs(sess, Path::new(path), &unused_externs);
+    }
+
     let mut analysis = ty::CrateAnalysis {
         export_map: export_map,
         access_levels: AccessLevels::default(),
@@ -805,6 +898,7 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
     sess.plugin_registrar_fn.set(time(time_passes, "looking for plugin registrar", || {
         plugin::build::find_plugin_registrar(sess.diagnostic(), &hir_map)
     }));
+    sess.derive_registrar_fn.set(derive_registrar::find(&hir_map));
 
     let region_map = time(time_passes,
                           "region resolution",
@@ -836,7 +930,7 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
              || rustc_incremental::load_dep_graph(tcx));
 
         // passes are timed inside typeck
-        try_with_f!(typeck::check_crate(tcx, trait_map), (tcx, None, analysis));
+        try_with_f!(typeck::check_crate(tcx, trait_map, traits_in_scope), (tcx, None, analysis));
 
         time(time_passes,
              "const checking",
@@ -938,6 +1032,20 @@ pub fn phase_3_run_analysis_passes<'tcx, F, R>(sess: &'tcx Session,
     })
 }
 
+/// Write the names of the unused `--extern` crates to `path` as a JSON object,
+/// e.g. `{"unused_externs":["foo","bar"]}`, so that build systems can drop them.
+fn write_unused_externs(sess: &Session, path: &Path, unused_externs: &[String]) {
+    let names = unused_externs.iter().map(|name| json::Json::String(name.clone())).collect();
+    let mut obj = BTreeMap::new();
+    obj.insert("unused_externs".to_string(), json::Json::Array(names));
+    let result = fs::File::create(path).and_then(|mut file| {
+        write!(file, "{}", json::Json::Object(obj))
+    });
+    if let Err(e) = result {
+        sess.err(&format!("could not write unused externs to {}: {}", path.display(), e));
+    }
+}
+
 /// Run the translation phase to LLVM, after which the AST and analysis can
 pub fn phase_4_translate_to_llvm<'tcx>(tcx: &TyCtxt<'tcx>,
                                        mut mir_map: MirMap<'tcx>,
@@ -1022,6 +1130,11 @@ fn escape_dep_filename(filename: &str) -> String {
     filename.replace(" ", "\\ ")
 }
 
+fn escape_dep_env(value: &str) -> String {
+    // Each variable is written on a single comment line
+    value.replace("\\", "\\\\").replace("\n", "\\n").replace("\r", "\\r")
+}
+
 fn write_out_deps(sess: &Session, outputs: &OutputFilenames, id: &str) {
     let mut out_filenames = Vec::new();
     for output_type in sess.opts.output_types.keys() {
@@ -1049,14 +1162,21 @@ fn write_out_deps(sess: &Session, outputs: &OutputFilenames, id: &str) {
         (|| -> io::Result<()> {
             // Build a list of files used to compile the output and
             // write Makefile-compatible dependency rules
-            let files: Vec<String> = sess.codemap()
-                                         .files
-                                         .borrow()
-                                         .iter()
-                                         .filter(|fmap| fmap.is_real_file())
-                                         .filter(|fmap| !fmap.is_imported())
-                                         .map(|fmap| escape_dep_filename(&fmap.name))
-                                         .collect();
+            let mut files: Vec<String> = sess.codemap()
+                                             .files
+                                             .borrow()
+                                             .iter()
+                                             .filter(|fmap| fmap.is_real_file())
+                                             .filter(|fmap| !fmap.is_imported())
+                                             .map(|fmap| escape_dep_filename(&fmap.name))
+                                             .collect();
+            // Files read by macros don't necessarily end up in the codemap,
+            // and those that do may appear more than once
+            for path in sess.parse_sess.tracked_files() {
+                files.push(escape_dep_filename(&path));
+            }
+            let mut seen = HashSet::new();
+            files.retain(|path| seen.insert(path.clone()));
             let mut file = fs::File::create(&deps_filename)?;
             for path in &out_filenames {
                 write!(file, "{}: {}\n\n", path.display(), files.join(" "))?;
@@ -1068,6 +1188,22 @@ fn write_out_deps(sess: &Session, outputs: &OutputFilenames, id: &str) {
             for path in files {
                 writeln!(file, "{}:", path)?;
             }
+
+            // Environment variables read by `env!` and `option_env!` can't be
+            // expressed as make dependencies, so record them in comments
+            // for build systems that know to look for them
+            let env_vars = sess.parse_sess.tracked_env_vars();
+            if !env_vars.is_empty() {
+                writeln!(file, "")?;
+            }
+            for (name, value) in env_vars {
+                match value {
+                    Some(value) => {
+                        writeln!(file, "# env-dep:{}={}", name, escape_dep_env(&value))?
+                    }
+                    None => writeln!(file, "# env-dep:{}", name)?,
+                }
+            }
             Ok(())
         })();
 
@@ -1101,6 +1237,9 @@ pub fn collect_crate_types(session: &Session, attrs: &[ast::Attribute]) -> Vec<c
                              Some(config::CrateTypeStaticlib)
                          }
                          Some(ref n) if *n == "bin" => Some(config::CrateTypeExecutable),
+                         Some(ref n) if *n == "proc-macro" => {
+                             Some(config::CrateTypeProcMacro)
+                         }
                          Some(_) => {
                              session.add_lint(lint::builtin::UNKNOWN_CRATE_TYPES,
                                               ast::CRATE_NODE_ID,
@@ -105,6 +105,7 @@ pub mod test;
 pub mod driver;
 pub mod pretty;
 pub mod target_features;
+mod derive_registrar;
 
 
 const BUG_REPORT_URL: &'static str = "https://github.com/rust-lang/rust/blob/master/CONTRIBUTING.\
@@ -483,15 +484,21 @@ impl<'a> CompilerCalls<'a> for RustcDefaultCalls {
             control.after_llvm.stop = Compilation::Stop;
         }
 
-        if sess.opts.debugging_opts.save_analysis {
-            control.after_analysis.callback = box |state| {
+        if sess.save_analysis() {
+            let format = if sess.opts.debugging_opts.save_analysis_json {
+                save::Format::Json
+            } else {
+                save::Format::Csv
+            };
+            control.after_analysis.callback = box move |state| {
                 time(state.session.time_passes(), "save analysis", || {
                     save::process_crate(state.tcx.unwrap(),
                                         state.lcx.unwrap(),
                                         state.krate.unwrap(),
                                         state.analysis.unwrap(),
                                         state.crate_name.unwrap(),
-                                        state.out_dir)
+                                        state.out_dir,
+                                        format)
                 });
             };
             control.after_analysis.run_callback_on_error = true;
@@ -63,6 +63,7 @@ use rustc::mir::mir_map::MirMap;
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub enum PpSourceMode {
     PpmNormal,
+    PpmFormatted,
     PpmEveryBodyLoops,
     PpmExpanded,
     PpmIdentified,
@@ -98,6 +99,7 @@ pub fn parse_pretty(sess: &Session,
     let opt_second = split.next();
     let first = match (first, extended) {
         ("normal", _) => PpmSource(PpmNormal),
+        ("formatted", true) => PpmSource(PpmFormatted),
         ("identified", _) => PpmSource(PpmIdentified),
         ("everybody_loops", true) => PpmSource(PpmEveryBodyLoops),
         ("expanded", _) => PpmSource(PpmExpanded),
@@ -113,7 +115,8 @@ pub fn parse_pretty(sess: &Session,
         _ => {
             if extended {
                 sess.fatal(&format!("argument to `unpretty` must be one of `normal`, \
-                                     `expanded`, `flowgraph[,unlabelled]=<nodeid>`, \
+                                     `formatted`, `expanded`, \
+                                     `flowgraph[,unlabelled]=<nodeid>`, \
                                      `identified`, `expanded,identified`, `everybody_loops`, \
                                      `hir`, `hir,identified`, `hir,typed`, or `mir`; got {}",
                                     name));
@@ -154,7 +157,7 @@ impl PpSourceMode {
         where F: FnOnce(&PrinterSupport, B) -> A
     {
         match *self {
-            PpmNormal | PpmEveryBodyLoops | PpmExpanded => {
+            PpmNormal | PpmFormatted | PpmEveryBodyLoops | PpmExpanded => {
                 let annotation = NoAnn {
                     sess: sess,
                     ast_map: ast_map,
@@ -585,6 +588,7 @@ impl UserIdentifiedItem {
 fn needs_ast_map(ppm: &PpMode, opt_uii: &Option<UserIdentifiedItem>) -> bool {
     match *ppm {
         PpmSource(PpmNormal) |
+        PpmSource(PpmFormatted) |
         PpmSource(PpmEveryBodyLoops) |
         PpmSource(PpmIdentified) => opt_uii.is_some(),
 
@@ -602,6 +606,7 @@ fn needs_ast_map(ppm: &PpMode, opt_uii: &Option<UserIdentifiedItem>) -> bool {
 fn needs_expansion(ppm: &PpMode) -> bool {
     match *ppm {
         PpmSource(PpmNormal) |
+        PpmSource(PpmFormatted) |
         PpmSource(PpmEveryBodyLoops) |
         PpmSource(PpmIdentified) => false,
 
@@ -761,6 +766,20 @@ pub fn pretty_print_input(sess: Session,
     let mut out = Vec::new();
 
     match (ppm, opt_uii) {
+        (PpmSource(PpmFormatted), _) => {
+            // Silently ignores an identified node.
+            let max_width = sess.opts.debugging_opts.pretty_width
+                                .unwrap_or(pprust::DEFAULT_FORMAT_COLUMNS);
+            let out: &mut Write = &mut out;
+            pprust::format_crate(sess.codemap(),
+                                 sess.diagnostic(),
+                                 &krate,
+                                 src_name.to_string(),
+                                 &mut rdr,
+                                 box out,
+                                 max_width)
+        }
+
         (PpmSource(s), _) => {
             // Silently ignores an identified node.
             let out: &mut Write = &mut out;
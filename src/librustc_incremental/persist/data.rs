@@ -20,6 +20,10 @@ pub struct SerializedDepGraph {
     pub nodes: Vec<DepNode<DefPathIndex>>,
     pub edges: Vec<SerializedEdge>,
     pub hashes: Vec<SerializedHash>,
+
+    /// the files and environment variables read by macros, whose
+    /// `MacroInput` nodes are dirty if their hash changed
+    pub inputs: Vec<SerializedInput>,
 }
 
 pub type SerializedEdge = (DepNode<DefPathIndex>, DepNode<DefPathIndex>);
@@ -31,3 +35,14 @@ pub struct SerializedHash {
     /// the hash itself, computed by `calculate_item_hash`
     pub hash: u64,
 }
+
+#[derive(Debug, RustcEncodable, RustcDecodable)]
+pub enum SerializedInput {
+    /// a file read by `include!`, `include_str!` or `include_bytes!`, with
+    /// the hash of its contents (`None` if it couldn't be read)
+    File(String, Option<u64>),
+
+    /// an environment variable read by `env!` or `option_env!`, with the
+    /// hash of its value (`None` if it wasn't set)
+    EnvVar(String, Option<u64>),
+}
@@ -0,0 +1,102 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The files and environment variables read during macro expansion. Each
+//! one gets a `MacroInput` node, read by the `Hir` node of the item the
+//! macro was expanded in. Their hashes are saved with the dep-graph, and the
+//! nodes of those that changed start out dirty when it is loaded.
+
+use rustc::dep_graph::DepNode;
+use rustc::hir::def_id::DefId;
+use rustc::ty;
+use std::env;
+use std::fs::File;
+use std::hash::{Hash, SipHasher, Hasher};
+use std::io::Read;
+use syntax::parse::MacroInput;
+
+use super::data::SerializedInput;
+
+/// Record an edge from each macro input to the `Hir` node of the innermost
+/// item containing the macro call that read it, or to `Krate` if the call
+/// is outside of any item.
+pub fn record_input_reads<'tcx>(tcx: &ty::TyCtxt<'tcx>) {
+    let krate = tcx.map.krate();
+    for (input, span) in tcx.sess.parse_sess.tracked_input_uses() {
+        let item = krate.items
+                        .values()
+                        .filter(|item| item.span.lo <= span.lo && span.hi <= item.span.hi)
+                        .min_by_key(|item| item.span.hi.0 - item.span.lo.0);
+        let reader = match item {
+            Some(item) => DepNode::Hir(tcx.map.local_def_id(item.id)),
+            None => DepNode::Krate,
+        };
+        let _task = tcx.dep_graph.in_task(reader);
+        tcx.dep_graph.read(input_node(&input));
+    }
+}
+
+/// The node of a macro input, identified by a hash of its path or name.
+pub fn input_node(input: &MacroInput) -> DepNode<DefId> {
+    let mut state = SipHasher::new();
+    input.hash(&mut state);
+    DepNode::MacroInput(state.finish())
+}
+
+/// The inputs recorded by the parse session, with hashes of their current
+/// contents.
+pub fn tracked_inputs<'tcx>(tcx: &ty::TyCtxt<'tcx>) -> Vec<SerializedInput> {
+    let parse_sess = &tcx.sess.parse_sess;
+    let files = parse_sess.tracked_files().into_iter().map(|path| {
+        let hash = hash_file(&path);
+        SerializedInput::File(path, hash)
+    });
+    let env_vars = parse_sess.tracked_env_vars().into_iter().map(|(name, value)| {
+        let hash = value.map(|value| hash_str(&value));
+        SerializedInput::EnvVar(name, hash)
+    });
+    files.chain(env_vars).collect()
+}
+
+/// The node of a saved input.
+pub fn serialized_input_node(input: &SerializedInput) -> DepNode<DefId> {
+    match *input {
+        SerializedInput::File(ref path, _) => input_node(&MacroInput::File(path.clone())),
+        SerializedInput::EnvVar(ref name, _) => input_node(&MacroInput::EnvVar(name.clone())),
+    }
+}
+
+/// Whether `input` still has the contents it had when it was saved.
+pub fn input_is_unchanged(input: &SerializedInput) -> bool {
+    match *input {
+        SerializedInput::File(ref path, hash) => hash_file(path) == hash,
+        SerializedInput::EnvVar(ref name, hash) => {
+            env::var(name).ok().map(|value| hash_str(&value)) == hash
+        }
+    }
+}
+
+fn hash_file(path: &str) -> Option<u64> {
+    let mut contents = vec![];
+    match File::open(path).and_then(|mut file| file.read_to_end(&mut contents)) {
+        Ok(_) => {
+            let mut state = SipHasher::new();
+            contents.hash(&mut state);
+            Some(state.finish())
+        }
+        Err(_) => None,
+    }
+}
+
+fn hash_str(value: &str) -> u64 {
+    let mut state = SipHasher::new();
+    value.hash(&mut state);
+    state.finish()
+}
@@ -25,6 +25,7 @@ use std::path::Path;
 use super::data::*;
 use super::directory::*;
 use super::dirty_clean;
+use super::inputs::*;
 use super::util::*;
 
 type DirtyNodes = FnvHashSet<DepNode<DefId>>;
@@ -41,6 +42,8 @@ pub fn load_dep_graph<'tcx>(tcx: &ty::TyCtxt<'tcx>) {
     let _ignore = tcx.dep_graph.in_ignore();
 
     if let Some(dep_graph) = dep_graph_path(tcx) {
+        record_input_reads(tcx);
+
         // FIXME(#32754) lock file?
         load_dep_graph_if_exists(tcx, &dep_graph);
         dirty_clean::check_dirty_clean_annotations(tcx);
@@ -94,6 +97,15 @@ pub fn decode_dep_graph<'tcx>(tcx: &ty::TyCtxt<'tcx>, data: &[u8])
     let mut dirty_nodes =
         initial_dirty_nodes(tcx, &serialized_dep_graph.hashes, &retraced);
 
+    // The files and environment variables read by macros that have changed
+    // are dirty too.
+    for input in &serialized_dep_graph.inputs {
+        if !input_is_unchanged(input) {
+            debug!("decode_dep_graph: {:?} changed", input);
+            dirty_nodes.insert(serialized_input_node(input));
+        }
+    }
+
     debug!("decode_dep_graph: initial dirty_nodes = {:#?}", dirty_nodes);
 
     // Find all DepNodes reachable from that core set. This loop
@@ -18,6 +18,7 @@ use std::fs::{self, File};
 
 use super::data::*;
 use super::directory::*;
+use super::inputs::*;
 use super::util::*;
 
 pub fn save_dep_graph<'tcx>(tcx: &ty::TyCtxt<'tcx>) {
@@ -122,6 +123,7 @@ pub fn encode_dep_graph<'tcx>(tcx: &ty::TyCtxt<'tcx>,
                             })
                             .collect(),
         hashes: hashes,
+        inputs: tracked_inputs(tcx),
     };
 
     debug!("graph = {:#?}", graph);
@@ -0,0 +1,399 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Opt-in lints for code that compiles but is very likely to be wrong.
+//!
+//! All of these are allowed by default and can be enabled together through
+//! the `correctness` lint group.
+
+use rustc::hir::def::Def;
+use rustc::ty::{self, TyCtxt};
+use middle::const_val::ConstVal;
+use rustc_const_eval::eval_const_expr_partial;
+use rustc_const_eval::EvalHint::ExprTypeChecked;
+use lint::{LateContext, LintContext, LintArray};
+use lint::{LintPass, LateLintPass};
+
+use syntax::ast;
+use syntax::attr;
+use syntax::codemap::{self, Span};
+
+use rustc::hir;
+use rustc::hir::intravisit::FnKind;
+use rustc::hir::print as pprust;
+
+declare_lint! {
+    FLOAT_CMP,
+    Allow,
+    "comparison of floating point values with `==` or `!=`"
+}
+
+declare_lint! {
+    CMP_NAN,
+    Allow,
+    "comparison with NaN, which is never equal to anything"
+}
+
+declare_lint! {
+    ITER_COUNT,
+    Allow,
+    "counting the elements of a slice through `.iter().count()`"
+}
+
+declare_lint! {
+    FORGET_IN_LOOP,
+    Allow,
+    "calls to `mem::forget` with a type that has a destructor inside a loop"
+}
+
+declare_lint! {
+    DIVIDE_BY_ZERO,
+    Allow,
+    "integer division or remainder by a constant zero"
+}
+
+declare_lint! {
+    IF_SAME_THEN_ELSE,
+    Allow,
+    "`if` expressions whose `then` and `else` blocks are identical"
+}
+
+declare_lint! {
+    SELF_ASSIGNMENT,
+    Allow,
+    "assignment of a place to itself"
+}
+
+/// Code generated by macros (e.g. `#[derive(PartialEq)]`) is often written
+/// generically enough to trip these lints, so it is never linted.
+fn in_macro(span: Span) -> bool {
+    span.expn_id != codemap::NO_EXPANSION
+}
+
+fn eval_float(tcx: &TyCtxt, e: &hir::Expr) -> Option<f64> {
+    match eval_const_expr_partial(tcx, e, ExprTypeChecked, None) {
+        Ok(ConstVal::Float(f)) => Some(f),
+        _ => None,
+    }
+}
+
+fn snippet(cx: &LateContext, span: Span) -> Option<String> {
+    cx.sess().codemap().span_to_snippet(span).ok()
+}
+
+/// The source of `e` for use as the receiver of a method call or an operand
+/// in a suggestion, parenthesized unless it is a primary expression.
+fn operand_snippet(cx: &LateContext, e: &hir::Expr) -> Option<String> {
+    snippet(cx, e.span).map(|s| match e.node {
+        hir::ExprPath(..) | hir::ExprLit(..) | hir::ExprCall(..) |
+        hir::ExprMethodCall(..) | hir::ExprField(..) | hir::ExprTupField(..) |
+        hir::ExprIndex(..) => s,
+        _ => format!("({})", s),
+    })
+}
+
+#[derive(Copy, Clone)]
+pub struct FloatCmp;
+
+impl LintPass for FloatCmp {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(FLOAT_CMP, CMP_NAN)
+    }
+}
+
+impl LateLintPass for FloatCmp {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        let (op, l, r) = match e.node {
+            hir::ExprBinary(op, ref l, ref r) if !in_macro(e.span) => (op.node, l, r),
+            _ => return,
+        };
+        let is_eq = match op {
+            hir::BiEq => true,
+            hir::BiNe => false,
+            hir::BiLt | hir::BiLe | hir::BiGt | hir::BiGe => {
+                if eval_float(cx.tcx, l).map_or(false, f64::is_nan) ||
+                   eval_float(cx.tcx, r).map_or(false, f64::is_nan) {
+                    cx.span_lint(CMP_NAN, e.span,
+                                 "comparison with NaN is always false");
+                }
+                return;
+            }
+            _ => return,
+        };
+        let fty = match cx.tcx.expr_ty(l).sty {
+            ty::TyFloat(fty) => fty,
+            _ => return,
+        };
+
+        let (lval, rval) = (eval_float(cx.tcx, l), eval_float(cx.tcx, r));
+
+        // `x == NAN` never holds, whatever `x` is
+        let other = if lval.map_or(false, f64::is_nan) {
+            Some(r)
+        } else if rval.map_or(false, f64::is_nan) {
+            Some(l)
+        } else {
+            None
+        };
+        if let Some(other) = other {
+            let msg = if is_eq {
+                "comparison with NaN using `==` is always false"
+            } else {
+                "comparison with NaN using `!=` is always true"
+            };
+            let mut err = cx.struct_span_lint(CMP_NAN, e.span, msg);
+            if let Some(other) = operand_snippet(cx, other) {
+                let not = if is_eq { "" } else { "!" };
+                err.span_suggestion(e.span, "use `is_nan` instead:",
+                                    format!("{}{}.is_nan()", not, other));
+            }
+            err.emit();
+            return;
+        }
+
+        // Comparing against zero or an infinity is exact and commonly intended.
+        let is_exact = |v: Option<f64>| v.map_or(false, |v| v == 0.0 || v.is_infinite());
+        if is_exact(lval) || is_exact(rval) {
+            return;
+        }
+
+        let mut err = cx.struct_span_lint(FLOAT_CMP, e.span,
+                                          "strict comparison of floating point values");
+        if let (Some(l), Some(r)) = (operand_snippet(cx, l), operand_snippet(cx, r)) {
+            let (cmp, epsilon) = (if is_eq { "<" } else { ">=" }, fty.ty_to_string());
+            let krate = if attr::contains_name(&cx.krate.attrs, "no_std") {
+                "core"
+            } else {
+                "std"
+            };
+            err.span_suggestion(e.span, "consider comparing them within some error:",
+                                format!("({} - {}).abs() {} ::{}::{}::EPSILON",
+                                        l, r, cmp, krate, epsilon));
+        }
+        err.emit();
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct IterCount;
+
+impl LintPass for IterCount {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(ITER_COUNT)
+    }
+}
+
+impl LateLintPass for IterCount {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        let iter = match e.node {
+            hir::ExprMethodCall(name, _, ref args)
+                if name.node.as_str() == "count" && args.len() == 1 => &args[0],
+            _ => return,
+        };
+        let slice = match iter.node {
+            hir::ExprMethodCall(name, _, ref args)
+                if (name.node.as_str() == "iter" || name.node.as_str() == "iter_mut") &&
+                   args.len() == 1 => &args[0],
+            _ => return,
+        };
+        if in_macro(e.span) || !is_slice_method(cx, iter.id) {
+            return;
+        }
+
+        let mut err = cx.struct_span_lint(ITER_COUNT, e.span,
+                                          "called `.count()` on the iterator of a slice");
+        if let Some(slice) = operand_snippet(cx, slice) {
+            err.span_suggestion(e.span, "use `len` instead:", format!("{}.len()", slice));
+        }
+        err.emit();
+
+        fn is_slice_method(cx: &LateContext, id: ast::NodeId) -> bool {
+            let method_call = ty::MethodCall::expr(id);
+            let def_id = match cx.tcx.tables.borrow().method_map.get(&method_call) {
+                Some(method) => method.def_id,
+                None => return false,
+            };
+            match cx.tcx.impl_or_trait_item(def_id).container() {
+                ty::ImplContainer(impl_def_id) => {
+                    cx.tcx.lang_items.slice_impl() == Some(impl_def_id)
+                }
+                ty::TraitContainer(_) => false,
+            }
+        }
+    }
+}
+
+pub struct ForgetInLoop {
+    /// How many loops the expression being checked is nested in, within the
+    /// innermost function or closure.
+    loop_depth: usize,
+    /// The loop depths of the enclosing functions and closures.
+    outer_depths: Vec<usize>,
+}
+
+impl ForgetInLoop {
+    pub fn new() -> ForgetInLoop {
+        ForgetInLoop {
+            loop_depth: 0,
+            outer_depths: Vec::new(),
+        }
+    }
+
+    fn is_loop(e: &hir::Expr) -> bool {
+        match e.node {
+            hir::ExprLoop(..) | hir::ExprWhile(..) => true,
+            _ => false,
+        }
+    }
+}
+
+impl LintPass for ForgetInLoop {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(FORGET_IN_LOOP)
+    }
+}
+
+impl LateLintPass for ForgetInLoop {
+    fn check_fn(&mut self, _: &LateContext, _: FnKind, _: &hir::FnDecl,
+                _: &hir::Block, _: Span, _: ast::NodeId) {
+        self.outer_depths.push(self.loop_depth);
+        self.loop_depth = 0;
+    }
+
+    fn check_fn_post(&mut self, _: &LateContext, _: FnKind, _: &hir::FnDecl,
+                     _: &hir::Block, _: Span, _: ast::NodeId) {
+        self.loop_depth = self.outer_depths.pop().unwrap_or(0);
+    }
+
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        if ForgetInLoop::is_loop(e) {
+            self.loop_depth += 1;
+            return;
+        }
+        if self.loop_depth == 0 || in_macro(e.span) {
+            return;
+        }
+        let (callee, args) = match e.node {
+            hir::ExprCall(ref callee, ref args) if args.len() == 1 => (callee, args),
+            _ => return,
+        };
+        match cx.tcx.def_map.borrow().get(&callee.id).map(|d| d.full_def()) {
+            Some(Def::Fn(def_id))
+                if cx.tcx.absolute_item_path_str(def_id) == "core::mem::forget" => {}
+            _ => return,
+        }
+
+        let ty = cx.tcx.expr_ty(&args[0]);
+        if ty.type_contents(cx.tcx).needs_drop(cx.tcx) {
+            cx.span_lint(FORGET_IN_LOOP, e.span,
+                         &format!("value of type `{}` with a destructor is forgotten \
+                                   on every iteration of this loop", ty));
+        }
+    }
+
+    fn check_expr_post(&mut self, _: &LateContext, e: &hir::Expr) {
+        if ForgetInLoop::is_loop(e) {
+            self.loop_depth -= 1;
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct DivideByZero;
+
+impl LintPass for DivideByZero {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(DIVIDE_BY_ZERO)
+    }
+}
+
+impl LateLintPass for DivideByZero {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        let (op, l, r) = match e.node {
+            hir::ExprBinary(op, ref l, ref r) => (op.node, l, r),
+            hir::ExprAssignOp(op, ref l, ref r) => (op.node, l, r),
+            _ => return,
+        };
+        let msg = match op {
+            hir::BiDiv => "this division by zero will panic at runtime",
+            hir::BiRem => "this remainder by zero will panic at runtime",
+            _ => return,
+        };
+        match cx.tcx.expr_ty(l).sty {
+            ty::TyInt(_) | ty::TyUint(_) => {}
+            _ => return,
+        }
+        // Constant operations are already reported by constant evaluation.
+        if eval_const_expr_partial(cx.tcx, l, ExprTypeChecked, None).is_ok() {
+            return;
+        }
+        match eval_const_expr_partial(cx.tcx, r, ExprTypeChecked, None) {
+            Ok(ConstVal::Integral(i)) if i.to_u64_unchecked() == 0 => {
+                cx.span_lint(DIVIDE_BY_ZERO, e.span, msg);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct IfSameThenElse;
+
+impl LintPass for IfSameThenElse {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(IF_SAME_THEN_ELSE)
+    }
+}
+
+impl LateLintPass for IfSameThenElse {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        if let hir::ExprIf(_, ref then, Some(ref els)) = e.node {
+            if let hir::ExprBlock(ref els) = els.node {
+                if !in_macro(e.span) &&
+                   pprust::block_to_string(then) == pprust::block_to_string(els) {
+                    cx.span_lint_note(IF_SAME_THEN_ELSE, els.span,
+                                      "this `if` has identical `then` and `else` blocks",
+                                      then.span, "the `then` block is here");
+                }
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct SelfAssignment;
+
+impl LintPass for SelfAssignment {
+    fn get_lints(&self) -> LintArray {
+        lint_array!(SELF_ASSIGNMENT)
+    }
+}
+
+impl LateLintPass for SelfAssignment {
+    fn check_expr(&mut self, cx: &LateContext, e: &hir::Expr) {
+        if let hir::ExprAssign(ref lhs, ref rhs) = e.node {
+            if !in_macro(e.span) && is_place(lhs) &&
+               pprust::expr_to_string(lhs) == pprust::expr_to_string(rhs) {
+                cx.span_lint(SELF_ASSIGNMENT, e.span,
+                             &format!("self-assignment of `{}` has no effect",
+                                      pprust::expr_to_string(lhs)));
+            }
+        }
+
+        // Only paths and field projections of them, which can't have side effects.
+        fn is_place(e: &hir::Expr) -> bool {
+            match e.node {
+                hir::ExprPath(..) => true,
+                hir::ExprField(ref base, _) | hir::ExprTupField(ref base, _) => is_place(base),
+                _ => false,
+            }
+        }
+    }
+}
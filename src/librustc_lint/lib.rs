@@ -34,7 +34,7 @@
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
-#![feature(slice_patterns)]
+#![cfg_attr(stage0, feature(slice_patterns))]
 #![feature(staged_api)]
 
 #[macro_use]
@@ -57,11 +57,13 @@ use lint::FutureIncompatibleInfo;
 
 mod bad_style;
 mod builtin;
+mod correctness;
 mod types;
 mod unused;
 
 use bad_style::*;
 use builtin::*;
+use correctness::*;
 use types::*;
 use unused::*;
 
@@ -128,12 +130,18 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
                  PluginAsLibrary,
                  DropWithReprExtern,
                  MutableTransmutes,
+                 FloatCmp,
+                 IterCount,
+                 DivideByZero,
+                 IfSameThenElse,
+                 SelfAssignment,
                  );
 
     add_builtin_with_new!(sess,
                           TypeLimits,
                           MissingDoc,
                           MissingDebugImplementations,
+                          ForgetInLoop,
                           );
 
     add_lint_group!(sess, "bad_style",
@@ -144,6 +152,10 @@ pub fn register_builtins(store: &mut lint::LintStore, sess: Option<&Session>) {
                     UNUSED_MUT, UNREACHABLE_CODE, UNUSED_MUST_USE,
                     UNUSED_UNSAFE, PATH_STATEMENTS, UNUSED_ATTRIBUTES);
 
+    add_lint_group!(sess, "correctness",
+                    FLOAT_CMP, CMP_NAN, ITER_COUNT, FORGET_IN_LOOP, DIVIDE_BY_ZERO,
+                    IF_SAME_THEN_ELSE, SELF_ASSIGNMENT);
+
     // Guidelines for creating a future incompatibility lint:
     //
     // - Create a lint defaulting to warn as normal, with ideally the same error
@@ -8,6 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use rustc::hir::def::Def;
 use rustc::hir::pat_util;
 use rustc::ty;
 use rustc::ty::adjustment;
@@ -101,7 +102,7 @@ impl LateLintPass for UnusedMut {
 declare_lint! {
     pub UNUSED_MUST_USE,
     Warn,
-    "unused result of a type flagged as #[must_use]"
+    "unused result of a type or function flagged as #[must_use]"
 }
 
 declare_lint! {
@@ -130,6 +131,28 @@ impl LateLintPass for UnusedResults {
             return;
         }
 
+        // the function or method being called may itself be `#[must_use]`
+        let callee = match expr.node {
+            hir::ExprCall(ref callee, _) => {
+                match cx.tcx.def_map.borrow().get(&callee.id).map(|d| d.full_def()) {
+                    Some(Def::Fn(def_id)) | Some(Def::Method(def_id)) => Some(def_id),
+                    _ => None,
+                }
+            }
+            hir::ExprMethodCall(..) => {
+                let method_call = ty::MethodCall::expr(expr.id);
+                cx.tcx.tables.borrow().method_map.get(&method_call).map(|m| m.def_id)
+            }
+            _ => None,
+        };
+        if let Some(def_id) = callee {
+            let attrs = cx.tcx.get_attrs(def_id);
+            let what = format!("return value of `{}`", cx.tcx.item_path_str(def_id));
+            if check_must_use(cx, &attrs[..], s.span, &what) {
+                return;
+            }
+        }
+
         let t = cx.tcx.expr_ty(&expr);
         let warned = match t.sty {
             ty::TyTuple(ref tys) if tys.is_empty() => return,
@@ -137,7 +160,7 @@ impl LateLintPass for UnusedResults {
             ty::TyStruct(def, _) |
             ty::TyEnum(def, _) => {
                 let attrs = cx.tcx.get_attrs(def.did);
-                check_must_use(cx, &attrs[..], s.span)
+                check_must_use(cx, &attrs[..], s.span, "result")
             }
             _ => false,
         };
@@ -145,10 +168,11 @@ impl LateLintPass for UnusedResults {
             cx.span_lint(UNUSED_RESULTS, s.span, "unused result");
         }
 
-        fn check_must_use(cx: &LateContext, attrs: &[ast::Attribute], sp: Span) -> bool {
+        fn check_must_use(cx: &LateContext, attrs: &[ast::Attribute], sp: Span,
+                          what: &str) -> bool {
             for attr in attrs {
                 if attr.check_name("must_use") {
-                    let mut msg = "unused result which must be used".to_string();
+                    let mut msg = format!("unused {} which must be used", what);
                     // check for #[must_use="..."]
                     match attr.value_str() {
                         None => {}
@@ -223,6 +223,7 @@ pub const tag_macro_def: usize = 0x9e;
 pub const tag_macro_def_body: usize = 0x9f;
 pub const tag_macro_def_span_lo: usize = 0xa8;
 pub const tag_macro_def_span_hi: usize = 0xa9;
+pub const tag_macro_def_scope: usize = 0xaa;
 
 pub const tag_paren_sugar: usize = 0xa0;
 
@@ -250,3 +251,5 @@ pub fn rustc_version() -> String {
         option_env!("CFG_VERSION").unwrap_or("unknown version")
     )
 }
+
+pub const tag_derive_registrar_fn: usize = 0x110; // top-level only
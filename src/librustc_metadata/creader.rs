@@ -121,6 +121,16 @@ fn register_native_lib(sess: &Session,
     cstore.add_used_library(name, kind);
 }
 
+/// The macros exported by a crate loaded with `#[macro_use] extern crate`.
+pub struct LoadedMacros {
+    /// The name of the crate the macros were loaded from.
+    pub crate_name: String,
+    pub macro_rules: Vec<ast::MacroDef>,
+    /// For a `proc-macro` crate, the path of its dylib and the symbol name
+    /// of the registrar function for its custom derives.
+    pub derive_registrar: Option<(PathBuf, String)>,
+}
+
 // Extra info about a crate loaded for plugins or exported macros.
 struct ExtensionCrate {
     metadata: PMDSource,
@@ -502,10 +512,17 @@ impl<'a> CrateReader<'a> {
             None => { load_ctxt.report_load_errs(); },
         };
 
+        // `proc-macro` crates are only loaded into the compiler; remember
+        // them so that the `extern crate` item doesn't link them either.
+        let is_proc_macro =
+            decoder::get_derive_registrar_fn(library.metadata.as_slice()).is_some();
+        if is_proc_macro {
+            self.cstore.add_proc_macro_crate(&info.name);
+        }
+
         let dylib = library.dylib.clone();
-        let register = should_link && self.existing_match(&info.name,
-                                                          None,
-                                                          PathKind::Crate).is_none();
+        let register = should_link && !is_proc_macro &&
+                       self.existing_match(&info.name, None, PathKind::Crate).is_none();
         let metadata = if register {
             // Register crate now to avoid double-reading metadata
             let (_, cmd, _) = self.register_crate(&None, &info.ident,
@@ -524,8 +541,15 @@ impl<'a> CrateReader<'a> {
         }
     }
 
+    /// Looks up the crate of `item` without reading its macros, which is
+    /// enough for a `proc-macro` crate to be remembered as one.
+    pub fn note_proc_macro_crate(&mut self, item: &ast::Item) {
+        let ci = self.extract_crate_info(item).unwrap();
+        self.read_extension_crate(item.span, &ci);
+    }
+
     /// Read exported macros.
-    pub fn read_exported_macros(&mut self, item: &ast::Item) -> Vec<ast::MacroDef> {
+    pub fn read_exported_macros(&mut self, item: &ast::Item) -> LoadedMacros {
         let ci = self.extract_crate_info(item).unwrap();
         let ekrate = self.read_extension_crate(item.span, &ci);
 
@@ -533,7 +557,7 @@ impl<'a> CrateReader<'a> {
         let mut macros = vec![];
         decoder::each_exported_macro(ekrate.metadata.as_slice(),
                                      &self.cstore.intr,
-            |name, attrs, span, body| {
+            |name, attrs, span, body, scope| {
                 // NB: Don't use parse::parse_tts_from_source_str because it parses with
                 // quote_depth > 0.
                 let mut p = parse::new_parser_from_source_str(&self.sess.parse_sess,
@@ -568,13 +592,36 @@ impl<'a> CrateReader<'a> {
                     allow_internal_unstable: false,
 
                     body: body,
+                    scope: scope,
                 });
                 self.sess.imported_macro_spans.borrow_mut()
                     .insert(local_span, (name.as_str().to_string(), span));
+                self.cstore.add_extension_macro(local_span, &ci.name);
                 true
             }
         );
-        macros
+
+        let derive_registrar =
+            decoder::get_derive_registrar_fn(ekrate.metadata.as_slice())
+            .map(|id| decoder::get_symbol_from_buf(ekrate.metadata.as_slice(), id));
+        if derive_registrar.is_some() && ekrate.target_only {
+            // Need to abort before syntax expansion.
+            let message = format!("proc-macro crate `{}` is not available for triple `{}` \
+                                   (only found {})",
+                                  ci.ident,
+                                  config::host_triple(),
+                                  self.sess.opts.target_triple);
+            span_fatal!(self.sess, item.span, E0526, "{}", &message[..]);
+        }
+
+        LoadedMacros {
+            crate_name: ci.name,
+            macro_rules: macros,
+            derive_registrar: match (ekrate.dylib, derive_registrar) {
+                (Some(dylib), Some(symbol)) => Some((dylib, symbol)),
+                _ => None,
+            },
+        }
     }
 
     /// Look for a plugin registrar. Returns library path and symbol name.
@@ -602,7 +649,10 @@ impl<'a> CrateReader<'a> {
             .map(|id| decoder::get_symbol_from_buf(ekrate.metadata.as_slice(), id));
 
         match (ekrate.dylib.as_ref(), registrar) {
-            (Some(dylib), Some(reg)) => Some((dylib.to_path_buf(), reg)),
+            (Some(dylib), Some(reg)) => {
+                self.cstore.add_used_extension_crate(name);
+                Some((dylib.to_path_buf(), reg))
+            }
             (None, Some(_)) => {
                 span_err!(self.sess, span, E0457,
                           "plugin `{}` only found in rlib format, but must be available \
@@ -662,6 +712,7 @@ impl<'a> CrateReader<'a> {
             match *ct {
                 config::CrateTypeExecutable => need_exe_alloc = true,
                 config::CrateTypeDylib |
+                config::CrateTypeProcMacro |
                 config::CrateTypeStaticlib => need_lib_alloc = true,
                 config::CrateTypeRlib => {}
             }
@@ -801,6 +852,10 @@ impl<'a> LocalCrateReader<'a> {
                 }
 
                 match self.creader.extract_crate_info(i) {
+                    Some(ref info) if self.cstore.is_proc_macro_crate(&info.name) => {
+                        // already loaded by `macro_import`; there is nothing
+                        // to link or resolve against
+                    }
                     Some(info) => {
                         let (cnum, _, _) = self.creader.resolve_crate(&None,
                                                                       &info.ident,
@@ -500,6 +500,11 @@ impl<'tcx> CrateStore<'tcx> for cstore::CStore {
         self.do_extern_mod_stmt_cnum(emod_id)
     }
 
+    fn is_used_extension_crate(&self, name: &str) -> bool
+    {
+        self.do_is_used_extension_crate(name)
+    }
+
     fn encode_metadata(&self,
                        tcx: &TyCtxt<'tcx>,
                        reexports: &def::ExportMap,
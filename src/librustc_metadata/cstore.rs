@@ -23,7 +23,7 @@ use loader;
 use rustc::hir::def_id::DefId;
 use rustc::hir::svh::Svh;
 use rustc::middle::cstore::{ExternCrate};
-use rustc::util::nodemap::{FnvHashMap, NodeMap, NodeSet, DefIdMap};
+use rustc::util::nodemap::{FnvHashMap, FnvHashSet, NodeMap, NodeSet, DefIdMap};
 
 use std::cell::{RefCell, Ref, Cell};
 use std::rc::Rc;
@@ -92,6 +92,17 @@ pub struct CStore {
     used_libraries: RefCell<Vec<(String, NativeLibraryKind)>>,
     used_link_args: RefCell<Vec<String>>,
     statically_included_foreign_items: RefCell<NodeSet>,
+    /// Names of the `proc-macro` crates loaded by `extern crate`. These are
+    /// only ever loaded into the compiler, never linked.
+    proc_macro_crates: RefCell<FnvHashSet<String>>,
+    /// The crates that imported macros come from, by the span of the macro
+    /// definition, and that custom derives come from, by the name of their
+    /// `derive_*` extension.
+    extension_macros: RefCell<FnvHashMap<codemap::Span, String>>,
+    custom_derives: RefCell<FnvHashMap<ast::Name, String>>,
+    /// Names of the crates whose macros were expanded or whose plugin
+    /// registrars were loaded. Name resolution never sees these uses.
+    used_extension_crates: RefCell<FnvHashSet<String>>,
     pub intr: Rc<IdentInterner>,
     pub visible_parent_map: RefCell<DefIdMap<DefId>>,
 }
@@ -106,6 +117,10 @@ impl CStore {
             used_link_args: RefCell::new(Vec::new()),
             intr: intr,
             statically_included_foreign_items: RefCell::new(NodeSet()),
+            proc_macro_crates: RefCell::new(FnvHashSet()),
+            extension_macros: RefCell::new(FnvHashMap()),
+            custom_derives: RefCell::new(FnvHashMap()),
+            used_extension_crates: RefCell::new(FnvHashSet()),
             visible_parent_map: RefCell::new(FnvHashMap()),
         }
     }
@@ -166,6 +181,10 @@ impl CStore {
         self.used_libraries.borrow_mut().clear();
         self.used_link_args.borrow_mut().clear();
         self.statically_included_foreign_items.borrow_mut().clear();
+        self.proc_macro_crates.borrow_mut().clear();
+        self.extension_macros.borrow_mut().clear();
+        self.custom_derives.borrow_mut().clear();
+        self.used_extension_crates.borrow_mut().clear();
     }
 
     // This method is used when generating the command line to pass through to
@@ -240,6 +259,48 @@ impl CStore {
         self.statically_included_foreign_items.borrow_mut().insert(id);
     }
 
+    pub fn add_proc_macro_crate(&self, name: &str) {
+        self.proc_macro_crates.borrow_mut().insert(name.to_string());
+    }
+
+    pub fn is_proc_macro_crate(&self, name: &str) -> bool {
+        self.proc_macro_crates.borrow().contains(name)
+    }
+
+    pub fn add_extension_macro(&self, span: codemap::Span, crate_name: &str) {
+        self.extension_macros.borrow_mut().insert(span, crate_name.to_string());
+    }
+
+    pub fn add_custom_derive(&self, name: ast::Name, crate_name: &str) {
+        self.custom_derives.borrow_mut().insert(name, crate_name.to_string());
+    }
+
+    pub fn add_used_extension_crate(&self, name: &str) {
+        self.used_extension_crates.borrow_mut().insert(name.to_string());
+    }
+
+    /// Marks the crates that any of the expanded macros were loaded from as
+    /// used. This must run once expansion is complete.
+    pub fn mark_expanded_macros(&self, codemap: &codemap::CodeMap) {
+        let extension_macros = self.extension_macros.borrow();
+        let custom_derives = self.custom_derives.borrow();
+        let mut used = self.used_extension_crates.borrow_mut();
+        codemap.each_expansion(|expn_info| {
+            let callee = &expn_info.callee;
+            let crate_name = match callee.format {
+                codemap::MacroAttribute(name) => custom_derives.get(&name),
+                codemap::MacroBang(_) => callee.span.and_then(|sp| extension_macros.get(&sp)),
+            };
+            if let Some(crate_name) = crate_name {
+                used.insert(crate_name.clone());
+            }
+        });
+    }
+
+    pub fn do_is_used_extension_crate(&self, name: &str) -> bool {
+        self.used_extension_crates.borrow().contains(name)
+    }
+
     pub fn do_is_statically_included_foreign_item(&self, id: ast::NodeId) -> bool {
         self.statically_included_foreign_items.borrow().contains(&id)
     }
@@ -89,4 +89,5 @@ register_diagnostics! {
     E0470, // reexported macro not found
     E0519, // local crate and dependency have same (crate-name, disambiguator)
     E0523, // two dependencies have same (crate-name, disambiguator) but different SVH
+    E0526, // proc-macro crate `..` is not available for triple `..`
 }
@@ -1627,6 +1627,16 @@ fn encode_plugin_registrar_fn(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     }
 }
 
+fn encode_derive_registrar_fn(ecx: &EncodeContext, rbml_w: &mut Encoder) {
+    match ecx.tcx.sess.derive_registrar_fn.get() {
+        Some(id) => {
+            let def_id = ecx.tcx.map.local_def_id(id);
+            rbml_w.wr_tagged_u32(tag_derive_registrar_fn, def_id.index.as_u32());
+        }
+        None => {}
+    }
+}
+
 fn encode_codemap(ecx: &EncodeContext, rbml_w: &mut Encoder) {
     rbml_w.start_tag(tag_codemap);
     let codemap = ecx.tcx.sess.codemap();
@@ -1668,6 +1678,12 @@ fn encode_macro_defs(rbml_w: &mut Encoder,
         rbml_w.wr_tagged_str(tag_macro_def_body,
                              &::syntax::print::pprust::tts_to_string(&def.body));
 
+        if let Some(ref scope) = def.scope {
+            rbml_w.start_tag(tag_macro_def_scope);
+            scope.encode(rbml_w).unwrap();
+            rbml_w.end_tag();
+        }
+
         rbml_w.end_tag();
     }
     rbml_w.end_tag();
@@ -1938,6 +1954,7 @@ fn encode_metadata_inner(rbml_w: &mut Encoder,
     // Encode the plugin registrar function
     i = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap();
     encode_plugin_registrar_fn(&ecx, rbml_w);
+    encode_derive_registrar_fn(&ecx, rbml_w);
     stats.plugin_registrar_fn_bytes = rbml_w.writer.seek(SeekFrom::Current(0)).unwrap() - i;
 
     // Encode codemap
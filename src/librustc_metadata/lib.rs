@@ -19,6 +19,8 @@
 
 #![feature(box_patterns)]
 #![feature(enumset)]
+#![feature(proc_macro_internals)]
+#![feature(proc_macro_lib)]
 #![feature(quote)]
 #![feature(rustc_diagnostic_macros)]
 #![feature(rustc_private)]
@@ -30,7 +32,9 @@
 #[macro_use] #[no_link] extern crate rustc_bitflags;
 
 extern crate flate;
+extern crate proc_macro;
 extern crate rbml;
+extern crate syntax_ext;
 extern crate serialize as rustc_serialize; // used by deriving
 
 #[macro_use]
@@ -10,34 +10,50 @@
 
 //! Used by `rustc` when loading a crate with exported macros.
 
-use creader::CrateReader;
+use creader::{CrateReader, LoadedMacros};
 use cstore::CStore;
 
 use rustc::session::Session;
 
 use std::collections::{HashSet, HashMap};
+use std::env;
+use std::mem;
+use std::path::PathBuf;
+use proc_macro::TokenStream;
+use proc_macro::__internal::{DeriveRegistrarFun, Registry};
 use syntax::codemap::Span;
-use syntax::parse::token;
+use syntax::ext::base::{CustomDerive, NamedSyntaxExtension};
+use syntax::parse::token::{self, InternedString};
 use syntax::ast;
 use syntax::attr;
 use syntax::visit;
 use syntax::visit::Visitor;
 use syntax::attr::AttrMetaMethods;
+use syntax_ext::deriving::custom;
 
 struct MacroLoader<'a> {
     sess: &'a Session,
+    cstore: &'a CStore,
     span_whitelist: HashSet<Span>,
     reader: CrateReader<'a>,
     macros: Vec<ast::MacroDef>,
+    custom_derives: Vec<NamedSyntaxExtension>,
+    // The path of the module being visited, and whether it is a block.
+    module: Vec<ast::Name>,
+    in_block: bool,
 }
 
 impl<'a> MacroLoader<'a> {
     fn new(sess: &'a Session, cstore: &'a CStore, crate_name: &str) -> MacroLoader<'a> {
         MacroLoader {
             sess: sess,
+            cstore: cstore,
             span_whitelist: HashSet::new(),
             reader: CrateReader::new(sess, cstore, crate_name),
             macros: vec![],
+            custom_derives: vec![],
+            module: vec![],
+            in_block: false,
         }
     }
 }
@@ -46,12 +62,15 @@ pub fn call_bad_macro_reexport(a: &Session, b: Span) {
     span_err!(a, b, E0467, "bad macro reexport");
 }
 
-/// Read exported macros.
+/// Read exported macros, as well as the custom derives of any `proc-macro`
+/// crates loaded with `#[macro_use]`. The `macro` items exported by a crate
+/// are read whether or not it is loaded with `#[macro_use]`, since they are
+/// named by path, and their scope is made relative to this crate.
 pub fn read_macro_defs(sess: &Session,
                        cstore: &CStore,
                        krate: &ast::Crate,
                        crate_name: &str)
-                       -> Vec<ast::MacroDef>
+                       -> (Vec<ast::MacroDef>, Vec<NamedSyntaxExtension>)
 {
     let mut loader = MacroLoader::new(sess, cstore, crate_name);
 
@@ -66,7 +85,7 @@ pub fn read_macro_defs(sess: &Session,
 
     visit::walk_crate(&mut loader, krate);
 
-    loader.macros
+    (loader.macros, loader.custom_derives)
 }
 
 pub type MacroSelection = HashMap<token::InternedString, Span>;
@@ -77,6 +96,12 @@ impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
         // We're only interested in `extern crate`.
         match item.node {
             ast::ItemKind::ExternCrate(_) => {}
+            ast::ItemKind::Mod(..) => {
+                self.module.push(item.ident.name);
+                visit::walk_item(self, item);
+                self.module.pop();
+                return;
+            }
             _ => {
                 visit::walk_item(self, item);
                 return;
@@ -133,6 +158,12 @@ impl<'a, 'v> Visitor<'v> for MacroLoader<'a> {
         self.load_macros(item, import, reexport)
     }
 
+    fn visit_block(&mut self, block: &ast::Block) {
+        let in_block = mem::replace(&mut self.in_block, true);
+        visit::walk_block(self, block);
+        self.in_block = in_block;
+    }
+
     fn visit_mac(&mut self, _: &ast::Mac) {
         // bummer... can't see macro imports inside macros.
         // do nothing.
@@ -144,22 +175,54 @@ impl<'a> MacroLoader<'a> {
                        vi: &ast::Item,
                        import: Option<MacroSelection>,
                        reexport: MacroSelection) {
-        if let Some(sel) = import.as_ref() {
-            if sel.is_empty() && reexport.is_empty() {
-                return;
-            }
-        }
+        let load_macro_rules = match import.as_ref() {
+            Some(sel) => !sel.is_empty() || !reexport.is_empty(),
+            None => true,
+        };
 
-        if !self.span_whitelist.contains(&vi.span) {
+        if load_macro_rules && !self.span_whitelist.contains(&vi.span) {
             span_err!(self.sess, vi.span, E0468,
                       "an `extern crate` loading macros must be at the crate root");
             return;
         }
+        // The `macro`s of a crate loaded in a block can't be named by path, so
+        // without `#[macro_use]` there is nothing to read. The crate is still
+        // looked up, so that a `proc-macro` crate is never linked.
+        if !load_macro_rules && self.in_block {
+            self.reader.note_proc_macro_crate(vi);
+            return;
+        }
 
-        let macros = self.reader.read_exported_macros(vi);
+        let LoadedMacros { crate_name, macro_rules, derive_registrar } =
+            self.reader.read_exported_macros(vi);
         let mut seen = HashSet::new();
 
-        for mut def in macros {
+        for mut def in macro_rules {
+            if let Some(scope) = def.scope.take() {
+                // Paths in the crate of the macro become paths through the
+                // `extern crate` item.
+                let mut prefix = self.module.clone();
+                prefix.push(vi.ident.name);
+                let relative = |path: Vec<ast::Name>| {
+                    let mut result = prefix.clone();
+                    result.extend(path);
+                    result
+                };
+                def.scope = Some(ast::MacroScope {
+                    module: relative(scope.module),
+                    items: scope.items,
+                    imports: scope.imports.into_iter()
+                                          .map(|(name, target)| (name, relative(target)))
+                                          .collect(),
+                });
+                debug!("load_macros: loaded: {:?}", def);
+                self.macros.push(def);
+                continue;
+            }
+            if !load_macro_rules {
+                continue;
+            }
+
             let name = def.ident.name.as_str();
 
             def.use_locally = match import.as_ref() {
@@ -174,6 +237,18 @@ impl<'a> MacroLoader<'a> {
             seen.insert(name);
         }
 
+        if !load_macro_rules {
+            return;
+        }
+
+        if let Some((path, symbol)) = derive_registrar {
+            let loaded = self.load_derive_macros(vi.span, &crate_name, path, symbol,
+                                                 import.as_ref());
+            for name in loaded {
+                seen.insert(name);
+            }
+        }
+
         if let Some(sel) = import.as_ref() {
             for (name, span) in sel {
                 if !seen.contains(&name) {
@@ -190,4 +265,75 @@ impl<'a> MacroLoader<'a> {
             }
         }
     }
+
+    /// Dynamically links the registrar of a `proc-macro` crate into the
+    /// compiler and registers the selected custom derives it provides as
+    /// `derive_*` syntax extensions. Returns the names of the derives that
+    /// were loaded.
+    fn load_derive_macros(&mut self,
+                          span: Span,
+                          crate_name: &str,
+                          path: PathBuf,
+                          symbol: String,
+                          import: Option<&MacroSelection>)
+                          -> Vec<InternedString> {
+        use rustc_back::dynamic_lib::DynamicLibrary;
+
+        // Make sure the path contains a / or the linker will search for it.
+        let path = match env::current_dir() {
+            Ok(cwd) => cwd.join(&path),
+            Err(err) => {
+                let msg = format!("could not determine the current directory: {}", err);
+                self.sess.span_fatal(span, &msg)
+            }
+        };
+        let lib = match DynamicLibrary::open(Some(&path)) {
+            Ok(lib) => lib,
+            Err(err) => self.sess.span_fatal(span, &err),
+        };
+
+        let registrar = unsafe {
+            match lib.symbol(&symbol) {
+                Ok(registrar) => mem::transmute::<*mut u8, DeriveRegistrarFun>(registrar),
+                Err(err) => self.sess.span_fatal(span, &err),
+            }
+        };
+
+        struct DeriveRegistry {
+            derives: Vec<(String, fn(TokenStream) -> TokenStream)>,
+        }
+
+        impl Registry for DeriveRegistry {
+            fn register_custom_derive(&mut self,
+                                      trait_name: &str,
+                                      expand: fn(TokenStream) -> TokenStream) {
+                self.derives.push((trait_name.to_string(), expand));
+            }
+        }
+
+        let mut registry = DeriveRegistry { derives: Vec::new() };
+        registrar(&mut registry);
+
+        let mut loaded = Vec::new();
+        for (trait_name, expand) in registry.derives {
+            let trait_name = token::intern_and_get_ident(&trait_name);
+            if let Some(sel) = import {
+                if !sel.contains_key(&trait_name) {
+                    continue
+                }
+            }
+
+            let name = token::intern(&format!("derive_{}", trait_name));
+            let ext = CustomDerive(Box::new(custom::CustomDerive::new(expand)));
+            self.cstore.add_custom_derive(name, crate_name);
+            self.custom_derives.push((name, ext));
+            loaded.push(trait_name);
+        }
+
+        // Intentionally leak the dynamic library, like plugins do: the
+        // expansion functions we just registered live in it.
+        mem::forget(lib);
+
+        loaded
+    }
 }
@@ -26,6 +26,7 @@ use build::{BlockAnd, BlockAndExtension, Builder};
 use build::matches::{Binding, MatchPair, Candidate};
 use hair::*;
 use rustc::mir::repr::*;
+use rustc::ty;
 
 use std::mem;
 
@@ -107,9 +108,14 @@ impl<'a,'tcx> Builder<'a,'tcx> {
 
             PatternKind::Array { ref prefix, ref slice, ref suffix } |
             PatternKind::Slice { ref prefix, ref slice, ref suffix } => {
+                let array_len = match match_pair.pattern.ty.sty {
+                    ty::TyArray(_, len) => Some(len),
+                    _ => None
+                };
                 unpack!(block = self.prefix_suffix_slice(&mut candidate.match_pairs,
                                                          block,
                                                          match_pair.lvalue.clone(),
+                                                         array_len,
                                                          prefix,
                                                          slice.as_ref(),
                                                          suffix));
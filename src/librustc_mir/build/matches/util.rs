@@ -46,10 +46,16 @@ impl<'a,'tcx> Builder<'a,'tcx> {
     /// ```
     ///
     /// and creates a match pair `tmp0 @ s`
+    ///
+    /// When `lv` is a fixed-size array, `array_len` is its length and the
+    /// suffix is indexed from the start instead (`lv[3 of 4] @ z` for an
+    /// array of length 4), so that every element has a single lvalue no
+    /// matter which pattern it is reached through.
     pub fn prefix_suffix_slice<'pat>(&mut self,
                                      match_pairs: &mut Vec<MatchPair<'pat, 'tcx>>,
                                      block: BasicBlock,
                                      lvalue: Lvalue<'tcx>,
+                                     array_len: Option<usize>,
                                      prefix: &'pat [Pattern<'tcx>],
                                      opt_slice: Option<&'pat Pattern<'tcx>>,
                                      suffix: &'pat [Pattern<'tcx>])
@@ -70,7 +76,7 @@ impl<'a,'tcx> Builder<'a,'tcx> {
             match_pairs.push(MatchPair::new(temp, slice));
         }
 
-        self.prefix_suffix(match_pairs, lvalue, prefix, suffix);
+        self.prefix_suffix(match_pairs, lvalue, array_len, prefix, suffix);
 
         block.unit()
     }
@@ -79,9 +85,10 @@ impl<'a,'tcx> Builder<'a,'tcx> {
     fn prefix_suffix<'pat>(&mut self,
                            match_pairs: &mut Vec<MatchPair<'pat, 'tcx>>,
                            lvalue: Lvalue<'tcx>,
+                           array_len: Option<usize>,
                            prefix: &'pat [Pattern<'tcx>],
                            suffix: &'pat [Pattern<'tcx>]) {
-        let min_length = prefix.len() + suffix.len();
+        let min_length = array_len.unwrap_or(prefix.len() + suffix.len());
         assert!(min_length < u32::MAX as usize);
         let min_length = min_length as u32;
 
@@ -104,10 +111,17 @@ impl<'a,'tcx> Builder<'a,'tcx> {
                   .rev()
                   .enumerate()
                   .map(|(idx, subpattern)| {
-                      let elem = ProjectionElem::ConstantIndex {
-                          offset: (idx+1) as u32,
-                          min_length: min_length,
-                          from_end: true,
+                      let elem = match array_len {
+                          Some(_) => ProjectionElem::ConstantIndex {
+                              offset: min_length - (idx+1) as u32,
+                              min_length: min_length,
+                              from_end: false,
+                          },
+                          None => ProjectionElem::ConstantIndex {
+                              offset: (idx+1) as u32,
+                              min_length: min_length,
+                              from_end: true,
+                          },
                       };
                       let lvalue = lvalue.clone().elem(elem);
                       MatchPair::new(lvalue, subpattern)
@@ -0,0 +1,234 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A backwards liveness analysis over the locals (args, vars and
+//! temps) of a MIR body. A local is live at a point if its current
+//! value may be read by some later statement or terminator before it
+//! is overwritten. Drops count as uses, since a destructor may access
+//! any data reachable from the dropped value.
+
+use rustc::mir::repr::*;
+use rustc::mir::visit::{LvalueContext, Visitor};
+use rustc::ty::Ty;
+use rustc_data_structures::bitvec::BitVector;
+
+use build::Location;
+
+/// A dense index over all locals of a MIR body: first the args, then
+/// the vars, then the temps.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocalIndex(usize);
+
+impl LocalIndex {
+    pub fn new(index: usize) -> LocalIndex {
+        LocalIndex(index)
+    }
+
+    pub fn index(self) -> usize {
+        self.0
+    }
+}
+
+pub fn num_locals(mir: &Mir) -> usize {
+    mir.arg_decls.len() + mir.var_decls.len() + mir.temp_decls.len()
+}
+
+pub fn local_index(mir: &Mir, lvalue: &Lvalue) -> Option<LocalIndex> {
+    let args = mir.arg_decls.len();
+    let vars = mir.var_decls.len();
+    match *lvalue {
+        Lvalue::Arg(index) => Some(LocalIndex(index as usize)),
+        Lvalue::Var(index) => Some(LocalIndex(args + index as usize)),
+        Lvalue::Temp(index) => Some(LocalIndex(args + vars + index as usize)),
+        Lvalue::Static(_) | Lvalue::ReturnPointer | Lvalue::Projection(_) => None,
+    }
+}
+
+pub fn local_ty<'tcx>(mir: &Mir<'tcx>, local: LocalIndex) -> Ty<'tcx> {
+    let args = mir.arg_decls.len();
+    let vars = mir.var_decls.len();
+    let index = local.index();
+    if index < args {
+        mir.arg_decls[index].ty
+    } else if index < args + vars {
+        mir.var_decls[index - args].ty
+    } else {
+        mir.temp_decls[index - args - vars].ty
+    }
+}
+
+/// The result of the liveness computation: the set of live locals on
+/// entry to every basic block.
+pub struct LivenessResult {
+    num_locals: usize,
+    ins: Vec<BitVector>,
+}
+
+impl LivenessResult {
+    /// Invokes `callback` for every point in `block`, together with
+    /// the set of locals that are live on entry to that point. Points
+    /// are visited from the terminator upwards.
+    pub fn simulate_block<'tcx, F>(&self, mir: &Mir<'tcx>, block: BasicBlock, mut callback: F)
+        where F: FnMut(Location, &BitVector)
+    {
+        let data = mir.basic_block_data(block);
+        let mut live = self.live_out(mir, block);
+
+        let terminator_location = Location {
+            block: block,
+            statement_index: data.statements.len(),
+        };
+        let defs_uses = DefsUses::for_terminator(mir, block, data.terminator());
+        defs_uses.apply(&mut live, self.num_locals);
+        callback(terminator_location, &live);
+
+        for (statement_index, statement) in data.statements.iter().enumerate().rev() {
+            let location = Location { block: block, statement_index: statement_index };
+            let defs_uses = DefsUses::for_statement(mir, block, statement);
+            defs_uses.apply(&mut live, self.num_locals);
+            callback(location, &live);
+        }
+    }
+
+    fn live_out(&self, mir: &Mir, block: BasicBlock) -> BitVector {
+        let mut live = BitVector::new(self.num_locals);
+        for &successor in mir.basic_block_data(block).terminator().successors().iter() {
+            live.insert_all(&self.ins[successor.index()]);
+        }
+        live
+    }
+}
+
+/// Computes the set of live locals on entry to each basic block, by
+/// iterating the usual backwards dataflow equations to a fixed point.
+pub fn liveness_of_locals(mir: &Mir) -> LivenessResult {
+    let num_locals = num_locals(mir);
+    let num_blocks = mir.basic_blocks.len();
+
+    let block_defs_uses: Vec<DefsUses> =
+        mir.all_basic_blocks()
+           .into_iter()
+           .map(|block| DefsUses::for_block(mir, block))
+           .collect();
+
+    let mut result = LivenessResult {
+        num_locals: num_locals,
+        ins: (0..num_blocks).map(|_| BitVector::new(num_locals)).collect(),
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in mir.all_basic_blocks().into_iter().rev() {
+            let mut live = result.live_out(mir, block);
+            block_defs_uses[block.index()].apply(&mut live, num_locals);
+            changed |= result.ins[block.index()].insert_all(&live);
+        }
+    }
+
+    result
+}
+
+/// The locals defined (overwritten entirely) and used by a statement,
+/// a terminator or a whole basic block.
+struct DefsUses {
+    defs: Vec<LocalIndex>,
+    uses: Vec<LocalIndex>,
+}
+
+impl DefsUses {
+    fn new() -> DefsUses {
+        DefsUses { defs: vec![], uses: vec![] }
+    }
+
+    fn for_statement<'tcx>(mir: &Mir<'tcx>,
+                           block: BasicBlock,
+                           statement: &Statement<'tcx>)
+                           -> DefsUses {
+        let mut visitor = DefsUsesVisitor { mir: mir, defs_uses: DefsUses::new() };
+        visitor.visit_statement(block, statement);
+        visitor.defs_uses
+    }
+
+    fn for_terminator<'tcx>(mir: &Mir<'tcx>,
+                            block: BasicBlock,
+                            terminator: &Terminator<'tcx>)
+                            -> DefsUses {
+        let mut visitor = DefsUsesVisitor { mir: mir, defs_uses: DefsUses::new() };
+        visitor.visit_terminator(block, terminator);
+        visitor.defs_uses
+    }
+
+    /// Summarizes a whole block as a single transfer function, walking
+    /// it backwards so that a def followed by a use in the same block
+    /// does not make the local live on entry.
+    fn for_block(mir: &Mir, block: BasicBlock) -> DefsUses {
+        let data = mir.basic_block_data(block);
+        let mut summary = DefsUses::new();
+
+        let mut parts = vec![DefsUses::for_terminator(mir, block, data.terminator())];
+        parts.extend(data.statements.iter().rev().map(|s| DefsUses::for_statement(mir, block, s)));
+
+        for part in parts {
+            for &def in &part.defs {
+                summary.uses.retain(|&use_| use_ != def);
+                if !summary.defs.contains(&def) {
+                    summary.defs.push(def);
+                }
+            }
+            for &use_ in &part.uses {
+                if !summary.uses.contains(&use_) {
+                    summary.uses.push(use_);
+                }
+            }
+        }
+
+        summary
+    }
+
+    fn apply(&self, live: &mut BitVector, num_locals: usize) {
+        let mut result = BitVector::new(num_locals);
+        for index in live.iter() {
+            if !self.defs.iter().any(|def| def.index() == index) {
+                result.insert(index);
+            }
+        }
+        for use_ in &self.uses {
+            result.insert(use_.index());
+        }
+        *live = result;
+    }
+}
+
+struct DefsUsesVisitor<'a, 'tcx: 'a> {
+    mir: &'a Mir<'tcx>,
+    defs_uses: DefsUses,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for DefsUsesVisitor<'a, 'tcx> {
+    fn visit_lvalue(&mut self, lvalue: &Lvalue<'tcx>, context: LvalueContext) {
+        if let Some(local) = local_index(self.mir, lvalue) {
+            match context {
+                LvalueContext::Store | LvalueContext::Call => {
+                    self.defs_uses.defs.push(local);
+                }
+                LvalueContext::Drop |
+                LvalueContext::Inspect |
+                LvalueContext::Borrow { .. } |
+                LvalueContext::Slice { .. } |
+                LvalueContext::Projection |
+                LvalueContext::Consume => {
+                    self.defs_uses.uses.push(local);
+                }
+            }
+        }
+        self.super_lvalue(lvalue, context);
+    }
+}
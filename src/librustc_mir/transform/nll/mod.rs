@@ -0,0 +1,312 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Non-lexical region inference (`-Z nll`).
+//!
+//! Instead of giving each region a lexical scope, as
+//! `middle::region` and `infer::region_inference` do, we compute
+//! regions as sets of points in the MIR:
+//!
+//! 1. Every region in a copy of the MIR is replaced with a fresh
+//!    region variable.
+//! 2. If a local is live at a point, every region in its type must
+//!    include that point.
+//! 3. Each assignment and call relates the types involved using the
+//!    ordinary `infer` machinery; the resulting subregion constraints
+//!    are tagged with the point at which they must start to hold. A
+//!    borrow through a reference also requires the reference's region
+//!    to outlive the new borrow.
+//! 4. The constraints are solved by `region_infer::RegionInferenceContext`.
+//!
+//! The results are consumed by the MIR borrow checker.
+
+use rustc::infer::{self, InferCtxt, InferOk, LateBoundRegionConversionTime, TypeOrigin};
+use rustc::infer::region_inference::Constraint;
+use rustc::traits::ProjectionMode;
+use rustc::ty::subst::Substs;
+use rustc::ty::{self, RegionVid, Ty, TyCtxt};
+use rustc::mir::repr::*;
+use rustc::mir::visit::MutVisitor;
+use syntax::ast::NodeId;
+use syntax::codemap::Span;
+
+use build::Location;
+use pretty;
+
+use self::region_infer::RegionInferenceContext;
+use self::liveness::{LocalIndex, liveness_of_locals, local_ty};
+
+pub mod liveness;
+pub mod region_infer;
+
+/// The MIR with every region replaced by a region variable, together
+/// with the inferred value of each of those variables.
+pub struct NllRegions<'tcx> {
+    pub mir: Mir<'tcx>,
+    pub regioncx: RegionInferenceContext,
+}
+
+/// Computes non-lexical regions for the fn `id` with body `mir`. The
+/// given MIR is left untouched.
+pub fn compute_regions<'tcx>(tcx: &TyCtxt<'tcx>, id: NodeId, mir: &Mir<'tcx>) -> NllRegions<'tcx> {
+    let param_env = ty::ParameterEnvironment::for_item(tcx, id);
+    let infcx = infer::new_infer_ctxt(tcx,
+                                      &tcx.tables,
+                                      Some(param_env),
+                                      ProjectionMode::AnyFinal);
+
+    let mut mir = mir.clone();
+    RenumberRegions { infcx: &infcx, span: mir.span }.visit_mir(&mut mir);
+
+    let (live_points, outlives, universal) = {
+        let mut cx = ConstraintGeneration {
+            infcx: &infcx,
+            mir: &mir,
+            live_points: vec![],
+            outlives: vec![],
+            universal: vec![],
+        };
+        cx.add_statement_constraints();
+        cx.add_liveness_constraints();
+        (cx.live_points, cx.outlives, cx.universal)
+    };
+
+    // Relating types above may have created further region variables
+    // (e.g. when instantiating a callee's late-bound regions), so only
+    // size the inference context now.
+    let mut regioncx = RegionInferenceContext::new(infcx.num_region_vars());
+    for (v, point) in live_points {
+        regioncx.add_live_point(v, point);
+    }
+    for v in universal {
+        regioncx.add_universal(v);
+    }
+    for (sup, sub, point) in outlives {
+        regioncx.add_outlives(sup, sub, point);
+    }
+    regioncx.solve(&mir);
+
+    for index in 0..regioncx.num_region_variables() {
+        let v = RegionVid { index: index as u32 };
+        debug!("compute_regions: {:?} universal={} value={:?}",
+               v, regioncx.is_universal(v), regioncx.region_value(v));
+    }
+    pretty::dump_mir(tcx, "nll", &0, id, &mir, None);
+
+    NllRegions {
+        mir: mir,
+        regioncx: regioncx,
+    }
+}
+
+/// Replaces every region that is local to the fn body with a fresh
+/// region variable. Free regions from the fn signature and `'static`
+/// are kept as they are, as are regions bound within types.
+struct RenumberRegions<'a, 'tcx: 'a> {
+    infcx: &'a InferCtxt<'a, 'tcx>,
+    span: Span,
+}
+
+impl<'a, 'tcx> RenumberRegions<'a, 'tcx> {
+    fn renumber<T>(&self, value: &T) -> T
+        where T: ty::fold::TypeFoldable<'tcx>
+    {
+        let infcx = self.infcx;
+        let span = self.span;
+        infcx.tcx.fold_regions(value, &mut false, |r, _| renumber_region(infcx, span, r))
+    }
+}
+
+fn renumber_region(infcx: &InferCtxt, span: Span, r: ty::Region) -> ty::Region {
+    match r {
+        ty::ReStatic | ty::ReFree(..) | ty::ReEarlyBound(..) | ty::ReLateBound(..) => r,
+        ty::ReScope(..) | ty::ReEmpty | ty::ReVar(..) | ty::ReSkolemized(..) => {
+            infcx.next_region_var(infer::MiscVariable(span))
+        }
+    }
+}
+
+impl<'a, 'tcx> MutVisitor<'tcx> for RenumberRegions<'a, 'tcx> {
+    fn visit_ty(&mut self, ty: &mut Ty<'tcx>) {
+        *ty = self.renumber(ty);
+    }
+
+    fn visit_substs(&mut self, substs: &mut &'tcx Substs<'tcx>) {
+        *substs = self.infcx.tcx.mk_substs(self.renumber(*substs));
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &mut Rvalue<'tcx>) {
+        if let Rvalue::Ref(ref mut r, _, _) = *rvalue {
+            *r = renumber_region(self.infcx, self.span, *r);
+        }
+        self.super_rvalue(rvalue);
+    }
+}
+
+struct ConstraintGeneration<'a, 'tcx: 'a> {
+    infcx: &'a InferCtxt<'a, 'tcx>,
+    mir: &'a Mir<'tcx>,
+    live_points: Vec<(RegionVid, Location)>,
+    outlives: Vec<(RegionVid, RegionVid, Location)>,
+    universal: Vec<RegionVid>,
+}
+
+impl<'a, 'tcx> ConstraintGeneration<'a, 'tcx> {
+    fn add_liveness_constraints(&mut self) {
+        let liveness = liveness_of_locals(self.mir);
+        let tcx = self.infcx.tcx;
+        let mir = self.mir;
+        let live_points = &mut self.live_points;
+        for block in mir.all_basic_blocks() {
+            liveness.simulate_block(mir, block, |location, live| {
+                for local in live.iter() {
+                    let ty = local_ty(mir, LocalIndex::new(local));
+                    for v in region_vids(tcx, &ty) {
+                        live_points.push((v, location));
+                    }
+                }
+            });
+        }
+    }
+
+    fn add_statement_constraints(&mut self) {
+        let tcx = self.infcx.tcx;
+        let mir = self.mir;
+        for block in mir.all_basic_blocks() {
+            let data = mir.basic_block_data(block);
+            for (statement_index, statement) in data.statements.iter().enumerate() {
+                // Effects of a statement become visible at the next point.
+                let successor = Location {
+                    block: block,
+                    statement_index: statement_index + 1,
+                };
+                match statement.kind {
+                    StatementKind::Assign(ref lv, ref rv) => {
+                        if let Rvalue::Ref(ty::ReVar(v), _, ref borrowed) = *rv {
+                            self.live_points.push((v, successor));
+                            self.add_reborrow_constraints(v, borrowed, successor);
+                        }
+                        let lv_ty = mir.lvalue_ty(tcx, lv).to_ty(tcx);
+                        if let Some(rv_ty) = mir.rvalue_ty(tcx, rv) {
+                            self.relate(statement.span, rv_ty, lv_ty, successor);
+                        }
+                    }
+                }
+            }
+
+            let terminator = data.terminator();
+            let location = Location {
+                block: block,
+                statement_index: data.statements.len(),
+            };
+            self.add_terminator_constraints(terminator, location);
+        }
+    }
+
+    /// A borrow through a reference, such as `&mut *r`, must not outlive
+    /// the reference: the region of every reference dereferenced in the
+    /// path of `borrowed` outlives the region `v` of the new borrow.
+    fn add_reborrow_constraints(&mut self,
+                                v: RegionVid,
+                                borrowed: &Lvalue<'tcx>,
+                                point: Location) {
+        let tcx = self.infcx.tcx;
+        let mut lvalue = borrowed;
+        while let Lvalue::Projection(ref proj) = *lvalue {
+            if let ProjectionElem::Deref = proj.elem {
+                let base_ty = self.mir.lvalue_ty(tcx, &proj.base).to_ty(tcx);
+                if let ty::TyRef(&ty::ReVar(base), _) = base_ty.sty {
+                    self.outlives.push((base, v, point));
+                }
+            }
+            lvalue = &proj.base;
+        }
+    }
+
+    fn add_terminator_constraints(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        let tcx = self.infcx.tcx;
+        match terminator.kind {
+            TerminatorKind::Goto { .. } |
+            TerminatorKind::If { .. } |
+            TerminatorKind::Switch { .. } |
+            TerminatorKind::SwitchInt { .. } |
+            TerminatorKind::Resume |
+            TerminatorKind::Return |
+            TerminatorKind::Drop { .. } => {
+                // no constraints
+            }
+            TerminatorKind::Call { ref func, ref args, ref destination, .. } => {
+                let func_ty = self.mir.operand_ty(tcx, func);
+                let fty = match func_ty.sty {
+                    ty::TyFnDef(_, _, fty) | ty::TyFnPtr(fty) => fty,
+                    _ => return,
+                };
+                let (sig, _) = self.infcx.replace_late_bound_regions_with_fresh_var(
+                    terminator.span,
+                    LateBoundRegionConversionTime::FnCall,
+                    &fty.sig);
+
+                for (&input_ty, arg) in sig.inputs.iter().zip(args) {
+                    let arg_ty = self.mir.operand_ty(tcx, arg);
+                    self.relate(terminator.span, arg_ty, input_ty, location);
+                }
+
+                if let (&Some((ref dest, target)), ty::FnConverging(output_ty)) =
+                    (destination, sig.output)
+                {
+                    let dest_ty = self.mir.lvalue_ty(tcx, dest).to_ty(tcx);
+                    let target_location = Location { block: target, statement_index: 0 };
+                    self.relate(terminator.span, output_ty, dest_ty, target_location);
+                }
+            }
+        }
+    }
+
+    /// Requires `sub <: sup`, recording the region constraints this
+    /// produces as holding from `point` onwards.
+    fn relate(&mut self, span: Span, sub: Ty<'tcx>, sup: Ty<'tcx>, point: Location) {
+        match infer::mk_subty(self.infcx, false, TypeOrigin::Misc(span), sub, sup) {
+            Ok(InferOk { .. }) => {}
+            Err(terr) => {
+                // The MIR type checker reports these.
+                debug!("relate: {:?} <: {:?} failed: {:?}", sub, sup, terr);
+            }
+        }
+
+        for (constraint, _) in self.infcx.take_and_reset_region_constraints() {
+            match constraint {
+                Constraint::ConstrainVarSubVar(sub, sup) => {
+                    self.outlives.push((sup, sub, point));
+                }
+                Constraint::ConstrainRegSubVar(r, sup) => {
+                    // Only free regions and `'static` survive
+                    // renumbering; both outlive the entire fn body.
+                    debug!("relate: {:?} <= {:?} makes it universal", r, sup);
+                    self.universal.push(sup);
+                }
+                Constraint::ConstrainVarSubReg(..) => {
+                    // A region that must be included in a free region;
+                    // always satisfied by a set of points in the body.
+                }
+            }
+        }
+    }
+}
+
+fn region_vids<'tcx>(tcx: &TyCtxt<'tcx>, ty: &Ty<'tcx>) -> Vec<RegionVid> {
+    let mut vids = vec![];
+    tcx.fold_regions(ty, &mut false, |r, _| {
+        if let ty::ReVar(v) = r {
+            vids.push(v);
+        }
+        r
+    });
+    vids
+}
@@ -0,0 +1,185 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Region inference where the value of each region variable is a set
+//! of points in the MIR, rather than a lexical scope.
+//!
+//! Each region variable starts out containing the points where it is
+//! required to be live (see `mod.rs`). Outlives constraints of the
+//! form `'sup: 'sub @ P` are then solved to a fixed point: `'sup` is
+//! extended with every point of `'sub` that is reachable from `P`
+//! without leaving `'sub`. This is what lets a borrow end after its
+//! last use instead of at the end of its enclosing block.
+
+use rustc::mir::repr::*;
+use rustc::ty::RegionVid;
+use rustc_data_structures::fnv::FnvHashSet;
+
+use build::Location;
+
+pub struct RegionInferenceContext {
+    /// The set of points contained in each region variable, indexed
+    /// by `RegionVid`.
+    values: Vec<FnvHashSet<Location>>,
+
+    /// Region variables that outlive the whole fn body, e.g. because
+    /// they must outlive a free region from the fn signature.
+    universal: Vec<bool>,
+
+    constraints: Vec<Constraint>,
+}
+
+/// `sup` must contain every point of `sub` that is reachable from
+/// `point`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Constraint {
+    sup: RegionVid,
+    sub: RegionVid,
+    point: Location,
+}
+
+impl RegionInferenceContext {
+    pub fn new(num_region_variables: usize) -> RegionInferenceContext {
+        RegionInferenceContext {
+            values: (0..num_region_variables).map(|_| FnvHashSet()).collect(),
+            universal: vec![false; num_region_variables],
+            constraints: vec![],
+        }
+    }
+
+    /// The points contained in the region `v`. This is only meaningful
+    /// after `solve` has been called.
+    pub fn region_value(&self, v: RegionVid) -> &FnvHashSet<Location> {
+        &self.values[v.index as usize]
+    }
+
+    pub fn is_universal(&self, v: RegionVid) -> bool {
+        self.universal[v.index as usize]
+    }
+
+    pub fn num_region_variables(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Indicates that the region `v` must include `point`, typically
+    /// because a value whose type mentions `v` is live there.
+    pub fn add_live_point(&mut self, v: RegionVid, point: Location) -> bool {
+        debug!("add_live_point({:?}, {:?})", v, point);
+        self.values[v.index as usize].insert(point)
+    }
+
+    /// Indicates that `v` must outlive the entire fn body.
+    pub fn add_universal(&mut self, v: RegionVid) {
+        debug!("add_universal({:?})", v);
+        self.universal[v.index as usize] = true;
+    }
+
+    /// Indicates that `sup: sub` must hold starting at `point`.
+    pub fn add_outlives(&mut self, sup: RegionVid, sub: RegionVid, point: Location) {
+        debug!("add_outlives({:?}: {:?} @ {:?})", sup, sub, point);
+        self.constraints.push(Constraint { sup: sup, sub: sub, point: point });
+    }
+
+    /// Propagates the outlives constraints until a fixed point is
+    /// reached.
+    pub fn solve(&mut self, mir: &Mir) {
+        let all_points = all_points(mir);
+        for (value, &universal) in self.values.iter_mut().zip(&self.universal) {
+            if universal {
+                value.extend(all_points.iter().cloned());
+            }
+        }
+
+        let mut changed = true;
+        let mut dfs = Dfs::new(mir);
+        while changed {
+            changed = false;
+            for constraint in &self.constraints {
+                let sub = constraint.sub.index as usize;
+                let sup = constraint.sup.index as usize;
+                if sub == sup {
+                    continue;
+                }
+
+                let new_points = dfs.copy(&self.values[sub], &self.values[sup], constraint.point);
+                if !new_points.is_empty() {
+                    debug!("solve: {:?} added {} points", constraint, new_points.len());
+                    self.values[sup].extend(new_points);
+                    changed = true;
+                }
+            }
+        }
+    }
+}
+
+fn all_points(mir: &Mir) -> Vec<Location> {
+    let mut points = vec![];
+    for block in mir.all_basic_blocks() {
+        let num_statements = mir.basic_block_data(block).statements.len();
+        for statement_index in 0..num_statements + 1 {
+            points.push(Location { block: block, statement_index: statement_index });
+        }
+    }
+    points
+}
+
+struct Dfs<'a, 'tcx: 'a> {
+    mir: &'a Mir<'tcx>,
+    stack: Vec<Location>,
+    visited: FnvHashSet<Location>,
+}
+
+impl<'a, 'tcx> Dfs<'a, 'tcx> {
+    fn new(mir: &'a Mir<'tcx>) -> Self {
+        Dfs {
+            mir: mir,
+            stack: vec![],
+            visited: FnvHashSet(),
+        }
+    }
+
+    /// Walks the control-flow graph forwards from `start`, staying
+    /// within the points of `from`, and returns every point found that
+    /// is not already in `to`.
+    fn copy(&mut self,
+            from: &FnvHashSet<Location>,
+            to: &FnvHashSet<Location>,
+            start: Location)
+            -> Vec<Location> {
+        let mut new_points = vec![];
+        self.stack.clear();
+        self.visited.clear();
+        self.stack.push(start);
+
+        while let Some(p) = self.stack.pop() {
+            if !from.contains(&p) || !self.visited.insert(p) {
+                continue;
+            }
+
+            if !to.contains(&p) {
+                new_points.push(p);
+            }
+
+            let block_data = self.mir.basic_block_data(p.block);
+            if p.statement_index < block_data.statements.len() {
+                self.stack.push(Location {
+                    block: p.block,
+                    statement_index: p.statement_index + 1,
+                });
+            } else {
+                self.stack.extend(block_data.terminator().successors().iter().map(|&block| {
+                    Location { block: block, statement_index: 0 }
+                }));
+            }
+        }
+
+        new_points
+    }
+}
@@ -43,7 +43,8 @@ use rustc::util::nodemap::NodeSet;
 use rustc::hir::map as ast_map;
 
 use syntax::ast;
-use syntax::codemap::Span;
+use syntax::codemap::{BytePos, Span};
+use syntax::errors::Level;
 
 pub mod diagnostics;
 
@@ -941,6 +942,9 @@ struct SearchInterfaceForPrivateItemsVisitor<'a, 'tcx: 'a> {
     /// The visibility of the least visible component that has been visited
     min_visibility: ty::Visibility,
     old_error_set: &'a NodeSet,
+    /// How the component being visited is reached from the interface being checked,
+    /// outermost first. Used to explain how a private component leaks.
+    leak_path: Vec<(Span, String)>,
 }
 
 impl<'a, 'tcx: 'a> SearchInterfaceForPrivateItemsVisitor<'a, 'tcx> {
@@ -950,6 +954,7 @@ impl<'a, 'tcx: 'a> SearchInterfaceForPrivateItemsVisitor<'a, 'tcx> {
             min_visibility: ty::Visibility::Public,
             required_visibility: ty::Visibility::PrivateExternal,
             old_error_set: old_error_set,
+            leak_path: Vec::new(),
         }
     }
 }
@@ -968,9 +973,8 @@ impl<'a, 'tcx: 'a> SearchInterfaceForPrivateItemsVisitor<'a, 'tcx> {
         // public, even if the type alias itself is private. So, something
         // like `type A = u8; pub fn f() -> A {...}` doesn't cause an error.
         if let hir::ItemTy(ref ty, ref generics) = item.node {
-            let mut check = SearchInterfaceForPrivateItemsVisitor {
-                min_visibility: ty::Visibility::Public, ..*self
-            };
+            let mut check = SearchInterfaceForPrivateItemsVisitor::new(self.tcx,
+                                                                       self.old_error_set);
             check.visit_ty(ty);
             // If a private type alias with default type parameters is used in public
             // interface we must ensure, that the defaults are public if they are actually used.
@@ -989,6 +993,103 @@ impl<'a, 'tcx: 'a> SearchInterfaceForPrivateItemsVisitor<'a, 'tcx> {
             None
         }
     }
+
+    fn start_leak_path(&mut self, interface_span: Span, interface: String) {
+        self.leak_path = vec![(self.item_head_span(interface_span), interface)];
+    }
+
+    fn with_leak_step<F: FnOnce(&mut Self)>(&mut self, span: Span, step: String, f: F) {
+        self.leak_path.push((span, step));
+        f(self);
+        self.leak_path.pop();
+    }
+
+    // Update the minimal visibility with the visibility of `item` used at `span` and
+    // report it if it isn't visible enough for the interface being checked
+    fn check_component(&mut self, item: &hir::Item, vis: ty::Visibility, span: Span,
+                       use_id: ast::NodeId, is_trait_ref: bool) {
+        if !vis.is_at_least(self.min_visibility, &self.tcx.map) {
+            self.min_visibility = vis;
+        }
+        if vis.is_at_least(self.required_visibility, &self.tcx.map) {
+            return;
+        }
+
+        let children = self.leak_explanation(item);
+        if self.tcx.sess.features.borrow().pub_restricted ||
+           self.old_error_set.contains(&use_id) {
+            let mut err = if is_trait_ref {
+                struct_span_err!(self.tcx.sess, span, E0445, "private trait in public interface")
+            } else {
+                struct_span_err!(self.tcx.sess, span, E0446, "private type in public interface")
+            };
+            for (level, span, msg) in children {
+                match (level, span) {
+                    (Level::Note, Some(span)) => err.span_note(span, &msg),
+                    (Level::Help, None) => err.help(&msg),
+                    _ => bug!("unexpected leak explanation {:?}", msg),
+                };
+            }
+            err.emit();
+        } else {
+            let msg = if is_trait_ref {
+                "private trait in public interface (error E0445)"
+            } else {
+                "private type in public interface"
+            };
+            let mut early_lint = lint::EarlyLint::new(lint::builtin::PRIVATE_IN_PUBLIC,
+                                                      span,
+                                                      msg.to_string());
+            early_lint.children = children;
+            self.tcx.sess.add_early_lint(item.id, early_lint);
+        }
+    }
+
+    // Explain how the private `item` is reached from the interface being checked
+    // and how the leak could be fixed
+    fn leak_explanation(&self, item: &hir::Item) -> Vec<(Level, Option<Span>, String)> {
+        let mut children = Vec::new();
+        let mut steps = self.leak_path.iter();
+        if let Some(&(span, ref interface)) = steps.next() {
+            let msg = format!("`{}` leaks through the interface of {}", item.name, interface);
+            children.push((Level::Note, Some(span), msg));
+        }
+        for &(span, ref step) in steps {
+            children.push((Level::Note, Some(span), format!("via {}", step)));
+        }
+
+        let (kind, alternative) = match item.node {
+            hir::ItemTy(..) => ("type alias", "using the aliased type directly"),
+            hir::ItemTrait(..) => ("trait", "removing it from the public interface"),
+            hir::ItemEnum(..) => ("enum", "wrapping it in a public newtype with a private field"),
+            _ => ("struct", "wrapping it in a public newtype with a private field"),
+        };
+        let declared = if item.vis == hir::Inherited {
+            "private"
+        } else {
+            "with restricted visibility"
+        };
+        children.push((Level::Note, Some(self.item_head_span(item.span)),
+                       format!("{} `{}` is declared {} here", kind, item.name, declared)));
+        let widen = if self.required_visibility == ty::Visibility::Public {
+            "making it public"
+        } else {
+            "widening its visibility"
+        };
+        children.push((Level::Help, None, format!("consider {}, or {}", widen, alternative)));
+        children
+    }
+
+    // The first line of an item, to avoid quoting whole item bodies in notes
+    fn item_head_span(&self, span: Span) -> Span {
+        match self.tcx.sess.codemap().span_to_snippet(span) {
+            Ok(snippet) => match snippet.find('\n') {
+                Some(newline) => Span { hi: span.lo + BytePos(newline as u32), ..span },
+                None => span,
+            },
+            Err(_) => span,
+        }
+    }
 }
 
 impl<'a, 'tcx: 'a, 'v> Visitor<'v> for SearchInterfaceForPrivateItemsVisitor<'a, 'tcx> {
@@ -1019,20 +1120,15 @@ impl<'a, 'tcx: 'a, 'v> Visitor<'v> for SearchInterfaceForPrivateItemsVisitor<'a,
                             None => ty::Visibility::from_hir(&item.vis, node_id, &self.tcx),
                         };
 
-                        if !vis.is_at_least(self.min_visibility, &self.tcx.map) {
-                            self.min_visibility = vis;
-                        }
-                        if !vis.is_at_least(self.required_visibility, &self.tcx.map) {
-                            if self.tcx.sess.features.borrow().pub_restricted ||
-                               self.old_error_set.contains(&ty.id) {
-                                span_err!(self.tcx.sess, ty.span, E0446,
-                                          "private type in public interface");
-                            } else {
-                                self.tcx.sess.add_lint(lint::builtin::PRIVATE_IN_PUBLIC,
-                                                       node_id,
-                                                       ty.span,
-                                                       format!("private type in public interface"));
-                            }
+                        if let Def::AssociatedTy(..) = def {
+                            let name = path.segments.last().unwrap().identifier.name;
+                            let step = format!("associated type `{}` of trait `{}`",
+                                               name, item.name);
+                            self.with_leak_step(ty.span, step, |this| {
+                                this.check_component(item, vis, ty.span, ty.id, false);
+                            });
+                        } else {
+                            self.check_component(item, vis, ty.span, ty.id, false);
                         }
                     }
                 }
@@ -1049,26 +1145,101 @@ impl<'a, 'tcx: 'a, 'v> Visitor<'v> for SearchInterfaceForPrivateItemsVisitor<'a,
         if let Some(node_id) = self.tcx.map.as_local_node_id(def_id) {
             let item = self.tcx.map.expect_item(node_id);
             let vis = ty::Visibility::from_hir(&item.vis, node_id, &self.tcx);
+            self.check_component(item, vis, trait_ref.path.span, trait_ref.ref_id, true);
+        }
+
+        intravisit::walk_trait_ref(self, trait_ref);
+    }
 
-            if !vis.is_at_least(self.min_visibility, &self.tcx.map) {
-                self.min_visibility = vis;
+    fn visit_item(&mut self, item: &hir::Item) {
+        if let hir::ItemTrait(_, ref generics, ref bounds, ref trait_items) = item.node {
+            self.visit_generics(generics);
+            for bound in bounds {
+                if let hir::TraitTyParamBound(ref poly_trait_ref, _) = *bound {
+                    let step = format!("the supertraits of `{}`", item.name);
+                    self.with_leak_step(poly_trait_ref.span, step, |this| {
+                        this.visit_ty_param_bound(bound);
+                    });
+                }
             }
-            if !vis.is_at_least(self.required_visibility, &self.tcx.map) {
-                if self.tcx.sess.features.borrow().pub_restricted ||
-                   self.old_error_set.contains(&trait_ref.ref_id) {
-                    span_err!(self.tcx.sess, trait_ref.path.span, E0445,
-                              "private trait in public interface");
-                } else {
-                    self.tcx.sess.add_lint(lint::builtin::PRIVATE_IN_PUBLIC,
-                                           node_id,
-                                           trait_ref.path.span,
-                                           "private trait in public interface (error E0445)"
-                                                .to_string());
+            for trait_item in trait_items {
+                self.visit_trait_item(trait_item);
+            }
+        } else {
+            intravisit::walk_item(self, item);
+        }
+    }
+
+    fn visit_generics(&mut self, generics: &hir::Generics) {
+        for ty_param in generics.ty_params.iter() {
+            let step = format!("type parameter `{}`", ty_param.name);
+            self.with_leak_step(ty_param.span, step, |this| {
+                for bound in ty_param.bounds.iter() {
+                    this.visit_ty_param_bound(bound);
+                }
+                if let Some(ref default_ty) = ty_param.default {
+                    this.visit_ty(default_ty);
+                }
+            });
+        }
+        for predicate in generics.where_clause.predicates.iter() {
+            match *predicate {
+                hir::WherePredicate::BoundPredicate(ref predicate) => {
+                    let step = "this `where` clause".to_string();
+                    self.with_leak_step(predicate.span, step, |this| {
+                        this.visit_ty(&predicate.bounded_ty);
+                        for bound in predicate.bounds.iter() {
+                            this.visit_ty_param_bound(bound);
+                        }
+                    });
+                }
+                hir::WherePredicate::EqPredicate(ref predicate) => {
+                    let step = "this `where` clause".to_string();
+                    self.with_leak_step(predicate.span, step, |this| {
+                        this.visit_path(&predicate.path, predicate.id);
+                        this.visit_ty(&predicate.ty);
+                    });
                 }
+                hir::WherePredicate::RegionPredicate(..) => {}
             }
         }
+    }
 
-        intravisit::walk_trait_ref(self, trait_ref);
+    fn visit_struct_field(&mut self, field: &hir::StructField) {
+        let step = format!("field `{}`", field.name);
+        self.with_leak_step(field.span, step, |this| intravisit::walk_struct_field(this, field));
+    }
+
+    fn visit_variant(&mut self, variant: &hir::Variant, generics: &hir::Generics,
+                     item_id: ast::NodeId) {
+        let step = format!("variant `{}`", variant.node.name);
+        self.with_leak_step(variant.span, step, |this| {
+            intravisit::walk_variant(this, variant, generics, item_id);
+        });
+    }
+
+    fn visit_trait_item(&mut self, trait_item: &hir::TraitItem) {
+        let kind = match trait_item.node {
+            hir::ConstTraitItem(..) => "associated constant",
+            hir::MethodTraitItem(..) => "method",
+            hir::TypeTraitItem(..) => "associated type",
+        };
+        let step = format!("{} `{}`", kind, trait_item.name);
+        self.with_leak_step(trait_item.span, step, |this| {
+            intravisit::walk_trait_item(this, trait_item);
+        });
+    }
+
+    fn visit_impl_item(&mut self, impl_item: &hir::ImplItem) {
+        let kind = match impl_item.node {
+            hir::ImplItemKind::Const(..) => "associated constant",
+            hir::ImplItemKind::Method(..) => "method",
+            hir::ImplItemKind::Type(..) => "associated type",
+        };
+        let step = format!("{} `{}`", kind, impl_item.name);
+        self.with_leak_step(impl_item.span, step, |this| {
+            intravisit::walk_impl_item(this, impl_item);
+        });
     }
 
     // Don't recurse into function bodies
@@ -1100,6 +1271,28 @@ impl<'a, 'tcx> PrivateItemsInPublicInterfacesVisitor<'a, 'tcx> {
     }
 }
 
+// Describes an item whose interface is checked, for the start of a leak path
+fn interface_description(item: &hir::Item) -> String {
+    match item.node {
+        hir::ItemConst(..) => format!("constant `{}`", item.name),
+        hir::ItemStatic(..) => format!("static `{}`", item.name),
+        hir::ItemFn(..) => format!("function `{}`", item.name),
+        hir::ItemEnum(..) => format!("enum `{}`", item.name),
+        hir::ItemTrait(..) => format!("trait `{}`", item.name),
+        hir::ItemTy(..) => format!("type alias `{}`", item.name),
+        hir::ItemStruct(..) => format!("struct `{}`", item.name),
+        hir::ItemImpl(_, _, _, None, ref ty, _) => {
+            format!("the inherent impl for `{}`", hir::print::ty_to_string(ty))
+        }
+        hir::ItemImpl(_, _, _, Some(ref trait_ref), ref ty, _) => {
+            format!("the impl of `{}` for `{}`",
+                    hir::print::path_to_string(&trait_ref.path),
+                    hir::print::ty_to_string(ty))
+        }
+        _ => format!("`{}`", item.name),
+    }
+}
+
 impl<'a, 'tcx, 'v> Visitor<'v> for PrivateItemsInPublicInterfacesVisitor<'a, 'tcx> {
     fn visit_item(&mut self, item: &hir::Item) {
         let min = |vis1: ty::Visibility, vis2| {
@@ -1119,12 +1312,19 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivateItemsInPublicInterfacesVisitor<'a, 'tc
             // Subitems of these items have inherited publicity
             hir::ItemConst(..) | hir::ItemStatic(..) | hir::ItemFn(..) |
             hir::ItemEnum(..) | hir::ItemTrait(..) | hir::ItemTy(..) => {
+                check.start_leak_path(item.span, interface_description(item));
                 check.required_visibility = item_visibility;
                 check.visit_item(item);
             }
             // Subitems of foreign modules have their own publicity
             hir::ItemForeignMod(ref foreign_mod) => {
                 for foreign_item in &foreign_mod.items {
+                    let kind = match foreign_item.node {
+                        hir::ForeignItemFn(..) => "foreign function",
+                        hir::ForeignItemStatic(..) => "foreign static",
+                    };
+                    check.start_leak_path(foreign_item.span,
+                                          format!("{} `{}`", kind, foreign_item.name));
                     check.required_visibility =
                         ty::Visibility::from_hir(&foreign_item.vis, item.id, &self.tcx);
                     check.visit_foreign_item(foreign_item);
@@ -1132,6 +1332,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivateItemsInPublicInterfacesVisitor<'a, 'tc
             }
             // Subitems of structs have their own publicity
             hir::ItemStruct(ref struct_def, ref generics) => {
+                check.start_leak_path(item.span, interface_description(item));
                 check.required_visibility = item_visibility;
                 check.visit_generics(generics);
 
@@ -1147,6 +1348,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivateItemsInPublicInterfacesVisitor<'a, 'tc
             // Subitems of inherent impls have their own publicity
             hir::ItemImpl(_, _, ref generics, None, ref ty, ref impl_items) => {
                 let ty_vis = self.ty_visibility(ty);
+                check.start_leak_path(item.span, interface_description(item));
                 check.required_visibility = ty_vis;
                 check.visit_generics(generics);
 
@@ -1161,6 +1363,7 @@ impl<'a, 'tcx, 'v> Visitor<'v> for PrivateItemsInPublicInterfacesVisitor<'a, 'tc
             // Subitems of trait impls have inherited publicity
             hir::ItemImpl(_, _, ref generics, Some(ref trait_ref), ref ty, ref impl_items) => {
                 let vis = min(self.ty_visibility(ty), self.trait_ref_visibility(trait_ref));
+                check.start_leak_path(item.span, interface_description(item));
                 check.required_visibility = vis;
                 check.visit_generics(generics);
                 for impl_item in impl_items {
@@ -16,14 +16,18 @@
 // resolve data structures and because it finalises the privacy information for
 // `use` directives.
 //
+// The same goes for `--extern` crates: whether one is needed can only be
+// decided once resolution knows which crates it actually used.
+//
 
+use std::collections::HashSet;
 use std::ops::{Deref, DerefMut};
 
 use Resolver;
 use Namespace::{TypeNS, ValueNS};
 
 use rustc::lint;
-use syntax::ast;
+use syntax::ast::{self, CrateNum};
 use syntax::codemap::{Span, DUMMY_SP};
 
 use rustc::hir;
@@ -32,6 +36,9 @@ use rustc::hir::intravisit::Visitor;
 
 struct UnusedImportCheckVisitor<'a, 'b: 'a, 'tcx: 'b> {
     resolver: &'a mut Resolver<'b, 'tcx>,
+    // Crates named by a `pub extern crate`, which are used by re-exporting
+    // them even if nothing in this crate refers to them.
+    reexported_crates: HashSet<CrateNum>,
 }
 
 // Deref and DerefMut impls allow treating UnusedImportCheckVisitor as Resolver.
@@ -65,6 +72,14 @@ impl<'a, 'b, 'tcx> UnusedImportCheckVisitor<'a, 'b, 'tcx> {
 
 impl<'a, 'b, 'v, 'tcx> Visitor<'v> for UnusedImportCheckVisitor<'a, 'b, 'tcx> {
     fn visit_item(&mut self, item: &hir::Item) {
+        if let hir::ItemExternCrate(_) = item.node {
+            if item.vis == hir::Public {
+                if let Some(crate_num) = self.session.cstore.extern_mod_stmt_cnum(item.id) {
+                    self.reexported_crates.insert(crate_num);
+                }
+            }
+        }
+
         // Ignore is_public import statements because there's no way to be sure
         // whether they're used or not. Also ignore imports with a dummy span
         // because this means that they were generated in some fashion by the
@@ -105,7 +120,42 @@ impl<'a, 'b, 'v, 'tcx> Visitor<'v> for UnusedImportCheckVisitor<'a, 'b, 'tcx> {
     }
 }
 
-pub fn check_crate(resolver: &mut Resolver, krate: &hir::Crate) {
-    let mut visitor = UnusedImportCheckVisitor { resolver: resolver };
+/// Finds the `--extern` crates that this crate does not need.
+///
+/// An `--extern` crate is used if resolution referred to it through an
+/// `extern crate` item, if it is re-exported by a `pub extern crate`, or if
+/// one of its macros was expanded or it was loaded as a plugin, which
+/// resolution never sees.
+fn unused_extern_options(visitor: &UnusedImportCheckVisitor) -> Vec<String> {
+    let cstore = &visitor.session.cstore;
+    let used_names = cstore.crates().into_iter().filter(|&cnum| {
+        let direct = cstore.extern_crate(cnum).map_or(false, |c| c.direct);
+        direct && (visitor.used_crates.contains(&cnum) ||
+                   visitor.reexported_crates.contains(&cnum))
+    }).map(|cnum| cstore.crate_name(cnum).to_string()).collect::<HashSet<_>>();
+
+    let mut unused = visitor.session.opts.externs.keys().filter(|name| {
+        !used_names.contains(*name) && !cstore.is_used_extension_crate(name)
+    }).cloned().collect::<Vec<_>>();
+    unused.sort();
+    unused
+}
+
+/// Reports unused imports and `extern crate` items, and returns the names of
+/// the `--extern` crates that are never used.
+pub fn check_crate(resolver: &mut Resolver, krate: &hir::Crate) -> Vec<String> {
+    let mut visitor = UnusedImportCheckVisitor {
+        resolver: resolver,
+        reexported_crates: HashSet::new(),
+    };
     krate.visit_all_items(&mut visitor);
+
+    let unused_externs = unused_extern_options(&visitor);
+    for name in &unused_externs {
+        visitor.session.add_lint(lint::builtin::UNUSED_EXTERN_OPTIONS,
+                                 ast::CRATE_NODE_ID,
+                                 DUMMY_SP,
+                                 format!("unused `--extern` crate: `{}`", name));
+    }
+    unused_externs
 }
@@ -50,7 +50,7 @@ use rustc::session::Session;
 use rustc::lint;
 use rustc::middle::cstore::CrateStore;
 use rustc::hir::def::*;
-use rustc::hir::def_id::DefId;
+use rustc::hir::def_id::{CRATE_DEF_INDEX, DefId};
 use rustc::hir::pat_util::pat_bindings;
 use rustc::ty;
 use rustc::ty::subst::{ParamSpace, FnSpace, TypeSpace};
@@ -97,6 +97,9 @@ mod diagnostics;
 mod check_unused;
 mod build_reduced_graph;
 mod resolve_imports;
+mod macros;
+
+pub use macros::DeclMacroResolver;
 
 // Perform the callback, not walking deeper if the return is true
 macro_rules! execute_callback {
@@ -116,9 +119,19 @@ enum SuggestionType {
 }
 
 /// Candidates for a name resolution failure
+#[derive(Clone, PartialEq, Eq, Debug)]
 struct SuggestedCandidates {
     name: String,
-    candidates: Vec<Path>,
+    candidates: Vec<ImportSuggestion>,
+}
+
+/// A path that the unresolved name could be imported through
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct ImportSuggestion {
+    path: Path,
+    /// The crate that has to be linked with `extern crate` before `path`
+    /// can be used, if it is not reachable from the crate root yet.
+    extern_crate: Option<Name>,
 }
 
 enum ResolutionError<'a> {
@@ -209,6 +222,11 @@ enum UnresolvedNameContext {
     /// of the unresolved name error. (Maybe we could eliminate all
     /// such cases; but for now, this is an information-free default.)
     Other,
+
+    /// `Importable(candidates)` indicates that the name is not in
+    /// scope, but items with that name exist elsewhere in the crate or
+    /// in one of its dependencies and could be imported.
+    Importable(SuggestedCandidates),
 }
 
 fn resolve_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
@@ -435,6 +453,9 @@ fn resolve_struct_error<'b, 'a: 'b, 'tcx: 'a>(resolver: &'b Resolver<'a, 'tcx>,
 
             match context {
                 UnresolvedNameContext::Other => { } // no help available
+                UnresolvedNameContext::Importable(candidates) => {
+                    show_candidates(&mut err, span, &candidates);
+                }
                 UnresolvedNameContext::PathIsMod(id) => {
                     let mut help_msg = String::new();
                     let parent_id = resolver.ast_map.get_parent_node(id);
@@ -1064,6 +1085,9 @@ pub struct Resolver<'a, 'tcx: 'a> {
     freevars_seen: NodeMap<NodeMap<usize>>,
     export_map: ExportMap,
     trait_map: TraitMap,
+    // All the traits in scope at each method call that no trait in scope has
+    // a method for, so that typeck can suggest similarly named methods.
+    traits_in_scope: TraitMap,
 
     // A map from nodes to modules, both normal (`mod`) modules and anonymous modules.
     // Anonymous modules are pseudo-modules that are implicitly created around items
@@ -1094,6 +1118,10 @@ pub struct Resolver<'a, 'tcx: 'a> {
     used_imports: HashSet<(NodeId, Namespace)>,
     used_crates: HashSet<CrateNum>,
 
+    // The root modules of the crates that aren't reachable from the module
+    // tree, built when they are first searched for import candidates.
+    unreachable_crate_modules: HashMap<CrateNum, Module<'a>>,
+
     // Callback function for intercepting walks
     callback: Option<Box<Fn(hir_map::Node, &mut bool) -> bool>>,
     // The intention is that the callback modifies this flag.
@@ -1182,9 +1210,11 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             freevars_seen: NodeMap(),
             export_map: NodeMap(),
             trait_map: NodeMap(),
+            traits_in_scope: NodeMap(),
             module_map: NodeMap(),
             used_imports: HashSet::new(),
             used_crates: HashSet::new(),
+            unreachable_crate_modules: HashMap::new(),
 
             emit_errors: true,
             make_glob_map: make_glob_map == MakeGlobMap::Yes,
@@ -1323,7 +1353,17 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                             None => format!("Maybe a missing `extern crate {}`?", segment_name),
                         }
                     } else {
-                        format!("Could not find `{}` in `{}`", segment_name, module_name)
+                        let similar_name =
+                            self.find_similar_name_in_module(search_module, name, Some(TypeNS));
+                        match similar_name {
+                            Some(found) => {
+                                format!("Could not find `{}` in `{}`. Did you mean `{}::{}`?",
+                                        segment_name, module_name, module_name, found)
+                            }
+                            None => {
+                                format!("Could not find `{}` in `{}`", segment_name, module_name)
+                            }
+                        }
                     };
 
                     return Failed(Some((span, msg)));
@@ -2222,6 +2262,8 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                     },
                                 );
 
+                            let similar_path = self.find_similar_path(path, TypeNS);
+
                             // create error object
                             let name = &path_names_to_string(path, 0);
                             let error =
@@ -2231,7 +2273,12 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                     candidates,
                                 );
 
-                            resolve_error(self, ty.span, error);
+                            let mut err = resolve_struct_error(self, ty.span, error);
+                            if let Some(similar_path) = similar_path {
+                                err.fileline_help(ty.span,
+                                                  &format!("did you mean `{}`?", similar_path));
+                            }
+                            err.emit();
                         }
                     }
                 }
@@ -2968,6 +3015,66 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         } SuggestionType::NotFound
     }
 
+    /// Finds the name defined in `module` that is most similar to `name`,
+    /// considering only the names that are accessible from the current module
+    /// and, if `namespace` is given, that are defined in that namespace.
+    fn find_similar_name_in_module(&mut self,
+                                   module: Module<'a>,
+                                   name: Name,
+                                   namespace: Option<Namespace>)
+                                   -> Option<token::InternedString> {
+        self.populate_module_if_necessary(module);
+
+        let resolutions = module.resolutions.borrow();
+        let names = resolutions.iter().filter_map(|(&(ref candidate, ns), resolution)| {
+            // Never suggest the same name
+            if *candidate == name || namespace.map_or(false, |namespace| namespace != ns) {
+                return None;
+            }
+            let resolution = resolution.borrow();
+            match resolution.binding {
+                Some(binding) if self.is_accessible(binding.vis) => Some(candidate),
+                Some(_) => None,
+                None if resolution.has_pending_single_import() => Some(candidate),
+                None => None,
+            }
+        });
+        find_best_match_for_name(names, &name.as_str(), None)
+    }
+
+    /// For a path like `a::b::c` that failed to resolve, finds a path to a
+    /// similarly named item in the module `a::b`, if that module exists.
+    fn find_similar_path(&mut self, path: &Path, namespace: Namespace) -> Option<String> {
+        let (last, module_segments) = match path.segments.split_last() {
+            Some((last, module_segments)) if !module_segments.is_empty() =>
+                (last, module_segments),
+            _ => return None,
+        };
+        let mut names = module_segments.iter()
+                                       .map(|seg| seg.identifier.name)
+                                       .collect::<Vec<_>>();
+
+        let module_result = if path.global {
+            let graph_root = self.graph_root;
+            self.resolve_module_path_from_root(graph_root, &names, 0, path.span)
+        } else {
+            self.resolve_module_path(&names, UseLexicalScope, path.span)
+        };
+        let module = match module_result {
+            Success(module) => module,
+            _ => return None,
+        };
+
+        let found = self.find_similar_name_in_module(module,
+                                                     last.identifier.name,
+                                                     Some(namespace));
+        found.map(|found| {
+            names.push(token::intern(&found));
+            let prefix = if path.global { "::" } else { "" };
+            format!("{}{}", prefix, names_to_string(&names))
+        })
+    }
+
     fn resolve_expr(&mut self, expr: &Expr) {
         // First, record candidate traits for this expression if it could
         // result in the invocation of a method call.
@@ -3081,6 +3188,14 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                             } else {
                                 let last_name = path.segments.last().unwrap().identifier.name;
                                 let mut msg = match self.find_fallback_in_self_type(last_name) {
+                                    NoSuggestion if path.segments.len() > 1 => {
+                                        // look for a similar name in the module
+                                        // that the path leads to
+                                        match self.find_similar_path(path, ValueNS) {
+                                            Some(s) => format!("`{}`", s),
+                                            None => "".to_string(),
+                                        }
+                                    }
                                     NoSuggestion => {
                                         // limit search to 5 to reduce the number
                                         // of stupid suggestions
@@ -3116,7 +3231,25 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                                         Success(_) => {
                                             context = UnresolvedNameContext::PathIsMod(expr.id);
                                         },
-                                        _ => {},
+                                        _ => {
+                                            // look for importable values with that name
+                                            let candidates = self.lookup_candidates(
+                                                last_name,
+                                                ValueNS,
+                                                |def| match def {
+                                                    Def::Fn(_) |
+                                                    Def::Const(_) |
+                                                    Def::Static(..) |
+                                                    Def::Struct(_) |
+                                                    Def::Variant(..) => true,
+                                                    _ => false,
+                                                },
+                                            );
+                                            if !candidates.candidates.is_empty() {
+                                                context =
+                                                    UnresolvedNameContext::Importable(candidates);
+                                            }
+                                        },
                                     };
                                 }
 
@@ -3209,6 +3342,14 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                 debug!("(recording candidate traits for expr) recording traits for {}",
                        expr.id);
                 let traits = self.get_traits_containing_item(name.node);
+                if traits.is_empty() {
+                    // No trait in scope has a method with this name, so this is
+                    // either an inherent method call or an error. Keep the traits
+                    // in scope aside, so that typeck can suggest their similarly
+                    // named methods.
+                    let traits_in_scope = self.get_traits_in_scope(None);
+                    self.traits_in_scope.insert(expr.id, traits_in_scope);
+                }
                 self.trait_map.insert(expr.id, traits);
             }
             _ => {
@@ -3219,19 +3360,22 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
     fn get_traits_containing_item(&mut self, name: Name) -> Vec<DefId> {
         debug!("(getting traits containing item) looking for '{}'", name);
+        self.get_traits_in_scope(Some(name))
+    }
 
-        fn add_trait_info(found_traits: &mut Vec<DefId>, trait_def_id: DefId, name: Name) {
-            debug!("(adding trait info) found trait {:?} for method '{}'",
-                   trait_def_id,
-                   name);
+    /// Returns the traits in scope that have an item named `name`, marking
+    /// them as used, or all the traits in scope if `name` is None.
+    fn get_traits_in_scope(&mut self, name: Option<Name>) -> Vec<DefId> {
+        fn add_trait_info(found_traits: &mut Vec<DefId>, trait_def_id: DefId) {
+            debug!("(adding trait info) found trait {:?}", trait_def_id);
             found_traits.push(trait_def_id);
         }
 
         let mut found_traits = Vec::new();
         // Look for the current trait.
         if let Some((trait_def_id, _)) = self.current_trait_ref {
-            if self.trait_item_map.contains_key(&(name, trait_def_id)) {
-                add_trait_info(&mut found_traits, trait_def_id, name);
+            if name.map_or(true, |name| self.trait_item_map.contains_key(&(name, trait_def_id))) {
+                add_trait_info(&mut found_traits, trait_def_id);
             }
         }
 
@@ -3253,10 +3397,15 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
                 for binding in traits.as_ref().unwrap().iter() {
                     let trait_def_id = binding.def().unwrap().def_id();
-                    if self.trait_item_map.contains_key(&(name, trait_def_id)) {
-                        add_trait_info(&mut found_traits, trait_def_id, name);
-                        let trait_name = self.get_trait_name(trait_def_id);
-                        self.record_use(trait_name, TypeNS, binding);
+                    match name {
+                        Some(name) => {
+                            if self.trait_item_map.contains_key(&(name, trait_def_id)) {
+                                add_trait_info(&mut found_traits, trait_def_id);
+                                let trait_name = self.get_trait_name(trait_def_id);
+                                self.record_use(trait_name, TypeNS, binding);
+                            }
+                        }
+                        None => add_trait_info(&mut found_traits, trait_def_id),
                     }
                 }
             };
@@ -3279,19 +3428,100 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
     /// When name resolution fails, this method can be used to look up candidate
     /// entities with the expected name. It allows filtering them using the
     /// supplied predicate (which should be used to only accept the types of
-    /// definitions expected e.g. traits). The lookup spans across all crates.
+    /// definitions expected e.g. traits). The lookup spans across all crates:
+    /// first the module tree of the current crate (including any `extern crate`
+    /// items in it), and then every other crate known to the crate store.
     ///
-    /// NOTE: The method does not look into imports, but this is not a problem,
-    /// since we report the definitions (thus, the de-aliased imports).
+    /// Public imports (`pub use`) are reported alongside the definitions they
+    /// re-export, since the re-exported path is usually the one to import.
     fn lookup_candidates<FilterFn>(&mut self,
                                    lookup_name: Name,
                                    namespace: Namespace,
                                    filter_fn: FilterFn) -> SuggestedCandidates
         where FilterFn: Fn(Def) -> bool {
 
+        let mut reached_crates = HashSet::new();
+        let graph_root = self.graph_root;
+        let local_results = self.lookup_candidates_in_module(graph_root, Vec::new(), false,
+                                                             lookup_name, namespace,
+                                                             &filter_fn, &mut reached_crates);
+
+        let mut found_defs: HashSet<_> = local_results.iter().map(|&(_, def_id)| def_id).collect();
+        let mut lookup_results: Vec<_> = local_results.into_iter().map(|(path, _)| {
+            ImportSuggestion { path: path, extern_crate: None }
+        }).collect();
+
+        // Crates that are loaded but not reachable from the module tree (e.g. ones
+        // only pulled in as dependencies) need an `extern crate` item before
+        // anything in them can be imported. Only suggest items from them that
+        // were not already found through a path which is usable as-is. The
+        // crates behind the standard library (`core`, `collections`, ...) are
+        // unstable and the allocators are injected, so they are never suggested.
+        let mut crates = self.session.cstore.crates();
+        crates.sort();
+        for cnum in crates {
+            if reached_crates.contains(&cnum) ||
+               self.session.cstore.is_staged_api(cnum) ||
+               self.session.cstore.is_allocator(cnum) {
+                continue
+            }
+
+            let crate_name = token::intern(&self.session.cstore.crate_name(cnum));
+            let module = self.unreachable_crate_module(cnum);
+
+            let segments = vec![PathSegment {
+                identifier: hir::Ident::from_name(crate_name),
+                parameters: PathParameters::none(),
+            }];
+            let crate_results = self.lookup_candidates_in_module(module, segments, true,
+                                                                 lookup_name, namespace,
+                                                                 &filter_fn,
+                                                                 &mut HashSet::new());
+
+            let mut crate_defs = HashSet::new();
+            for (path, def_id) in crate_results {
+                crate_defs.insert(def_id);
+                if found_defs.contains(&def_id) { continue }
+                lookup_results.push(ImportSuggestion {
+                    path: path,
+                    extern_crate: Some(crate_name),
+                });
+            }
+            found_defs.extend(crate_defs);
+        }
+
+        SuggestedCandidates {
+            name: lookup_name.as_str().to_string(),
+            candidates: lookup_results,
+        }
+    }
+
+    fn unreachable_crate_module(&mut self, cnum: CrateNum) -> Module<'a> {
+        if let Some(&module) = self.unreachable_crate_modules.get(&cnum) {
+            return module;
+        }
+
+        let def = Def::Mod(DefId { krate: cnum, index: CRATE_DEF_INDEX });
+        let module = self.new_module(NoParentLink, Some(def), false, ty::Visibility::Public);
+        self.build_reduced_graph_for_external_crate(module);
+        self.unreachable_crate_modules.insert(cnum, module);
+        module
+    }
+
+    fn lookup_candidates_in_module<FilterFn>(&mut self,
+                                             root: Module<'a>,
+                                             root_segments: Vec<PathSegment>,
+                                             root_is_extern: bool,
+                                             lookup_name: Name,
+                                             namespace: Namespace,
+                                             filter_fn: &FilterFn,
+                                             reached_crates: &mut HashSet<CrateNum>)
+                                             -> Vec<(Path, DefId)>
+        where FilterFn: Fn(Def) -> bool {
+
         let mut lookup_results = Vec::new();
         let mut worklist = Vec::new();
-        worklist.push((self.graph_root, Vec::new(), false));
+        worklist.push((root, root_segments, root_is_extern));
 
         while let Some((in_module,
                         path_segments,
@@ -3300,12 +3530,25 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
 
             in_module.for_each_child(|name, ns, name_binding| {
 
-                // avoid imports entirely
-                if name_binding.is_import() { return; }
+                // the entity is accessible in the following cases:
+                // 1. if it's defined in the same crate, it's always
+                // accessible (since private entities can be made public)
+                // 2. if it's defined in another crate, it's accessible
+                // only if both the module is public and the entity is
+                // declared as public (due to pruning, we don't explore
+                // outside crate private modules => no need to check this)
+                let is_accessible =
+                    !in_module_is_extern || name_binding.vis == ty::Visibility::Public;
+
+                // imports are only interesting when they re-export something
+                if name_binding.is_import() && name_binding.vis != ty::Visibility::Public {
+                    return;
+                }
 
                 // collect results based on the filter function
                 if let Some(def) = name_binding.def() {
-                    if name == lookup_name && ns == namespace && filter_fn(def) {
+                    if name == lookup_name && ns == namespace && filter_fn(def) &&
+                       is_accessible {
                         // create the path
                         let ident = hir::Ident::from_name(name);
                         let params = PathParameters::none();
@@ -3322,19 +3565,13 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                             global: true,
                             segments: segms,
                         };
-                        // the entity is accessible in the following cases:
-                        // 1. if it's defined in the same crate, it's always
-                        // accessible (since private entities can be made public)
-                        // 2. if it's defined in another crate, it's accessible
-                        // only if both the module is public and the entity is
-                        // declared as public (due to pruning, we don't explore
-                        // outside crate private modules => no need to check this)
-                        if !in_module_is_extern || name_binding.vis == ty::Visibility::Public {
-                            lookup_results.push(path);
-                        }
+                        lookup_results.push((path, def.def_id()));
                     }
                 }
 
+                // don't explore modules through imports, they would be visited twice
+                if name_binding.is_import() { return; }
+
                 // collect submodules to explore
                 if let Some(module) = name_binding.module() {
                     // form the path
@@ -3354,7 +3591,10 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
                         _ => bug!(),
                     };
 
-                    if !in_module_is_extern || name_binding.vis == ty::Visibility::Public {
+                    if is_accessible {
+                        if name_binding.is_extern_crate() {
+                            reached_crates.insert(module.def_id().unwrap().krate);
+                        }
                         // add the module to the lookup
                         let is_extern = in_module_is_extern || name_binding.is_extern_crate();
                         worklist.push((module, path_segments, is_extern));
@@ -3363,10 +3603,7 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
             })
         }
 
-        SuggestedCandidates {
-            name: lookup_name.as_str().to_string(),
-            candidates: lookup_results,
-        }
+        lookup_results
     }
 
     fn record_def(&mut self, node_id: NodeId, resolution: PathResolution) {
@@ -3411,9 +3648,23 @@ impl<'a, 'tcx> Resolver<'a, 'tcx> {
         let vis = match self.resolve_module_path(&segments, DontUseLexicalScope, path.span) {
             Success(module) => {
                 let def = module.def.unwrap();
-                let path_resolution = PathResolution { base_def: def, depth: 0 };
-                self.def_map.borrow_mut().insert(id, path_resolution);
-                ty::Visibility::Restricted(self.ast_map.as_local_node_id(def.def_id()).unwrap())
+                let local_id = match def {
+                    Def::Mod(def_id) => self.ast_map.as_local_node_id(def_id),
+                    _ => None,
+                };
+                match local_id {
+                    Some(local_id) => {
+                        let path_resolution = PathResolution { base_def: def, depth: 0 };
+                        self.def_map.borrow_mut().insert(id, path_resolution);
+                        ty::Visibility::Restricted(local_id)
+                    }
+                    None => {
+                        // only modules of the current crate can be ancestors
+                        let msg = "visibilities can only be restricted to ancestor modules";
+                        self.session.span_err(path.span, msg);
+                        return ty::Visibility::Public;
+                    }
+                }
             }
             Failed(Some((span, msg))) => {
                 self.session.span_err(span, &format!("failed to resolve module path. {}", msg));
@@ -3553,38 +3804,41 @@ fn show_candidates(session: &mut DiagnosticBuilder,
         const MAX_CANDIDATES: usize = 5;
 
         // we want consistent results across executions, but candidates are produced
-        // by iterating through a hash map, so make sure they are ordered:
-        let mut path_strings: Vec<_> = paths.into_iter()
-                                            .map(|p| path_names_to_string(&p, 0))
-                                            .collect();
+        // by iterating through a hash map, so make sure they are ordered. The same
+        // path can also be found more than once (e.g. through a glob re-export).
+        let mut path_strings: Vec<_> = paths.iter().map(|candidate| {
+            let path = format!("`use {};`", path_names_to_string(&candidate.path, 0));
+            match candidate.extern_crate {
+                Some(name) => format!("{} (after adding `extern crate {};`)", path, name),
+                None => path,
+            }
+        }).collect();
         path_strings.sort();
+        path_strings.dedup();
 
         // behave differently based on how many candidates we have:
-        if !paths.is_empty() {
-            if paths.len() == 1 {
-                session.fileline_help(
-                    span,
-                    &format!("you can import it into scope: `use {};`.",
-                        &path_strings[0]),
-                );
-            } else {
-                session.fileline_help(span, "you can import several candidates \
-                    into scope (`use ...;`):");
-                let count = path_strings.len() as isize - MAX_CANDIDATES as isize + 1;
-
-                for (idx, path_string) in path_strings.iter().enumerate() {
-                    if idx == MAX_CANDIDATES - 1 && count > 1 {
-                        session.fileline_help(
-                            span,
-                            &format!("  and {} other candidates", count).to_string(),
-                        );
-                        break;
-                    } else {
-                        session.fileline_help(
-                            span,
-                            &format!("  `{}`", path_string).to_string(),
-                        );
-                    }
+        if path_strings.len() == 1 {
+            session.fileline_help(
+                span,
+                &format!("you can import it into scope: {}.", &path_strings[0]),
+            );
+        } else {
+            session.fileline_help(span, "you can import several candidates \
+                into scope (`use ...;`):");
+            let count = path_strings.len() as isize - MAX_CANDIDATES as isize + 1;
+
+            for (idx, path_string) in path_strings.iter().enumerate() {
+                if idx == MAX_CANDIDATES - 1 && count > 1 {
+                    session.fileline_help(
+                        span,
+                        &format!("  and {} other candidates", count).to_string(),
+                    );
+                    break;
+                } else {
+                    session.fileline_help(
+                        span,
+                        &format!("  {}", path_string).to_string(),
+                    );
                 }
             }
         }
@@ -3638,7 +3892,12 @@ pub struct CrateMap {
     pub freevars: FreevarMap,
     pub export_map: ExportMap,
     pub trait_map: TraitMap,
+    /// The traits in scope at method calls that no trait in scope has a
+    /// method for. Only used when reporting that the method wasn't found.
+    pub traits_in_scope: TraitMap,
     pub glob_map: Option<GlobMap>,
+    /// Names of the `--extern` crates that are never used.
+    pub unused_externs: Vec<String>,
 }
 
 #[derive(PartialEq,Copy, Clone)]
@@ -3667,7 +3926,7 @@ pub fn resolve_crate<'a, 'tcx>(session: &'a Session,
 
     resolver.resolve_crate(krate);
 
-    check_unused::check_crate(&mut resolver, krate);
+    let unused_externs = check_unused::check_crate(&mut resolver, krate);
     resolver.report_privacy_errors();
 
     CrateMap {
@@ -3675,11 +3934,13 @@ pub fn resolve_crate<'a, 'tcx>(session: &'a Session,
         freevars: resolver.freevars,
         export_map: resolver.export_map,
         trait_map: resolver.trait_map,
+        traits_in_scope: resolver.traits_in_scope,
         glob_map: if resolver.make_glob_map {
             Some(resolver.glob_map)
         } else {
             None
         },
+        unused_externs: unused_externs,
     }
 }
 
@@ -0,0 +1,395 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Resolution of the paths of invocations of `macro` items.
+//!
+//! The rest of name resolution runs on the HIR, once the crate has been
+//! expanded, but invocations of `macro`s have to be resolved during
+//! expansion. `DeclMacroResolver` keeps a table of the modules of the crate,
+//! built from the AST as the expander tells it about the items of the crate
+//! and the items that macros expand to, along with the modules of other
+//! crates that export `macro`s. Only as much of each module is recorded as
+//! is needed to resolve paths to `macro`s through modules and imports.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use syntax::ast::{self, Name};
+use syntax::ext::base::{MacroResolver, SyntaxExtension};
+use syntax::ext::decl_macro::import_path;
+use syntax::parse::token::keywords;
+use syntax::ptr::P;
+
+/// How many imports resolving a path may go through, to stop at cycles.
+const MAX_IMPORT_DEPTH: usize = 32;
+
+/// Where an item can be named from.
+#[derive(Clone, PartialEq)]
+enum Vis {
+    Public,
+    /// Only from the module at the path from the crate root and the modules
+    /// inside it.
+    Restricted(Vec<Name>),
+}
+
+impl Vis {
+    /// The visibility `vis` of an item of `module`.
+    fn new(vis: &ast::Visibility, module: &[Name]) -> Vis {
+        match *vis {
+            ast::Visibility::Public => Vis::Public,
+            ast::Visibility::Crate(_) => Vis::Restricted(Vec::new()),
+            // Like the paths of imports, `pub(path)` is relative to the crate
+            // root unless it starts with `self` or `super`.
+            ast::Visibility::Restricted { ref path, .. } => {
+                Vis::Restricted(import_path(path, module))
+            }
+            ast::Visibility::Inherited => Vis::Restricted(module.to_vec()),
+        }
+    }
+
+    fn is_visible_from(&self, module: &[Name]) -> bool {
+        match *self {
+            Vis::Public => true,
+            Vis::Restricted(ref scope) => module.starts_with(scope),
+        }
+    }
+}
+
+struct DeclMacro {
+    ext: Rc<SyntaxExtension>,
+    vis: Vis,
+    def: ast::MacroDef,
+}
+
+/// What the resolver knows about a module.
+#[derive(Default)]
+struct ModuleData {
+    /// The `macro` items of the module.
+    macros: HashMap<Name, DeclMacro>,
+    /// The names of the module's other items, child modules included.
+    items: HashSet<Name>,
+    /// The child modules, and whether they are `pub`.
+    children: HashMap<Name, bool>,
+    /// The names imported with `use`, with the path from the crate root
+    /// they import and the visibility of the import.
+    imports: HashMap<Name, (Vec<Name>, Vis)>,
+    /// The paths from the crate root imported with `use path::*`.
+    globs: Vec<(Vec<Name>, Vis)>,
+}
+
+/// The `MacroResolver` used to expand a crate.
+pub struct DeclMacroResolver {
+    /// The modules of the crate by their path from the crate root, along
+    /// with the modules of other crates, whose paths start with the name of
+    /// the `extern crate` item they are loaded through.
+    modules: HashMap<Vec<Name>, ModuleData>,
+}
+
+impl DeclMacroResolver {
+    pub fn new() -> DeclMacroResolver {
+        DeclMacroResolver {
+            modules: HashMap::new(),
+        }
+    }
+
+    /// Resolves a module path written in `from`, which is relative to the
+    /// crate root if `global` and relative to `from` otherwise.
+    fn resolve_module(&self, global: bool, segments: &[Name], from: &[Name], depth: usize)
+                      -> Option<Vec<Name>> {
+        let mut module = if global { Vec::new() } else { from.to_vec() };
+        for (i, &segment) in segments.iter().enumerate() {
+            if !global && i == 0 && segment == keywords::SelfValue.to_name() {
+                continue
+            }
+            if !global && segment == keywords::Super.to_name() &&
+               segments[..i].iter().all(|&s| s == keywords::SelfValue.to_name() ||
+                                             s == keywords::Super.to_name()) {
+                if module.pop().is_none() {
+                    return None
+                }
+                continue
+            }
+            module = match self.resolve_child(module, segment, depth) {
+                Some(module) => module,
+                None => return None,
+            };
+        }
+        Some(module)
+    }
+
+    /// Resolves `name`, a child module or an import of one, in `module`.
+    fn resolve_child(&self, mut module: Vec<Name>, name: Name, depth: usize)
+                     -> Option<Vec<Name>> {
+        let target = match self.modules.get(&module) {
+            Some(data) if data.children.contains_key(&name) => None,
+            Some(data) => match data.imports.get(&name) {
+                Some(&(ref target, _)) => Some(target.clone()),
+                None => return None,
+            },
+            None => return None,
+        };
+        match target {
+            None => {
+                module.push(name);
+                Some(module)
+            }
+            Some(_) if depth >= MAX_IMPORT_DEPTH => None,
+            Some(target) => self.resolve_module(true, &target, &module, depth + 1),
+        }
+    }
+
+    /// Looks up the `macro` `name` of `module`, as seen from module `from`.
+    fn resolve_in(&self, module: Vec<Name>, name: Name, from: &[Name], depth: usize)
+                  -> Result<Option<(Rc<SyntaxExtension>, Vec<Name>)>, String> {
+        let data = match self.modules.get(&module) {
+            Some(data) if depth < MAX_IMPORT_DEPTH => data,
+            _ => return Ok(None),
+        };
+        if let Some(def) = data.macros.get(&name) {
+            if !def.vis.is_visible_from(from) {
+                return Err(format!("macro `{}` is private", name));
+            }
+            return Ok(Some((def.ext.clone(), module.clone())));
+        }
+
+        // Imports are resolved from the importing module.
+        if let Some(&(ref target, ref vis)) = data.imports.get(&name) {
+            if vis.is_visible_from(from) {
+                let (&target_name, prefix) = target.split_last().unwrap();
+                return match self.resolve_module(true, prefix, &module, depth + 1) {
+                    Some(target_module) => {
+                        self.resolve_in(target_module, target_name, &module, depth + 1)
+                    }
+                    None => Ok(None),
+                };
+            }
+        }
+        for &(ref glob, ref vis) in &data.globs {
+            if !vis.is_visible_from(from) {
+                continue
+            }
+            if let Some(glob_module) = self.resolve_module(true, glob, &module, depth + 1) {
+                if let Ok(Some(found)) = self.resolve_in(glob_module, name, &module, depth + 1) {
+                    return Ok(Some(found));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether `name` in `module` names a `macro` and nothing else.
+    fn is_macro_only(&self, module: &[Name], name: Name, depth: usize) -> bool {
+        let data = match self.modules.get(module) {
+            Some(data) if depth < MAX_IMPORT_DEPTH => data,
+            _ => return false,
+        };
+        if data.items.contains(&name) {
+            return false;
+        }
+        if data.macros.contains_key(&name) {
+            return !data.imports.contains_key(&name);
+        }
+        if let Some(&(ref target, _)) = data.imports.get(&name) {
+            return self.imports_macro_only(module, target, depth + 1);
+        }
+        data.globs.iter().any(|&(ref glob, _)| {
+            match self.resolve_module(true, glob, module, depth + 1) {
+                Some(glob_module) => self.is_macro_only(&glob_module, name, depth + 1),
+                None => false,
+            }
+        })
+    }
+
+    fn imports_macro_only(&self, module: &[Name], target: &[Name], depth: usize) -> bool {
+        let (&name, prefix) = match target.split_last() {
+            Some(split) => split,
+            None => return false,
+        };
+        match self.resolve_module(true, prefix, module, depth) {
+            Some(target_module) => self.is_macro_only(&target_module, name, depth),
+            None => false,
+        }
+    }
+
+    /// Whether the module at `module` can be named from other crates.
+    fn is_reachable(&self, module: &[Name]) -> bool {
+        (0..module.len()).all(|i| {
+            self.modules.get(&module[..i]).and_then(|data| data.children.get(&module[i]))
+                        .map_or(false, |&public| public)
+        })
+    }
+}
+
+fn record_import(data: &mut ModuleData, view_path: &ast::ViewPath, module: &[Name], vis: Vis) {
+    match view_path.node {
+        ast::ViewPathSimple(ident, ref path) => {
+            data.imports.insert(ident.name, (import_path(path, module), vis));
+        }
+        ast::ViewPathGlob(ref path) => {
+            data.globs.push((import_path(path, module), vis));
+        }
+        ast::ViewPathList(ref prefix, ref items) => {
+            let prefix = import_path(prefix, module);
+            for item in items {
+                match item.node {
+                    ast::PathListItemKind::Ident { name, rename, .. } => {
+                        let mut target = prefix.clone();
+                        target.push(name.name);
+                        data.imports.insert(rename.unwrap_or(name).name, (target, vis.clone()));
+                    }
+                    ast::PathListItemKind::Mod { rename, .. } => {
+                        if let Some(&last) = prefix.last() {
+                            let name = rename.map_or(last, |rename| rename.name);
+                            data.imports.insert(name, (prefix.clone(), vis.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl MacroResolver for DeclMacroResolver {
+    fn add_items(&mut self, module: &[Name], items: &[P<ast::Item>]) {
+        let data = self.modules.entry(module.to_vec()).or_insert_with(ModuleData::default);
+        for item in items {
+            let vis = Vis::new(&item.vis, module);
+            match item.node {
+                ast::ItemKind::Mac(..) | ast::ItemKind::Impl(..) => {}
+                ast::ItemKind::Use(ref view_path) => {
+                    record_import(data, view_path, module, vis);
+                }
+                ast::ItemKind::Mod(..) => {
+                    data.items.insert(item.ident.name);
+                    data.children.insert(item.ident.name, vis == Vis::Public);
+                }
+                ast::ItemKind::ForeignMod(ref m) => {
+                    data.items.extend(m.items.iter().map(|item| item.ident.name));
+                }
+                _ => {
+                    data.items.insert(item.ident.name);
+                }
+            }
+        }
+    }
+
+    fn add_macro(&mut self, module: &[Name], def: ast::MacroDef, ext: Rc<SyntaxExtension>,
+                 vis: &ast::Visibility) -> bool {
+        if let Some(ref scope) = def.scope {
+            // The module of a `macro` of another crate, and the modules it
+            // is reached through, are only known from the macro's scope.
+            for i in 0..module.len() {
+                self.modules.entry(module[..i].to_vec())
+                            .or_insert_with(ModuleData::default)
+                            .children.entry(module[i]).or_insert(false);
+            }
+            let data = self.modules.entry(module.to_vec()).or_insert_with(ModuleData::default);
+            data.items.extend(scope.items.iter().cloned());
+            for &(name, ref target) in &scope.imports {
+                data.imports.insert(name, (target.clone(), Vis::Restricted(module.to_vec())));
+            }
+        }
+
+        let data = self.modules.entry(module.to_vec()).or_insert_with(ModuleData::default);
+        if data.macros.contains_key(&def.ident.name) {
+            return false;
+        }
+        let vis = Vis::new(vis, module);
+        data.macros.insert(def.ident.name, DeclMacro { ext: ext, vis: vis, def: def });
+        true
+    }
+
+    fn has_macros(&self) -> bool {
+        self.modules.values().any(|data| !data.macros.is_empty())
+    }
+
+    fn resolve_macro(&self, module: &[Name], path: &ast::Path)
+                     -> Result<Option<(Rc<SyntaxExtension>, Vec<Name>)>, String> {
+        let segments = path.segments.iter()
+                                    .map(|segment| segment.identifier.name)
+                                    .collect::<Vec<_>>();
+        let (&name, prefix) = segments.split_last().unwrap();
+        match self.resolve_module(path.global, prefix, module, 0) {
+            Some(found) => self.resolve_in(found, name, module, 0),
+            None => Ok(None),
+        }
+    }
+
+    fn def_site_path(&self, def_module: &[Name], path: &ast::Path)
+                     -> Option<(Vec<Name>, usize)> {
+        let first = path.segments[0].identifier.name;
+        if path.segments.len() > 1 && first == keywords::SelfValue.to_name() {
+            return Some((def_module.to_vec(), 1));
+        }
+        if path.segments.len() > 1 && first == keywords::Super.to_name() {
+            let supers = path.segments.iter()
+                             .take_while(|s| s.identifier.name == keywords::Super.to_name())
+                             .count();
+            if supers > def_module.len() || supers == path.segments.len() {
+                return None;
+            }
+            return Some((def_module[..def_module.len() - supers].to_vec(), supers));
+        }
+
+        let data = match self.modules.get(def_module) {
+            Some(data) => data,
+            None => return None,
+        };
+        if data.items.contains(&first) {
+            let mut target = def_module.to_vec();
+            target.push(first);
+            return Some((target, 1));
+        }
+        data.imports.get(&first).map(|&(ref target, _)| (target.clone(), 1))
+    }
+
+    fn is_macro_import(&self, module: &[Name], target: &[Name]) -> bool {
+        self.imports_macro_only(module, target, 0)
+    }
+
+    fn exported_macros(&self) -> Vec<ast::MacroDef> {
+        let mut exported = Vec::new();
+        for (module, data) in &self.modules {
+            if !self.is_reachable(module) {
+                continue
+            }
+            let mut items = data.items.iter().cloned().collect::<Vec<_>>();
+            items.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+            let mut imports = data.imports.iter()
+                                          .map(|(&name, &(ref target, _))| (name, target.clone()))
+                                          .collect::<Vec<_>>();
+            imports.sort_by(|a, b| a.0.as_str().cmp(&b.0.as_str()));
+            let scope = ast::MacroScope {
+                module: module.clone(),
+                items: items,
+                imports: imports,
+            };
+            for def in data.macros.values() {
+                // Macros of other crates have a scope already.
+                if def.vis == Vis::Public && def.def.scope.is_none() {
+                    exported.push(ast::MacroDef {
+                        scope: Some(scope.clone()),
+                        ..def.def.clone()
+                    });
+                }
+            }
+        }
+        // Keep the metadata the same from one compilation to the next.
+        exported.sort_by(|a, b| {
+            let module = |def: &ast::MacroDef| {
+                def.scope.as_ref().unwrap().module.iter()
+                         .map(|name| name.as_str())
+                         .collect::<Vec<_>>()
+            };
+            (module(a), a.ident.name.as_str()).cmp(&(module(b), b.ident.name.as_str()))
+        });
+        exported
+    }
+}
@@ -27,7 +27,6 @@ use rustc::hir::def::*;
 use syntax::ast::{NodeId, Name};
 use syntax::attr::AttrMetaMethods;
 use syntax::codemap::Span;
-use syntax::util::lev_distance::find_best_match_for_name;
 
 use std::cell::{Cell, RefCell};
 
@@ -151,6 +150,14 @@ impl<'a> NameResolution<'a> {
         Ok(())
     }
 
+    // Returns true if a single import that is not resolved yet may define the name.
+    pub fn has_pending_single_import(&self) -> bool {
+        match self.single_imports {
+            SingleImports::None => false,
+            _ => true,
+        }
+    }
+
     // Returns the binding for the name if it is known or None if it not known.
     fn binding(&self) -> Option<&'a NameBinding<'a>> {
         self.binding.and_then(|binding| match self.single_imports {
@@ -528,16 +535,9 @@ impl<'a, 'b:'a, 'tcx:'b> ImportResolver<'a, 'b, 'tcx> {
         match (&value_result, &type_result) {
             (&Indeterminate, _) | (_, &Indeterminate) => return Indeterminate,
             (&Failed(_), &Failed(_)) => {
-                let resolutions = target_module.resolutions.borrow();
-                let names = resolutions.iter().filter_map(|(&(ref name, _), resolution)| {
-                    if *name == source { return None; } // Never suggest the same name
-                    match *resolution.borrow() {
-                        NameResolution { binding: Some(_), .. } => Some(name),
-                        NameResolution { single_imports: SingleImports::None, .. } => None,
-                        _ => Some(name),
-                    }
-                });
-                let lev_suggestion = match find_best_match_for_name(names, &source.as_str(), None) {
+                let similar_name =
+                    self.resolver.find_similar_name_in_module(target_module, source, None);
+                let lev_suggestion = match similar_name {
                     Some(name) => format!(". Did you mean to use `{}`?", name),
                     None => "".to_owned(),
                 };
@@ -57,7 +57,8 @@ impl<'a, 'b, W: Write + 'b> Dump for CsvDumper<'a, 'b, W> {
 
         let values = make_values_str(&[
             ("name", &data.crate_name),
-            ("crate_root", &crate_root)
+            ("crate_root", &crate_root),
+            ("disambiguator", &data.disambiguator)
         ]);
 
         self.record("crate", span, values);
@@ -69,7 +70,8 @@ impl<'a, 'b, W: Write + 'b> Dump for CsvDumper<'a, 'b, W> {
             let values = make_values_str(&[
                 ("name", &c.name),
                 ("crate", &num),
-                ("file_name", &file_name)
+                ("file_name", &file_name),
+                ("disambiguator", &c.disambiguator)
             ]);
 
             self.record_raw(&format!("external_crate{}\n", values));
@@ -338,6 +340,20 @@ impl<'a, 'b, W: Write + 'b> Dump for CsvDumper<'a, 'b, W> {
         self.record("mod_ref", data.span, values);
     }
 
+    fn stable_id(&mut self, data: StableIdData) {
+        if self.dump_spans {
+            return;
+        }
+
+        let id = data.id.index.as_usize().to_string();
+        let values = make_values_str(&[
+            ("id", &id),
+            ("def_path", &data.def_path)
+        ]);
+
+        self.record_raw(&format!("def_path{}\n", values));
+    }
+
     fn struct_data(&mut self, span: Span, data: StructData) {
         if self.dump_spans {
             self.dump_span("struct", span);
@@ -23,6 +23,7 @@ use syntax::codemap::Span;
 pub struct CrateData {
     pub name: String,
     pub number: u32,
+    pub disambiguator: String,
 }
 
 /// Data for any entity in the Rust language. The actual data contained varies
@@ -83,14 +84,17 @@ pub enum Data {
 pub struct CratePreludeData {
     pub crate_name: String,
     pub crate_root: Option<String>,
+    pub disambiguator: String,
     pub external_crates: Vec<ExternalCrateData>
 }
 
-/// Data for external crates in the prelude of a crate.
+/// Data for external crates in the prelude of a crate. The name and
+/// disambiguator identify the crate across compilations, unlike its number.
 #[derive(Debug)]
 pub struct ExternalCrateData {
     pub name: String,
-    pub num: CrateNum
+    pub num: CrateNum,
+    pub disambiguator: String,
 }
 
 /// Data for enum declarations.
@@ -101,6 +105,7 @@ pub struct EnumData {
     pub qualname: String,
     pub span: Span,
     pub scope: NodeId,
+    pub docs: String,
 }
 
 /// Data for extern crates.
@@ -131,6 +136,7 @@ pub struct FunctionData {
     pub declaration: Option<DefId>,
     pub span: Span,
     pub scope: NodeId,
+    pub docs: String,
 }
 
 /// Data about a function call.
@@ -208,6 +214,7 @@ pub struct MethodData {
     pub qualname: String,
     pub span: Span,
     pub scope: NodeId,
+    pub docs: String,
 }
 
 /// Data for modules.
@@ -219,6 +226,7 @@ pub struct ModData {
     pub span: Span,
     pub scope: NodeId,
     pub filename: String,
+    pub docs: String,
 }
 
 /// Data for a reference to a module.
@@ -237,7 +245,8 @@ pub struct StructData {
     pub ctor_id: NodeId,
     pub qualname: String,
     pub scope: NodeId,
-    pub value: String
+    pub value: String,
+    pub docs: String,
 }
 
 #[derive(Debug)]
@@ -247,7 +256,8 @@ pub struct StructVariantData {
     pub qualname: String,
     pub type_value: String,
     pub value: String,
-    pub scope: NodeId
+    pub scope: NodeId,
+    pub docs: String,
 }
 
 #[derive(Debug)]
@@ -256,7 +266,8 @@ pub struct TraitData {
     pub id: NodeId,
     pub qualname: String,
     pub scope: NodeId,
-    pub value: String
+    pub value: String,
+    pub docs: String,
 }
 
 #[derive(Debug)]
@@ -267,7 +278,17 @@ pub struct TupleVariantData {
     pub qualname: String,
     pub type_value: String,
     pub value: String,
-    pub scope: NodeId
+    pub scope: NodeId,
+    pub docs: String,
+}
+
+/// The `DefPath` of a local definition. Together with the crate's name and
+/// disambiguator it identifies the definition across crates and compilations,
+/// unlike its `DefId`.
+#[derive(Debug)]
+pub struct StableIdData {
+    pub id: DefId,
+    pub def_path: String,
 }
 
 /// Data for a typedef.
@@ -277,6 +298,7 @@ pub struct TypedefData {
     pub span: Span,
     pub qualname: String,
     pub value: String,
+    pub docs: String,
 }
 
 /// Data for a reference to a type or trait.
@@ -315,6 +337,7 @@ pub struct VariableData {
     pub scope: NodeId,
     pub value: String,
     pub type_value: String,
+    pub docs: String,
 }
 
 /// Data for the use of some item (e.g., the use of a local variable, which
@@ -27,6 +27,7 @@ pub trait Dump {
     fn macro_use(&mut self, _: Span, _: MacroUseData) {}
     fn mod_data(&mut self, _: ModData) {}
     fn mod_ref(&mut self, _: Span, _: ModRefData) {}
+    fn stable_id(&mut self, _: StableIdData) {}
     fn struct_data(&mut self, _: Span, _: StructData) {}
     fn struct_variant(&mut self, _: Span, _: StructVariantData) {}
     fn trait_data(&mut self, _: Span, _: TraitData) {}
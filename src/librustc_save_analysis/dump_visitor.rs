@@ -28,7 +28,8 @@
 //! for an example).
 
 use rustc::hir::def::Def;
-use rustc::hir::def_id::DefId;
+use rustc::hir::def_id::{DefId, DefIndex};
+use rustc::middle::cstore::LOCAL_CRATE;
 use rustc::session::Session;
 use rustc::ty::{self, TyCtxt};
 
@@ -44,7 +45,7 @@ use syntax::ptr::P;
 
 use rustc::hir::lowering::lower_expr;
 
-use super::{escape, generated_code, SaveContext, PathCollector};
+use super::{docs_for_attrs, escape, generated_code, SaveContext, PathCollector};
 use super::data::*;
 use super::dump::Dump;
 use super::span_utils::SpanUtils;
@@ -121,7 +122,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
         let external_crates = self.save_ctxt.get_external_crates().into_iter().map(|c| {
             ExternalCrateData {
                 name: c.name,
-                num: c.number
+                num: c.number,
+                disambiguator: c.disambiguator,
             }
         }).collect();
 
@@ -129,10 +131,21 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
         let data = CratePreludeData {
             crate_name: name.into(),
             crate_root: crate_root,
+            disambiguator: self.tcx.crate_disambiguator(LOCAL_CRATE).to_string(),
             external_crates: external_crates
         };
 
         self.dumper.crate_prelude(krate.span, data);
+
+        // The stable ids of all local definitions, so that references from other
+        // crates can be resolved.
+        for index in 0..self.tcx.map.num_local_def_ids() {
+            let def_id = DefId::local(DefIndex::new(index));
+            self.dumper.stable_id(StableIdData {
+                id: def_id,
+                def_path: self.save_ctxt.get_def_path(def_id),
+            });
+        }
     }
 
     // Return all non-empty prefixes of a path.
@@ -369,7 +382,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                         qualname: format!("{}::{}", qualname, path_to_string(p)),
                         type_value: typ,
                         value: String::new(),
-                        scope: 0
+                        scope: 0,
+                        docs: String::new(),
                     }.normalize(&self.tcx));
                 }
             }
@@ -381,10 +395,11 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                       body: Option<&ast::Block>,
                       id: ast::NodeId,
                       name: ast::Name,
-                      span: Span) {
+                      span: Span,
+                      attrs: &[ast::Attribute]) {
         debug!("process_method: {}:{}", id, name);
 
-        if let Some(method_data) = self.save_ctxt.get_method_data(id, name, span) {
+        if let Some(method_data) = self.save_ctxt.get_method_data(id, name, span, attrs) {
 
             if body.is_some() {
                 if !self.span.filter_generated(Some(method_data.span), span) {
@@ -398,6 +413,7 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                         span: method_data.span,
                         scope: method_data.scope,
                         qualname: method_data.qualname.clone(),
+                        docs: method_data.docs.clone(),
                     }.normalize(&self.tcx));
                 }
             }
@@ -464,7 +480,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                     span: param_ss,
                     id: param.id,
                     qualname: name,
-                    value: String::new()
+                    value: String::new(),
+                    docs: String::new(),
                 }.normalize(&self.tcx));
             }
         }
@@ -515,7 +532,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                      name: ast::Name,
                      span: Span,
                      typ: &ast::Ty,
-                     expr: &ast::Expr) {
+                     expr: &ast::Expr,
+                     attrs: &[ast::Attribute]) {
         let qualname = format!("::{}", self.tcx.node_path_str(id));
 
         let sub_span = self.span.sub_span_after_keyword(span, keywords::Const);
@@ -528,7 +546,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                 qualname: qualname,
                 value: self.span.snippet(expr.span),
                 type_value: ty_to_string(&typ),
-                scope: normalize_node_id(&self.tcx, self.cur_scope) as u32
+                scope: normalize_node_id(&self.tcx, self.cur_scope) as u32,
+                docs: docs_for_attrs(attrs),
             }.normalize(&self.tcx));
         }
 
@@ -552,7 +571,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                 ctor_id: def.id(),
                 qualname: qualname.clone(),
                 scope: self.cur_scope,
-                value: val
+                value: val,
+                docs: docs_for_attrs(&item.attrs),
             }.normalize(&self.tcx));
         }
 
@@ -598,7 +618,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                             qualname: qualname,
                             type_value: enum_data.qualname.clone(),
                             value: val,
-                            scope: enum_data.scope
+                            scope: enum_data.scope,
+                            docs: docs_for_attrs(&variant.node.attrs),
                         }.normalize(&self.tcx));
                     }
                 }
@@ -612,7 +633,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                             qualname: qualname,
                             type_value: enum_data.qualname.clone(),
                             value: val,
-                            scope: enum_data.scope
+                            scope: enum_data.scope,
+                            docs: docs_for_attrs(&variant.node.attrs),
                         }.normalize(&self.tcx));
                     }
                 }
@@ -651,12 +673,27 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
             }
 
             if !self.span.filter_generated(Some(impl_data.span), item.span) {
+                let (trait_ref, self_ref) = self.impl_refs(item.id);
                 self.dumper.impl_data(item.span, ImplData {
                     id: impl_data.id,
                     span: impl_data.span,
                     scope: impl_data.scope,
-                    trait_ref: impl_data.trait_ref.map(|d| d.ref_id.unwrap()),
-                    self_ref: impl_data.self_ref.map(|d| d.ref_id.unwrap())
+                    trait_ref: trait_ref,
+                    self_ref: self_ref,
+                }.normalize(&self.tcx));
+            }
+        } else if generated_code(item.span) {
+            // Impls generated by `#[derive]` and other syntax extensions are recorded
+            // at their callsite, so that all the implementations of a trait can be found.
+            let callsite = self.sess.codemap().source_callsite(item.span);
+            if !self.span.filter_generated(Some(callsite), item.span) {
+                let (trait_ref, self_ref) = self.impl_refs(item.id);
+                self.dumper.impl_data(item.span, ImplData {
+                    id: item.id,
+                    span: callsite,
+                    scope: self.cur_scope,
+                    trait_ref: trait_ref,
+                    self_ref: self_ref,
                 }.normalize(&self.tcx));
             }
         }
@@ -669,6 +706,30 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
         }
     }
 
+    // The implemented trait and the self type of an impl, taken from the type
+    // system rather than the written paths so that aliases are resolved.
+    // References, raw pointers and boxes are looked through, so an impl for
+    // `&Foo` refers to `Foo`. Self types with no definition of their own
+    // (primitives, tuples, slices, function pointers, ...) give `None`.
+    fn impl_refs(&self, impl_id: NodeId) -> (Option<DefId>, Option<DefId>) {
+        let def_id = self.tcx.map.local_def_id(impl_id);
+        let trait_ref = self.tcx.impl_trait_ref(def_id).map(|trait_ref| trait_ref.def_id);
+        let mut self_ty = self.tcx.lookup_item_type(def_id).ty;
+        loop {
+            self_ty = match self_ty.sty {
+                ty::TyRef(_, mt) | ty::TyRawPtr(mt) => mt.ty,
+                ty::TyBox(ty) => ty,
+                _ => break,
+            };
+        }
+        let self_ref = match self_ty.sty {
+            ty::TyEnum(def, _) | ty::TyStruct(def, _) => Some(def.did),
+            ty::TyTrait(ref data) => Some(data.principal_def_id()),
+            _ => None,
+        };
+        (trait_ref, self_ref)
+    }
+
     fn process_trait(&mut self,
                      item: &ast::Item,
                      generics: &ast::Generics,
@@ -683,7 +744,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                 id: item.id,
                 qualname: qualname.clone(),
                 scope: self.cur_scope,
-                value: val
+                value: val,
+                docs: docs_for_attrs(&item.attrs),
             }.normalize(&self.tcx));
         }
 
@@ -929,7 +991,8 @@ impl<'l, 'tcx: 'l, 'll, D: Dump + 'll> DumpVisitor<'l, 'tcx, 'll, D> {
                     qualname: format!("{}${}", path_to_string(p), id),
                     value: value,
                     type_value: typ,
-                    scope: 0
+                    scope: 0,
+                    docs: String::new(),
                 }.normalize(&self.tcx));
             }
         }
@@ -1113,7 +1176,8 @@ impl<'v, 'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor<'v> for DumpVisitor<'l, 'tcx,
                         span: sub_span.expect("No span found for typedef"),
                         id: item.id,
                         qualname: qualname.clone(),
-                        value: value
+                        value: value,
+                        docs: docs_for_attrs(&item.attrs),
                     }.normalize(&self.tcx));
                 }
 
@@ -1146,14 +1210,16 @@ impl<'v, 'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor<'v> for DumpVisitor<'l, 'tcx,
                                    trait_item.ident.name,
                                    trait_item.span,
                                    &ty,
-                                   &expr);
+                                   &expr,
+                                   &trait_item.attrs);
             }
             ast::TraitItemKind::Method(ref sig, ref body) => {
                 self.process_method(sig,
                                     body.as_ref().map(|x| &**x),
                                     trait_item.id,
                                     trait_item.ident.name,
-                                    trait_item.span);
+                                    trait_item.span,
+                                    &trait_item.attrs);
             }
             ast::TraitItemKind::Const(_, None) |
             ast::TraitItemKind::Type(..) => {}
@@ -1168,14 +1234,16 @@ impl<'v, 'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor<'v> for DumpVisitor<'l, 'tcx,
                                    impl_item.ident.name,
                                    impl_item.span,
                                    &ty,
-                                   &expr);
+                                   &expr,
+                                   &impl_item.attrs);
             }
             ast::ImplItemKind::Method(ref sig, ref body) => {
                 self.process_method(sig,
                                     Some(body),
                                     impl_item.id,
                                     impl_item.ident.name,
-                                    impl_item.span);
+                                    impl_item.span,
+                                    &impl_item.attrs);
             }
             ast::ImplItemKind::Type(_) |
             ast::ImplItemKind::Macro(_) => {}
@@ -1341,7 +1409,8 @@ impl<'v, 'l, 'tcx: 'l, 'll, D: Dump +'ll> Visitor<'v> for DumpVisitor<'l, 'tcx,
                             qualname: format!("{}${}", path_to_string(p), id),
                             value: value,
                             type_value: String::new(),
-                            scope: 0
+                            scope: 0,
+                            docs: String::new(),
                         }.normalize(&self.tcx));
                     }
                 }
@@ -0,0 +1,425 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Dumps save-analysis data as a single JSON object.
+//!
+//! The top level object has the following fields:
+//!
+//! * `format_version`: the version of this schema, currently `1`. The version
+//!   is bumped whenever a field is removed or changes meaning.
+//! * `prelude`: the crate name, root file and disambiguator, and the external
+//!   crates it uses, each as `{ "name", "num", "disambiguator" }`.
+//! * `def_paths`: the `DefPath` of every local definition as `{ "id",
+//!   "def_path" }`. A definition is identified across compilations by its crate
+//!   name, crate disambiguator and def path, so a reference from another crate
+//!   can be resolved by looking up the referenced crate's number in that
+//!   crate's prelude and the id's index in the defining crate's `def_paths`.
+//! * `records`: every definition and reference, in the order they were
+//!   found. Each record has a `kind` field (e.g. `"function"`, `"fn_call"`)
+//!   and the fields of the corresponding structure in `data.rs`.
+//!
+//! Spans are objects with a `file_name`, `byte_start` and `byte_end` (byte
+//! offsets into the file), and `line_start`, `line_end`, `column_start` and
+//! `column_end` (all 1-based, columns count characters). Ids are objects with
+//! the number of the crate that defines the item (`0` is the local crate) and
+//! its `index` in that crate, so they can be cross-referenced with the output
+//! of other crates. Missing ids are `null`. Scopes are ids of the enclosing
+//! item and encode the parent/child relation between records.
+//!
+//! `impl` records, including those generated by `#[derive]`, link the impl to
+//! its `trait_ref` and the `self_ref` type, so all the implementations of a
+//! trait or for a type can be found. Methods that implement a trait item
+//! refer to it through their `declaration`.
+
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use rustc::hir::def_id::DefId;
+use rustc::middle::cstore::LOCAL_CRATE;
+use rustc::session::Session;
+use serialize::json::Json;
+use syntax::ast::NodeId;
+use syntax::codemap::{CodeMap, Span};
+
+use super::data::*;
+use super::dump::Dump;
+use super::span_utils::SpanUtils;
+
+/// The version of the JSON schema, see the module documentation.
+pub const FORMAT_VERSION: u64 = 1;
+
+pub struct JsonDumper<'b, W: Write + 'b> {
+    output: &'b mut W,
+    sess: &'b Session,
+    codemap: &'b CodeMap,
+    prelude: Option<Json>,
+    def_paths: Vec<Json>,
+    records: Vec<Json>,
+}
+
+impl<'b, W: Write> JsonDumper<'b, W> {
+    pub fn new(writer: &'b mut W, sess: &'b Session) -> JsonDumper<'b, W> {
+        JsonDumper {
+            output: writer,
+            sess: sess,
+            codemap: sess.codemap(),
+            prelude: None,
+            def_paths: vec![],
+            records: vec![],
+        }
+    }
+
+    fn record(&mut self, kind: &str, values: Vec<(&'static str, Json)>) {
+        let mut record = make_object(values);
+        record.insert("kind".to_owned(), Json::String(kind.to_owned()));
+        self.records.push(Json::Object(record));
+    }
+
+    fn span(&self, span: Span) -> Json {
+        let lo_loc = self.codemap.lookup_char_pos(span.lo);
+        let hi_loc = self.codemap.lookup_char_pos(span.hi);
+        let lo_byte = self.codemap.lookup_byte_offset(span.lo).pos;
+        let hi_byte = self.codemap.lookup_byte_offset(span.hi).pos;
+        Json::Object(make_object(vec![
+            ("file_name", Json::String(SpanUtils::make_path_string(&lo_loc.file.name))),
+            ("byte_start", Json::U64(lo_byte.0 as u64)),
+            ("byte_end", Json::U64(hi_byte.0 as u64)),
+            ("line_start", Json::U64(lo_loc.line as u64)),
+            ("line_end", Json::U64(hi_loc.line as u64)),
+            ("column_start", Json::U64(lo_loc.col.0 as u64 + 1)),
+            ("column_end", Json::U64(hi_loc.col.0 as u64 + 1)),
+        ]))
+    }
+}
+
+// The data is written once all records have been collected.
+impl<'b, W: Write> Drop for JsonDumper<'b, W> {
+    fn drop(&mut self) {
+        let analysis = make_object(vec![
+            ("format_version", Json::U64(FORMAT_VERSION)),
+            ("prelude", self.prelude.take().unwrap_or(Json::Null)),
+            ("def_paths", Json::Array(::std::mem::replace(&mut self.def_paths, vec![]))),
+            ("records", Json::Array(::std::mem::replace(&mut self.records, vec![]))),
+        ]);
+        if let Err(e) = write!(self.output, "{}", Json::Object(analysis)) {
+            self.sess.err(&format!("Error writing save-analysis output: {}", e));
+        }
+    }
+}
+
+impl<'b, W: Write + 'b> Dump for JsonDumper<'b, W> {
+    fn crate_prelude(&mut self, span: Span, data: CratePreludeData) {
+        let external_crates = data.external_crates.into_iter().map(|c| {
+            Json::Object(make_object(vec![
+                ("name", Json::String(c.name)),
+                ("num", Json::U64(c.num as u64)),
+                ("disambiguator", Json::String(c.disambiguator)),
+            ]))
+        }).collect();
+
+        self.prelude = Some(Json::Object(make_object(vec![
+            ("crate_name", Json::String(data.crate_name)),
+            ("crate_root", data.crate_root.map_or(Json::Null, Json::String)),
+            ("disambiguator", Json::String(data.disambiguator)),
+            ("external_crates", Json::Array(external_crates)),
+            ("span", self.span(span)),
+        ])));
+    }
+
+    fn enum_data(&mut self, _: Span, data: EnumData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("value", Json::String(data.value)),
+            ("qualname", Json::String(data.qualname)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("enum", values);
+    }
+
+    fn extern_crate(&mut self, _: Span, data: ExternCrateData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("name", Json::String(data.name)),
+            ("crate_num", Json::U64(data.crate_num as u64)),
+            ("location", Json::String(data.location)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+        ];
+        self.record("extern_crate", values);
+    }
+
+    fn impl_data(&mut self, _: Span, data: ImplData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("trait_ref", opt_def_id(data.trait_ref)),
+            ("self_ref", opt_def_id(data.self_ref)),
+        ];
+        self.record("impl", values);
+    }
+
+    fn inheritance(&mut self, data: InheritanceData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("base_id", def_id(data.base_id)),
+            ("deriv_id", local_id(data.deriv_id)),
+        ];
+        self.record("inheritance", values);
+    }
+
+    fn function(&mut self, _: Span, data: FunctionData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+            ("declaration", opt_def_id(data.declaration)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("function", values);
+    }
+
+    fn function_ref(&mut self, _: Span, data: FunctionRefData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", def_id(data.ref_id)),
+        ];
+        self.record("fn_ref", values);
+    }
+
+    fn function_call(&mut self, _: Span, data: FunctionCallData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", def_id(data.ref_id)),
+        ];
+        self.record("fn_call", values);
+    }
+
+    fn method(&mut self, _: Span, data: MethodData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("qualname", Json::String(data.qualname)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("method_decl", values);
+    }
+
+    fn method_call(&mut self, _: Span, data: MethodCallData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", opt_def_id(data.ref_id)),
+            ("decl_id", opt_def_id(data.decl_id)),
+        ];
+        self.record("method_call", values);
+    }
+
+    fn macro_data(&mut self, _: Span, data: MacroData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+        ];
+        self.record("macro", values);
+    }
+
+    fn macro_use(&mut self, _: Span, data: MacroUseData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+            ("callee_span", self.span(data.callee_span)),
+            ("scope", local_id(data.scope)),
+            ("imported", Json::Boolean(data.imported)),
+        ];
+        self.record("macro_use", values);
+    }
+
+    fn mod_data(&mut self, data: ModData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("filename", Json::String(data.filename)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("module", values);
+    }
+
+    fn mod_ref(&mut self, _: Span, data: ModRefData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", opt_def_id(data.ref_id)),
+            ("qualname", Json::String(data.qualname)),
+        ];
+        self.record("mod_ref", values);
+    }
+
+    fn stable_id(&mut self, data: StableIdData) {
+        self.def_paths.push(Json::Object(make_object(vec![
+            ("id", def_id(data.id)),
+            ("def_path", Json::String(data.def_path)),
+        ])));
+    }
+
+    fn struct_data(&mut self, _: Span, data: StructData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("id", local_id(data.id)),
+            ("ctor_id", local_id(data.ctor_id)),
+            ("qualname", Json::String(data.qualname)),
+            ("scope", local_id(data.scope)),
+            ("value", Json::String(data.value)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("struct", values);
+    }
+
+    fn struct_variant(&mut self, _: Span, data: StructVariantData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("id", local_id(data.id)),
+            ("qualname", Json::String(data.qualname)),
+            ("type_value", Json::String(data.type_value)),
+            ("value", Json::String(data.value)),
+            ("scope", local_id(data.scope)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("variant_struct", values);
+    }
+
+    fn trait_data(&mut self, _: Span, data: TraitData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("id", local_id(data.id)),
+            ("qualname", Json::String(data.qualname)),
+            ("scope", local_id(data.scope)),
+            ("value", Json::String(data.value)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("trait", values);
+    }
+
+    fn tuple_variant(&mut self, _: Span, data: TupleVariantData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("id", local_id(data.id)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+            ("type_value", Json::String(data.type_value)),
+            ("value", Json::String(data.value)),
+            ("scope", local_id(data.scope)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("variant", values);
+    }
+
+    fn type_ref(&mut self, _: Span, data: TypeRefData) {
+        let values = vec![
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", opt_def_id(data.ref_id)),
+            ("qualname", Json::String(data.qualname)),
+        ];
+        self.record("type_ref", values);
+    }
+
+    fn typedef(&mut self, _: Span, data: TypedefData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("span", self.span(data.span)),
+            ("qualname", Json::String(data.qualname)),
+            ("value", Json::String(data.value)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("typedef", values);
+    }
+
+    fn use_data(&mut self, _: Span, data: UseData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("span", self.span(data.span)),
+            ("name", Json::String(data.name)),
+            ("mod_id", opt_def_id(data.mod_id)),
+            ("scope", local_id(data.scope)),
+        ];
+        self.record("use_alias", values);
+    }
+
+    fn use_glob(&mut self, _: Span, data: UseGlobData) {
+        let names = data.names.into_iter().map(Json::String).collect();
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("span", self.span(data.span)),
+            ("names", Json::Array(names)),
+            ("scope", local_id(data.scope)),
+        ];
+        self.record("use_glob", values);
+    }
+
+    fn variable(&mut self, _: Span, data: VariableData) {
+        let values = vec![
+            ("id", local_id(data.id)),
+            ("name", Json::String(data.name)),
+            ("qualname", Json::String(data.qualname)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("value", Json::String(data.value)),
+            ("type_value", Json::String(data.type_value)),
+            ("docs", Json::String(data.docs)),
+        ];
+        self.record("variable", values);
+    }
+
+    fn variable_ref(&mut self, _: Span, data: VariableRefData) {
+        let values = vec![
+            ("name", Json::String(data.name)),
+            ("span", self.span(data.span)),
+            ("scope", local_id(data.scope)),
+            ("ref_id", def_id(data.ref_id)),
+        ];
+        self.record("var_ref", values);
+    }
+}
+
+fn make_object(values: Vec<(&'static str, Json)>) -> BTreeMap<String, Json> {
+    values.into_iter().map(|(k, v)| (k.to_owned(), v)).collect()
+}
+
+fn id(krate: u32, index: usize) -> Json {
+    Json::Object(make_object(vec![
+        ("krate", Json::U64(krate as u64)),
+        ("index", Json::U64(index as u64)),
+    ]))
+}
+
+// Node ids in the data have already been normalized into crate-local indices.
+fn local_id(node_id: NodeId) -> Json {
+    id(LOCAL_CRATE, node_id as usize)
+}
+
+fn def_id(def_id: DefId) -> Json {
+    id(def_id.krate, def_id.index.as_usize())
+}
+
+fn opt_def_id(opt: Option<DefId>) -> Json {
+    opt.map_or(Json::Null, def_id)
+}
@@ -26,6 +26,7 @@
 
 #[macro_use] extern crate log;
 #[macro_use] extern crate syntax;
+extern crate serialize;
 
 use rustc::hir::{self, lowering};
 use rustc::hir::map::NodeItem;
@@ -39,7 +40,9 @@ use std::fs::{self, File};
 use std::path::{Path, PathBuf};
 
 use syntax::ast::{self, NodeId, PatKind};
+use syntax::attr::AttrMetaMethods;
 use syntax::codemap::*;
+use syntax::parse::lexer::comments::strip_doc_comment_decoration;
 use syntax::parse::token::{self, keywords};
 use syntax::visit::{self, Visitor};
 use syntax::print::pprust::ty_to_string;
@@ -48,10 +51,12 @@ mod csv_dumper;
 mod data;
 mod dump;
 mod dump_visitor;
+mod json_dumper;
 #[macro_use]
 pub mod span_utils;
 
 pub use self::csv_dumper::CsvDumper;
+pub use self::json_dumper::JsonDumper;
 pub use self::data::*;
 pub use self::dump::Dump;
 pub use self::dump_visitor::DumpVisitor;
@@ -107,12 +112,24 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
             result.push(CrateData {
                 name: (&self.tcx.sess.cstore.crate_name(n)[..]).to_owned(),
                 number: n,
+                disambiguator: self.tcx.crate_disambiguator(n).to_string(),
             });
         }
 
         result
     }
 
+    /// The `DefPath` of `def_id` within its crate, e.g. `foo::{{impl}}[1]::bar`.
+    pub fn get_def_path(&self, def_id: DefId) -> String {
+        self.tcx.def_path(def_id).data.iter().map(|component| {
+            if component.disambiguator == 0 {
+                component.data.to_string()
+            } else {
+                format!("{}[{}]", component.data.to_string(), component.disambiguator)
+            }
+        }).collect::<Vec<_>>().join("::")
+    }
+
     pub fn get_item_data(&self, item: &ast::Item) -> Option<Data> {
         match item.node {
             ast::ItemKind::Fn(..) => {
@@ -127,6 +144,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     declaration: None,
                     span: sub_span.unwrap(),
                     scope: self.enclosing_scope(item.id),
+                    docs: docs_for_attrs(&item.attrs),
                 }))
             }
             ast::ItemKind::Static(ref typ, mt, ref expr) => {
@@ -150,6 +168,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     scope: self.enclosing_scope(item.id),
                     value: value,
                     type_value: ty_to_string(&typ),
+                    docs: docs_for_attrs(&item.attrs),
                 }))
             }
             ast::ItemKind::Const(ref typ, ref expr) => {
@@ -164,6 +183,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     scope: self.enclosing_scope(item.id),
                     value: self.span_utils.snippet(expr.span),
                     type_value: ty_to_string(&typ),
+                    docs: docs_for_attrs(&item.attrs),
                 }))
             }
             ast::ItemKind::Mod(ref m) => {
@@ -181,6 +201,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     span: sub_span.unwrap(),
                     scope: self.enclosing_scope(item.id),
                     filename: filename,
+                    docs: docs_for_attrs(&item.attrs),
                 }))
             }
             ast::ItemKind::Enum(..) => {
@@ -194,6 +215,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                     span: sub_span.unwrap(),
                     qualname: enum_name,
                     scope: self.enclosing_scope(item.id),
+                    docs: docs_for_attrs(&item.attrs),
                 }))
             }
             ast::ItemKind::Impl(_, _, _, ref trait_ref, ref typ, _) => {
@@ -257,6 +279,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
                 scope: scope,
                 value: "".to_owned(),
                 type_value: typ,
+                docs: docs_for_attrs(&field.attrs),
             })
         } else {
             None
@@ -266,7 +289,8 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
     // FIXME would be nice to take a MethodItem here, but the ast provides both
     // trait and impl flavours, so the caller must do the disassembly.
     pub fn get_method_data(&self, id: ast::NodeId,
-                           name: ast::Name, span: Span) -> Option<FunctionData> {
+                           name: ast::Name, span: Span,
+                           attrs: &[ast::Attribute]) -> Option<FunctionData> {
         // The qualname for a method is the trait name or name of the struct in an impl in
         // which the method is declared in, followed by the method's name.
         let qualname = match self.tcx.impl_of_method(self.tcx.map.local_def_id(id)) {
@@ -346,6 +370,7 @@ impl<'l, 'tcx: 'l> SaveContext<'l, 'tcx> {
             declaration: decl_id,
             span: sub_span.unwrap(),
             scope: self.enclosing_scope(id),
+            docs: docs_for_attrs(attrs),
         })
     }
 
@@ -677,12 +702,29 @@ impl<'v> Visitor<'v> for PathCollector {
     }
 }
 
+/// The format save-analysis data is written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+impl Format {
+    fn extension(&self) -> &'static str {
+        match *self {
+            Format::Csv => ".csv",
+            Format::Json => ".json",
+        }
+    }
+}
+
 pub fn process_crate<'l, 'tcx>(tcx: &'l TyCtxt<'tcx>,
                                lcx: &'l lowering::LoweringContext<'l>,
                                krate: &ast::Crate,
                                analysis: &'l ty::CrateAnalysis<'l>,
                                cratename: &str,
-                               odir: Option<&Path>) {
+                               odir: Option<&Path>,
+                               format: Format) {
     let _ignore = tcx.dep_graph.in_ignore();
 
     assert!(analysis.glob_map.is_some());
@@ -718,7 +760,7 @@ pub fn process_crate<'l, 'tcx>(tcx: &'l TyCtxt<'tcx>,
     };
     out_name.push_str(&cratename);
     out_name.push_str(&tcx.sess.opts.cg.extra_filename);
-    out_name.push_str(".csv");
+    out_name.push_str(format.extension());
     root_path.push(&out_name);
     let mut output_file = File::create(&root_path).unwrap_or_else(|e| {
         let disp = root_path.display();
@@ -726,18 +768,50 @@ pub fn process_crate<'l, 'tcx>(tcx: &'l TyCtxt<'tcx>,
     });
     root_path.pop();
 
-    let utils: SpanUtils<'tcx> = SpanUtils::new(&tcx.sess);
     let save_ctxt = SaveContext::new(tcx, lcx);
-    let mut dumper = CsvDumper::new(&mut output_file, utils);
-    let mut visitor = DumpVisitor::new(tcx, save_ctxt, analysis, &mut dumper);
-    // FIXME: we don't write anything!
 
-    visitor.dump_crate_info(cratename, krate);
-    visit::walk_crate(&mut visitor, krate);
+    macro_rules! dump {
+        ($new_dumper: expr) => {{
+            let mut dumper = $new_dumper;
+            let mut visitor = DumpVisitor::new(tcx, save_ctxt, analysis, &mut dumper);
+
+            visitor.dump_crate_info(cratename, krate);
+            visit::walk_crate(&mut visitor, krate);
+        }}
+    }
+
+    match format {
+        Format::Csv => {
+            let utils: SpanUtils<'tcx> = SpanUtils::new(&tcx.sess);
+            dump!(CsvDumper::new(&mut output_file, utils))
+        }
+        Format::Json => dump!(JsonDumper::new(&mut output_file, &tcx.sess)),
+    }
 }
 
 // Utility functions for the module.
 
+// Concatenates the doc comments (and `#[doc]` attributes) of an item, one
+// line per attribute, with the comment markers removed.
+fn docs_for_attrs(attrs: &[ast::Attribute]) -> String {
+    let mut result = String::new();
+
+    for attr in attrs {
+        if attr.check_name("doc") {
+            if let Some(val) = attr.value_str() {
+                if attr.node.is_sugared_doc {
+                    result.push_str(&strip_doc_comment_decoration(&val));
+                } else {
+                    result.push_str(&val);
+                }
+                result.push('\n');
+            }
+        }
+    }
+
+    result
+}
+
 // Helper function to escape quotes in a string
 fn escape(s: String) -> String {
     s.replace("\"", "\"\"")
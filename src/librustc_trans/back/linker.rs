@@ -19,8 +19,7 @@ use back::archive;
 use middle::cstore::CrateStore;
 use middle::dependency_format::Linkage;
 use session::Session;
-use session::config::CrateTypeDylib;
-use session::config;
+use session::config::{self, CrateType};
 use syntax::ast;
 use CrateTranslation;
 
@@ -54,8 +53,11 @@ pub trait Linker {
     fn hint_dynamic(&mut self);
     fn whole_archives(&mut self);
     fn no_whole_archives(&mut self);
-    fn export_symbols(&mut self, sess: &Session, trans: &CrateTranslation,
-                      tmpdir: &Path);
+    fn export_symbols(&mut self,
+                      sess: &Session,
+                      trans: &CrateTranslation,
+                      tmpdir: &Path,
+                      crate_type: CrateType);
 }
 
 pub struct GnuLinker<'a> {
@@ -199,7 +201,11 @@ impl<'a> Linker for GnuLinker<'a> {
         self.cmd.arg("-Wl,-Bdynamic");
     }
 
-    fn export_symbols(&mut self, _: &Session, _: &CrateTranslation, _: &Path) {
+    fn export_symbols(&mut self,
+                      _: &Session,
+                      _: &CrateTranslation,
+                      _: &Path,
+                      _: CrateType) {
         // noop, visibility in object files takes care of this
     }
 }
@@ -323,8 +329,11 @@ impl<'a> Linker for MsvcLinker<'a> {
     // crates. Upstream rlibs may be linked statically to this dynamic library,
     // in which case they may continue to transitively be used and hence need
     // their symbols exported.
-    fn export_symbols(&mut self, sess: &Session, trans: &CrateTranslation,
-                      tmpdir: &Path) {
+    fn export_symbols(&mut self,
+                      sess: &Session,
+                      trans: &CrateTranslation,
+                      tmpdir: &Path,
+                      crate_type: CrateType) {
         let path = tmpdir.join("lib.def");
         let res = (|| -> io::Result<()> {
             let mut f = BufWriter::new(File::create(&path)?);
@@ -344,7 +353,7 @@ impl<'a> Linker for MsvcLinker<'a> {
             // their reachable symbols and emit them as well.
             let cstore = &sess.cstore;
             let formats = sess.dependency_formats.borrow();
-            let symbols = formats[&CrateTypeDylib].iter();
+            let symbols = formats[&crate_type].iter();
             let symbols = symbols.enumerate().filter_map(|(i, f)| {
                 if *f == Linkage::Static {
                     Some((i + 1) as ast::CrateNum)
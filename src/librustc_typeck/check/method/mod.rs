@@ -49,11 +49,13 @@ pub enum MethodError<'tcx> {
 }
 
 // Contains a list of static methods that may apply, a list of unsatisfied trait predicates which
-// could lead to matches if satisfied, and a list of not-in-scope traits which may work.
+// could lead to matches if satisfied, a list of not-in-scope traits which may work, and an
+// applicable item with a similar name, in case the name was misspelled.
 pub struct NoMatchData<'tcx> {
     pub static_candidates: Vec<CandidateSource>,
     pub unsatisfied_predicates: Vec<TraitRef<'tcx>>,
     pub out_of_scope_traits: Vec<DefId>,
+    pub lev_candidate: Option<ty::ImplOrTraitItem<'tcx>>,
     pub mode: probe::Mode
 }
 
@@ -61,11 +63,13 @@ impl<'tcx> NoMatchData<'tcx> {
     pub fn new(static_candidates: Vec<CandidateSource>,
                unsatisfied_predicates: Vec<TraitRef<'tcx>>,
                out_of_scope_traits: Vec<DefId>,
+               lev_candidate: Option<ty::ImplOrTraitItem<'tcx>>,
                mode: probe::Mode) -> Self {
         NoMatchData {
             static_candidates: static_candidates,
             unsatisfied_predicates: unsatisfied_predicates,
             out_of_scope_traits: out_of_scope_traits,
+            lev_candidate: lev_candidate,
             mode: mode
         }
     }
@@ -24,7 +24,9 @@ use rustc::ty::{self, NoPreference, Ty, TyCtxt, ToPolyTraitRef, TraitRef, TypeFo
 use rustc::infer::{self, InferCtxt, InferOk, TypeOrigin};
 use syntax::ast;
 use syntax::codemap::{Span, DUMMY_SP};
+use syntax::util::lev_distance::lev_distance;
 use rustc::hir;
+use std::cmp;
 use std::collections::HashSet;
 use std::mem;
 use std::rc::Rc;
@@ -37,6 +39,7 @@ struct ProbeContext<'a, 'tcx:'a> {
     span: Span,
     mode: Mode,
     item_name: ast::Name,
+    scope_expr_id: ast::NodeId,
     steps: Rc<Vec<CandidateStep<'tcx>>>,
     opt_simplified_steps: Option<Vec<ty::fast_reject::SimplifiedType>>,
     inherent_candidates: Vec<Candidate<'tcx>>,
@@ -150,7 +153,7 @@ pub fn probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         match create_steps(fcx, span, self_ty) {
             Some(steps) => steps,
             None =>return Err(MethodError::NoMatch(NoMatchData::new(Vec::new(), Vec::new(),
-                                                                    Vec::new(), mode))),
+                                                                    Vec::new(), None, mode))),
         }
     } else {
         vec![CandidateStep {
@@ -186,7 +189,8 @@ pub fn probe<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                              span,
                                              mode,
                                              item_name,
-                                             steps,
+                                             scope_expr_id,
+                                             Rc::new(steps),
                                              opt_simplified_steps);
         probe_cx.assemble_inherent_candidates();
         probe_cx.assemble_extension_candidates_for_traits_in_scope(scope_expr_id)?;
@@ -235,7 +239,8 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
            span: Span,
            mode: Mode,
            item_name: ast::Name,
-           steps: Vec<CandidateStep<'tcx>>,
+           scope_expr_id: ast::NodeId,
+           steps: Rc<Vec<CandidateStep<'tcx>>>,
            opt_simplified_steps: Option<Vec<ty::fast_reject::SimplifiedType>>)
            -> ProbeContext<'a,'tcx>
     {
@@ -244,10 +249,11 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
             span: span,
             mode: mode,
             item_name: item_name,
+            scope_expr_id: scope_expr_id,
             inherent_candidates: Vec::new(),
             extension_candidates: Vec::new(),
             impl_dups: HashSet::new(),
-            steps: Rc::new(steps),
+            steps: steps,
             opt_simplified_steps: opt_simplified_steps,
             static_candidates: Vec::new(),
             private_candidate: None,
@@ -859,6 +865,7 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
         let static_candidates = mem::replace(&mut self.static_candidates, vec![]);
         let private_candidate = mem::replace(&mut self.private_candidate, None);
         let unsatisfied_predicates = mem::replace(&mut self.unsatisfied_predicates, vec![]);
+        let inherent_impls = mem::replace(&mut self.impl_dups, HashSet::new());
 
         // things failed, so lets look at all traits, for diagnostic purposes now:
         self.reset();
@@ -898,8 +905,67 @@ impl<'a,'tcx> ProbeContext<'a,'tcx> {
             return Err(MethodError::PrivateMatch(def));
         }
 
+        // If nothing else explains the error, the name may just be misspelled.
+        let lev_candidate = if static_candidates.is_empty() &&
+                               unsatisfied_predicates.is_empty() &&
+                               out_of_scope_traits.is_empty() {
+            self.probe_for_similar_candidate(&inherent_impls)
+        } else {
+            None
+        };
+
         Err(MethodError::NoMatch(NoMatchData::new(static_candidates, unsatisfied_predicates,
-                                                  out_of_scope_traits, self.mode)))
+                                                  out_of_scope_traits, lev_candidate,
+                                                  self.mode)))
+    }
+
+    /// Looks for an applicable item whose name is similar to the one that was
+    /// not found. The candidates are the items of the inherent impls that were
+    /// searched and of the traits in scope.
+    fn probe_for_similar_candidate(&self, inherent_impls: &HashSet<DefId>)
+                                   -> Option<ty::ImplOrTraitItem<'tcx>> {
+        let tcx = self.tcx();
+        let traits_in_scope = self.fcx.ccx.traits_in_scope.get(&self.scope_expr_id)
+                                                          .map_or(&[][..], |traits| &traits[..]);
+        let mut names = HashSet::new();
+        for impl_def_id in inherent_impls {
+            let item_ids = tcx.impl_items.borrow().get(impl_def_id).unwrap().clone();
+            for item_id in item_ids {
+                names.insert(tcx.impl_or_trait_item(item_id.def_id()).name());
+            }
+        }
+        for &trait_def_id in traits_in_scope {
+            names.extend(tcx.trait_items(trait_def_id).iter().map(|item| item.name()));
+        }
+
+        let item_name = self.item_name.as_str();
+        let max_dist = cmp::max(item_name.len(), 3) / 3;
+        names.into_iter().filter_map(|name| {
+            let dist = lev_distance(&item_name, &name.as_str());
+            if name == self.item_name || dist > max_dist {
+                return None;
+            }
+
+            // Only suggest items that would actually be picked.
+            let mut probe_cx = ProbeContext::new(self.fcx,
+                                                 self.span,
+                                                 self.mode,
+                                                 name,
+                                                 self.scope_expr_id,
+                                                 self.steps.clone(),
+                                                 self.opt_simplified_steps.clone());
+            probe_cx.assemble_inherent_candidates();
+            for &trait_def_id in traits_in_scope {
+                if probe_cx.assemble_extension_candidates_for_trait(trait_def_id).is_err() {
+                    return None;
+                }
+            }
+            match probe_cx.pick_core() {
+                Some(Ok(pick)) => Some((dist, pick.item)),
+                _ => None,
+            }
+        }).min_by_key(|&(dist, ref item)| (dist, item.name().as_str()))
+          .map(|(_, item)| item)
     }
 
     fn pick_core(&mut self) -> Option<PickResult<'tcx>> {
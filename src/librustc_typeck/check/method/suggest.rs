@@ -105,6 +105,7 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         MethodError::NoMatch(NoMatchData { static_candidates: static_sources,
                                            unsatisfied_predicates,
                                            out_of_scope_traits,
+                                           lev_candidate,
                                            mode, .. }) => {
             let cx = fcx.tcx();
 
@@ -195,6 +196,14 @@ pub fn report_error<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                              bound_list));
             }
 
+            if let Some(lev_candidate) = lev_candidate {
+                err.fileline_help(span,
+                                  &format!("there is {} with a similar name: `{}`",
+                                           if mode == Mode::MethodCall { "a method" }
+                                           else { "an associated item" },
+                                           cx.item_path_str(lev_candidate.def_id())));
+            }
+
             suggest_traits_to_import(fcx, &mut err, span, rcvr_ty, item_name,
                                      rcvr_expr, out_of_scope_traits);
             err.emit();
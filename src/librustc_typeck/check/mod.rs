@@ -85,7 +85,6 @@ use check::_match::pat_ctxt;
 use dep_graph::DepNode;
 use fmt_macros::{Parser, Piece, Position};
 use middle::astconv_util::prohibit_type_params;
-use middle::cstore::LOCAL_CRATE;
 use hir::def::{self, Def};
 use hir::def_id::DefId;
 use rustc::infer::{self, InferOk, TypeOrigin, TypeTrace, type_variable};
@@ -95,7 +94,7 @@ use rustc::traits::{self, report_fulfillment_errors, ProjectionMode};
 use rustc::ty::{GenericPredicates, TypeScheme};
 use rustc::ty::{ParamTy, ParameterEnvironment};
 use rustc::ty::{LvaluePreference, NoPreference, PreferMutLvalue};
-use rustc::ty::{self, ToPolyTraitRef, Ty, TyCtxt, Visibility};
+use rustc::ty::{self, ToPolyTraitRef, Ty, TyCtxt};
 use rustc::ty::{MethodCall, MethodCallee};
 use rustc::ty::adjustment;
 use rustc::ty::error::TypeError;
@@ -1837,6 +1836,13 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
 
             debug!("select_all_obligations_and_apply_defaults: defaults={:?}", default_map);
 
+            // Variables that have been equated share a root, and each of
+            // them may carry its own default. Check that those defaults
+            // agree before applying any of them, so that the error can
+            // name every source involved rather than whichever one
+            // happens to be applied second.
+            self.report_conflicting_defaults_for_same_variable(&default_map);
+
             // We loop over the unsolved variables, resolving them and if they are
             // and unconstrainted numeric type we add them to the set of unbound
             // variables. We do this so we only apply literal fallback to type
@@ -1943,12 +1949,19 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 // a unification failure and then report an error for each.
                 for (conflict, default) in conflicts {
                     let conflicting_default =
-                        self.find_conflicting_default(&unbound_tyvars, &default_map, conflict)
-                            .unwrap_or(type_variable::Default {
-                                ty: self.infcx().next_ty_var(),
-                                origin_span: codemap::DUMMY_SP,
-                                def_id: self.tcx().map.local_def_id(0) // what do I put here?
-                            });
+                        self.find_conflicting_default(&unbound_tyvars, &default_map, conflict);
+
+                    let conflicting_default = match conflicting_default {
+                        Some(conflicting_default) => conflicting_default,
+                        None => {
+                            // No other default is to blame, so the default
+                            // itself is incompatible with how the variable
+                            // is used, e.g. a non-integral default for an
+                            // integer literal.
+                            self.report_inapplicable_default(conflict, default);
+                            continue;
+                        }
+                    };
 
                     // This is to ensure that we elimnate any non-determinism from the error
                     // reporting by fixing an order, it doesn't matter what order we choose
@@ -1989,42 +2002,127 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         unbound_tyvars.push(conflict);
         unbound_tyvars.extend(unbound_vars.iter());
 
-        let mut result = None;
         // We run the same code as above applying defaults in order, this time when
         // we find the conflict we just return it for error reporting above.
 
-        // We also run this inside snapshot that never commits so we can do error
-        // reporting for more then one conflict.
-        for ty in &unbound_tyvars {
-            if self.infcx().type_var_diverges(ty) {
-                demand::eqtype(self, codemap::DUMMY_SP, *ty, self.tcx().mk_nil());
-            } else {
-                match self.infcx().type_is_unconstrained_numeric(ty) {
-                    UnconstrainedInt => {
-                        demand::eqtype(self, codemap::DUMMY_SP, *ty, self.tcx().types.i32)
-                    },
-                    UnconstrainedFloat => {
-                        demand::eqtype(self, codemap::DUMMY_SP, *ty, self.tcx().types.f64)
-                    },
-                    Neither => {
-                        if let Some(default) = default_map.get(ty) {
-                            let default = default.clone();
-                            match infer::mk_eqty(self.infcx(), false,
-                                                 TypeOrigin::Misc(default.origin_span),
-                                                 ty, default.ty) {
-                                // FIXME(#32730) propagate obligations
-                                Ok(InferOk { obligations, .. }) => assert!(obligations.is_empty()),
-                                Err(_) => {
-                                    result = Some(default);
+        // We also run this inside a probe that never commits, so we can do error
+        // reporting for more than one conflict.
+        self.infcx().probe(|_| {
+            for ty in &unbound_tyvars {
+                let fallback = if self.infcx().type_var_diverges(ty) {
+                    self.tcx().mk_nil()
+                } else {
+                    match self.infcx().type_is_unconstrained_numeric(ty) {
+                        UnconstrainedInt => self.tcx().types.i32,
+                        UnconstrainedFloat => self.tcx().types.f64,
+                        Neither => {
+                            if let Some(default) = default_map.get(ty) {
+                                let default = default.clone();
+                                match infer::mk_eqty(self.infcx(), false,
+                                                     TypeOrigin::Misc(default.origin_span),
+                                                     ty, default.ty) {
+                                    // FIXME(#32730) propagate obligations
+                                    Ok(InferOk { obligations, .. }) => {
+                                        assert!(obligations.is_empty())
+                                    }
+                                    Err(_) => {
+                                        // If the default we started with cannot be
+                                        // applied on its own, no other default is to
+                                        // blame.
+                                        if *ty == conflict {
+                                            return None;
+                                        }
+                                        return Some(default);
+                                    }
                                 }
                             }
+                            continue;
                         }
                     }
+                };
+                let _ = infer::mk_eqty(self.infcx(), false,
+                                       TypeOrigin::Misc(codemap::DUMMY_SP),
+                                       ty, fallback);
+            }
+
+            None
+        })
+    }
+
+    /// Reports every pair of defaults that apply to the same (equated)
+    /// type variable but name different types, and resolves such
+    /// variables to the error type so that no further errors are
+    /// reported for them.
+    fn report_conflicting_defaults_for_same_variable(
+        &self,
+        default_map: &FnvHashMap<&Ty<'tcx>, type_variable::Default<'tcx>>)
+    {
+        let mut defaults_by_root: FnvHashMap<Ty<'tcx>, Vec<type_variable::Default<'tcx>>> =
+            FnvHashMap();
+        let mut roots = vec![];
+        for (ty, default) in default_map {
+            let root = self.infcx().resolve_type_vars_if_possible(*ty);
+            if let ty::TyInfer(ty::TyVar(_)) = root.sty {
+                let defaults = defaults_by_root.entry(root).or_insert_with(|| {
+                    roots.push(root);
+                    vec![]
+                });
+                defaults.push(default.clone());
+            }
+        }
+
+        for root in roots {
+            let mut defaults = defaults_by_root.remove(&root).unwrap();
+            // Sort so that the errors do not depend on hash map order.
+            defaults.sort_by(|a, b| a.def_id.cmp(&b.def_id));
+
+            let first = defaults[0].clone();
+            let mut conflicted = false;
+            for default in &defaults[1..] {
+                let compatible = self.infcx().probe(|_| {
+                    infer::mk_eqty(self.infcx(), false,
+                                   TypeOrigin::Misc(default.origin_span),
+                                   first.ty, default.ty).is_ok()
+                });
+                if !compatible {
+                    self.infcx().report_conflicting_default_types(first.origin_span,
+                                                                  first.clone(),
+                                                                  default.clone());
+                    conflicted = true;
                 }
             }
+
+            if conflicted {
+                demand::eqtype(self, codemap::DUMMY_SP, root, self.tcx().types.err);
+            }
         }
+    }
 
-        return result;
+    /// Reports that `default` could not be applied to the type variable
+    /// `ty` because of how `ty` has been constrained, as opposed to a
+    /// conflict with some other default.
+    fn report_inapplicable_default(&self, ty: Ty<'tcx>, default: type_variable::Default<'tcx>) {
+        let resolved = self.infcx().resolve_type_vars_if_possible(&ty);
+        let found = match resolved.sty {
+            ty::TyInfer(ty::IntVar(_)) => "an integer literal".to_string(),
+            ty::TyInfer(ty::FloatVar(_)) => "a floating-point literal".to_string(),
+            _ => format!("`{}`", resolved),
+        };
+        let mut err = struct_span_err!(self.tcx().sess, default.origin_span, E0525,
+                                       "the type parameter default `{}` cannot be applied \
+                                        to a type variable inferred to be {}",
+                                       default.ty, found);
+        match self.tcx().map.as_local_node_id(default.def_id)
+                            .and_then(|node_id| self.tcx().map.opt_span(node_id)) {
+            Some(span) => {
+                err.span_note(span, "the default was defined here");
+            }
+            None => {
+                err.note(&format!("the default is defined on `{}`",
+                                  self.tcx().item_path_str(default.def_id)));
+            }
+        }
+        err.emit();
     }
 
     fn select_all_obligations_or_error(&self) {
@@ -2957,6 +3055,7 @@ fn check_expr_with_expectation_and_lvalue_pref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
         check_expr_with_lvalue_pref(fcx, base, lvalue_pref);
         let expr_t = structurally_resolved_type(fcx, expr.span, fcx.expr_ty(base));
         let mut private_candidate = None;
+        let mut first_struct = None;
         let (_, autoderefs, field_ty) = autoderef(fcx,
                                                   expr.span,
                                                   expr_t,
@@ -2966,6 +3065,7 @@ fn check_expr_with_expectation_and_lvalue_pref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                                                   |base_t, _| {
                 if let ty::TyStruct(base_def, substs) = base_t.sty {
                     debug!("struct named {:?}",  base_t);
+                    first_struct = first_struct.or(Some(base_def));
                     if let Some(field) = base_def.struct_variant().find_field_named(field.node) {
                         let field_ty = fcx.field_ty(expr.span, field, substs);
                         if field.vis.is_accessible_from(fcx.body_id, &fcx.tcx().map) {
@@ -3015,8 +3115,8 @@ fn check_expr_with_expectation_and_lvalue_pref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
                             actual)
                 },
                 expr_t, None);
-            if let ty::TyStruct(def, _) = expr_t.sty {
-                suggest_field_names(&mut err, def.struct_variant(), field, vec![]);
+            if let Some(def) = first_struct {
+                suggest_field_names(fcx, &mut err, def.struct_variant(), field, vec![]);
             }
             err.emit();
             fcx.write_error(expr.id);
@@ -3024,17 +3124,18 @@ fn check_expr_with_expectation_and_lvalue_pref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
     }
 
     // displays hints about the closest matches in field names
-    fn suggest_field_names<'tcx>(err: &mut DiagnosticBuilder,
-                                 variant: ty::VariantDef<'tcx>,
-                                 field: &Spanned<ast::Name>,
-                                 skip : Vec<InternedString>) {
+    fn suggest_field_names<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
+                                     err: &mut DiagnosticBuilder,
+                                     variant: ty::VariantDef<'tcx>,
+                                     field: &Spanned<ast::Name>,
+                                     skip : Vec<InternedString>) {
         let name = field.node.as_str();
         let names = variant.fields
                     .iter()
                     .filter_map(|ref field| {
-                        // ignore already set fields and private fields from non-local crates
+                        // ignore already set fields and fields that are not accessible here
                         if skip.iter().any(|x| *x == field.name.as_str()) ||
-                           (variant.did.krate != LOCAL_CRATE && field.vis != Visibility::Public) {
+                           !field.vis.is_accessible_from(fcx.body_id, &fcx.tcx().map) {
                                None
                         } else {
                             Some(&field.name)
@@ -3142,7 +3243,7 @@ fn check_expr_with_expectation_and_lvalue_pref<'a, 'tcx>(fcx: &FnCtxt<'a, 'tcx>,
             None);
         // prevent all specified fields from being suggested
         let skip_fields = skip_fields.iter().map(|ref x| x.name.node.as_str());
-        suggest_field_names(&mut err, variant, &field.name, skip_fields.collect());
+        suggest_field_names(fcx, &mut err, variant, &field.name, skip_fields.collect());
         err.emit();
     }
 
@@ -137,6 +137,9 @@ pub struct TypeAndSubsts<'tcx> {
 pub struct CrateCtxt<'a, 'tcx: 'a> {
     // A mapping from method call sites to traits that have that method.
     pub trait_map: hir::TraitMap,
+    /// The traits in scope at method calls that no trait in scope has a
+    /// method for. Only used to suggest similarly named methods.
+    pub traits_in_scope: hir::TraitMap,
     /// A vector of every trait accessible in the whole crate
     /// (i.e. including those from subcrates). This is used only for
     /// error reporting, and so is lazily initialised and generally
@@ -329,10 +332,13 @@ fn check_for_entry_fn(ccx: &CrateCtxt) {
     }
 }
 
-pub fn check_crate(tcx: &TyCtxt, trait_map: hir::TraitMap) -> CompileResult {
+pub fn check_crate(tcx: &TyCtxt,
+                   trait_map: hir::TraitMap,
+                   traits_in_scope: hir::TraitMap) -> CompileResult {
     let time_passes = tcx.sess.time_passes();
     let ccx = CrateCtxt {
         trait_map: trait_map,
+        traits_in_scope: traits_in_scope,
         all_traits: RefCell::new(None),
         tcx: tcx
     };
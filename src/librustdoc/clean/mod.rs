@@ -1802,12 +1802,23 @@ impl<'tcx> Clean<Item> for ty::FieldDefData<'tcx, 'static> {
 #[derive(Clone, PartialEq, Eq, RustcDecodable, RustcEncodable, Debug)]
 pub enum Visibility {
     Public,
+    /// `pub(crate)`
+    Crate,
+    /// `pub(path)`, holding the path the item is restricted to
+    Restricted(String),
     Inherited,
 }
 
 impl Clean<Option<Visibility>> for hir::Visibility {
     fn clean(&self, _: &DocContext) -> Option<Visibility> {
-        Some(if *self == hir::Visibility::Public { Public } else { Inherited })
+        Some(match *self {
+            hir::Public => Public,
+            hir::Visibility::Crate => Visibility::Crate,
+            hir::Visibility::Restricted { ref path, .. } => {
+                Visibility::Restricted(path.to_string())
+            }
+            hir::Inherited => Inherited,
+        })
     }
 }
 
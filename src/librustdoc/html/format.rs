@@ -658,6 +658,11 @@ impl<'a> fmt::Display for VisSpace<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self.get() {
             Some(clean::Public) => write!(f, "pub "),
+            Some(clean::Visibility::Crate) => write!(f, "pub(crate) "),
+            Some(clean::Visibility::Restricted(ref path)) if path == "self" || path == "super" => {
+                write!(f, "pub({}) ", path)
+            }
+            Some(clean::Visibility::Restricted(ref path)) => write!(f, "pub(in {}) ", path),
             Some(clean::Inherited) | None => Ok(())
         }
     }
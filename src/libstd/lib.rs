@@ -254,7 +254,7 @@
 #![feature(shared)]
 #![feature(slice_bytes)]
 #![feature(slice_concat_ext)]
-#![feature(slice_patterns)]
+#![cfg_attr(stage0, feature(slice_patterns))]
 #![feature(staged_api)]
 #![feature(stmt_expr_attributes)]
 #![feature(str_char)]
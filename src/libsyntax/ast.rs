@@ -176,7 +176,8 @@ impl fmt::Debug for Lifetime {
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct LifetimeDef {
     pub lifetime: Lifetime,
-    pub bounds: Vec<Lifetime>
+    pub bounds: Vec<Lifetime>,
+    pub attrs: ThinAttributes,
 }
 
 /// A "Path" is essentially Rust's notion of a name; for instance:
@@ -393,7 +394,8 @@ pub struct TyParam {
     pub id: NodeId,
     pub bounds: TyParamBounds,
     pub default: Option<P<Ty>>,
-    pub span: Span
+    pub span: Span,
+    pub attrs: ThinAttributes,
 }
 
 /// Represents lifetimes and type parameters attached to a declaration
@@ -1046,6 +1048,10 @@ pub enum ExprKind {
 
     /// `expr?`
     Try(P<Expr>),
+
+    /// Placeholder for an expression that failed to parse, left by parser
+    /// recovery. The error has already been reported.
+    Err,
 }
 
 /// The explicit Self type in a "qualified path". The actual
@@ -1607,6 +1613,16 @@ pub enum AsmDialect {
     Intel,
 }
 
+/// What an operand of `asm_operands!` is allocated to, which decides the
+/// types it can have.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub enum AsmOperandClass {
+    /// A register of the named class, such as `reg`.
+    Reg(InternedString),
+    /// An immediate, from a `const` operand.
+    Const,
+}
+
 #[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
 pub struct InlineAsmOutput {
     pub constraint: InternedString,
@@ -1626,6 +1642,10 @@ pub struct InlineAsm {
     pub alignstack: bool,
     pub dialect: AsmDialect,
     pub expn_id: ExpnId,
+    /// The classes of the outputs followed by those of the inputs, which
+    /// the types of the operands are checked against once they are known.
+    /// Empty for `asm!`, which leaves the types to LLVM.
+    pub operand_classes: Vec<AsmOperandClass>,
 }
 
 /// represents an argument in a function header
@@ -1634,6 +1654,7 @@ pub struct Arg {
     pub ty: P<Ty>,
     pub pat: P<Pat>,
     pub id: NodeId,
+    pub attrs: ThinAttributes,
 }
 
 impl Arg {
@@ -1651,7 +1672,8 @@ impl Arg {
                 node: PatKind::Ident(BindingMode::ByValue(mutability), path, None),
                 span: span
             }),
-            id: DUMMY_NODE_ID
+            id: DUMMY_NODE_ID,
+            attrs: None,
         }
     }
 }
@@ -2076,6 +2098,23 @@ pub struct MacroDef {
     pub use_locally: bool,
     pub allow_internal_unstable: bool,
     pub body: Vec<TokenTree>,
+    /// For a `macro` item, as opposed to a `macro_rules!` macro, the module
+    /// it is defined in.
+    pub scope: Option<MacroScope>,
+}
+
+/// The module a `macro` item is defined in, as much of it as is needed to
+/// resolve the macro by path and to make its expansions hygienic, including
+/// in other crates.
+#[derive(Clone, PartialEq, Eq, RustcEncodable, RustcDecodable, Hash, Debug)]
+pub struct MacroScope {
+    /// The path of the module from the crate root.
+    pub module: Vec<Name>,
+    /// The names of the module's items, other than `macro`s and imports.
+    pub items: Vec<Name>,
+    /// The names the module imports, with the path from the crate root of
+    /// what they import.
+    pub imports: Vec<(Name, Vec<Name>)>,
 }
 
 #[cfg(test)]
@@ -389,16 +389,31 @@ pub fn cfg_matches<T: CfgDiag>(cfgs: &[P<MetaItem>],
             });
             false
         },
+        ast::MetaItemKind::Word(ref name) |
+        ast::MetaItemKind::NameValue(ref name, _) if is_cfg_predicate(name) => {
+            diag.emit_error(|diagnostic| {
+                diagnostic.span_err(cfg.span,
+                    &format!("expected a list of cfg-patterns after `{}`", name));
+            });
+            false
+        }
         ast::MetaItemKind::Word(_) | ast::MetaItemKind::NameValue(..) => {
+            let value = cfg.value_str();
             diag.flag_gated(|feature_gated_cfgs| {
                 feature_gated_cfgs.extend(
                     GatedCfg::gate(cfg).map(GatedCfgAttr::GatedCfg));
+                feature_gated_cfgs.push(
+                    GatedCfgAttr::UsedCfg(cfg.span, cfg.name(), value.clone()));
             });
             contains(cfgs, cfg)
         }
     }
 }
 
+fn is_cfg_predicate(name: &str) -> bool {
+    name == "any" || name == "all" || name == "not"
+}
+
 /// Represents the #[stable], #[unstable] and #[rustc_deprecated] attributes.
 #[derive(RustcEncodable, RustcDecodable, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Stability {
@@ -1348,6 +1348,13 @@ impl CodeMap {
         }
     }
 
+    /// Calls `f` with every expansion recorded so far.
+    pub fn each_expansion<F>(&self, mut f: F) where F: FnMut(&ExpnInfo) {
+        for expn_info in self.expansions.borrow().iter() {
+            f(expn_info);
+        }
+    }
+
     /// Check if a span is "internal" to a macro in which #[unstable]
     /// items can be used (that is, a macro marked with
     /// `#[allow_internal_unstable]`).
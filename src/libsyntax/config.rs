@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use attr::AttrMetaMethods;
+use attr::{AttrMetaMethods, ThinAttributesExt};
 use errors::Handler;
 use feature_gate::GatedCfgAttr;
 use fold::Folder;
@@ -81,6 +81,12 @@ impl<'a, F> fold::Folder for Context<'a, F> where F: FnMut(&[ast::Attribute]) ->
     fn fold_item(&mut self, item: P<ast::Item>) -> SmallVector<P<ast::Item>> {
         fold_item(self, item)
     }
+    fn fold_fn_decl(&mut self, decl: P<ast::FnDecl>) -> P<ast::FnDecl> {
+        fold_fn_decl(self, decl)
+    }
+    fn fold_generics(&mut self, generics: ast::Generics) -> ast::Generics {
+        fold_generics(self, generics)
+    }
 }
 
 pub fn strip_items<'a, F>(diagnostic: &'a Handler,
@@ -192,6 +198,115 @@ fn fold_struct<F>(cx: &mut Context<F>, vdata: ast::VariantData) -> ast::VariantD
     }
 }
 
+fn fold_fn_decl<F>(cx: &mut Context<F>, decl: P<ast::FnDecl>) -> P<ast::FnDecl> where
+    F: FnMut(&[ast::Attribute]) -> bool
+{
+    let decl = decl.map(|ast::FnDecl {inputs, output, variadic}| {
+        ast::FnDecl {
+            inputs: inputs.into_iter()
+                          .filter(|a| param_in_cfg(cx, a.attrs.as_attr_slice()))
+                          .collect(),
+            output: output,
+            variadic: variadic,
+        }
+    });
+    fold::noop_fold_fn_decl(decl, cx)
+}
+
+fn fold_generics<F>(cx: &mut Context<F>, generics: ast::Generics) -> ast::Generics where
+    F: FnMut(&[ast::Attribute]) -> bool
+{
+    let ast::Generics {lifetimes, ty_params, where_clause} = generics;
+    let mut finder = StrippedParamFinder {
+        ty_params: vec![],
+        lifetimes: vec![],
+        found: false,
+    };
+    let lifetimes = lifetimes.into_iter()
+                             .filter(|l| {
+                                 let keep = param_in_cfg(cx, l.attrs.as_attr_slice());
+                                 if !keep {
+                                     finder.lifetimes.push(l.lifetime.name);
+                                 }
+                                 keep
+                             })
+                             .collect();
+    let ty_params = ty_params.into_vec()
+                             .into_iter()
+                             .filter(|tp| {
+                                 let keep = param_in_cfg(cx, tp.attrs.as_attr_slice());
+                                 if !keep {
+                                     finder.ty_params.push(tp.ident.name);
+                                 }
+                                 keep
+                             })
+                             .collect();
+    // Predicates that name a stripped parameter go with it.
+    let ast::WhereClause {id, predicates} = where_clause;
+    let predicates = predicates.into_iter()
+                               .filter(|p| !finder.names_stripped_param(p))
+                               .collect();
+    fold::noop_fold_generics(ast::Generics {
+        lifetimes: lifetimes,
+        ty_params: P::from_vec(ty_params),
+        where_clause: ast::WhereClause {
+            id: id,
+            predicates: predicates,
+        },
+    }, cx)
+}
+
+/// Looks for uses of the type and lifetime parameters stripped from a set of
+/// generics.
+struct StrippedParamFinder {
+    ty_params: Vec<ast::Name>,
+    lifetimes: Vec<ast::Name>,
+    found: bool,
+}
+
+impl StrippedParamFinder {
+    fn names_stripped_param(&mut self, predicate: &ast::WherePredicate) -> bool {
+        if self.ty_params.is_empty() && self.lifetimes.is_empty() {
+            return false;
+        }
+        self.found = false;
+        match *predicate {
+            ast::WherePredicate::BoundPredicate(ref p) => {
+                visit::Visitor::visit_ty(self, &p.bounded_ty);
+                for bound in p.bounds.iter() {
+                    visit::Visitor::visit_ty_param_bound(self, bound);
+                }
+            }
+            ast::WherePredicate::RegionPredicate(ref p) => {
+                visit::Visitor::visit_lifetime(self, &p.lifetime);
+                for bound in &p.bounds {
+                    visit::Visitor::visit_lifetime(self, bound);
+                }
+            }
+            ast::WherePredicate::EqPredicate(ref p) => {
+                visit::Visitor::visit_path(self, &p.path, p.id);
+                visit::Visitor::visit_ty(self, &p.ty);
+            }
+        }
+        self.found
+    }
+}
+
+impl<'v> visit::Visitor<'v> for StrippedParamFinder {
+    fn visit_path(&mut self, path: &'v ast::Path, _id: ast::NodeId) {
+        if !path.global && self.ty_params.contains(&path.segments[0].identifier.name) {
+            self.found = true;
+        }
+        visit::walk_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'v ast::Lifetime) {
+        if self.lifetimes.contains(&lifetime.name) {
+            self.found = true;
+        }
+    }
+}
+
 fn fold_opt_expr<F>(cx: &mut Context<F>, expr: P<ast::Expr>) -> Option<P<ast::Expr>>
     where F: FnMut(&[ast::Attribute]) -> bool
 {
@@ -256,6 +371,20 @@ fn foreign_item_in_cfg<F>(cx: &mut Context<F>, item: &ast::ForeignItem) -> bool
     return (cx.in_cfg)(&item.attrs);
 }
 
+// Parameters have nowhere to keep attributes, so only `cfg` is allowed on
+// them. `cfg_attr` has been expanded by now.
+fn param_in_cfg<F>(cx: &mut Context<F>, attrs: &[ast::Attribute]) -> bool where
+    F: FnMut(&[ast::Attribute]) -> bool
+{
+    if !(cx.in_cfg)(attrs) {
+        return false;
+    }
+    for attr in attrs.iter().filter(|a| !is_cfg(a)) {
+        cx.diagnostic.span_err(attr.span, "only `cfg` attributes are allowed on parameters");
+    }
+    true
+}
+
 fn is_cfg(attr: &ast::Attribute) -> bool {
     attr.check_name("cfg")
 }
@@ -328,7 +457,8 @@ impl<'a, T: CfgDiag> fold::Folder for CfgAttrFolder<'a, T> {
         };
 
         if attr::cfg_matches(&self.config[..], &cfg, &mut self.diag) {
-            Some(respan(mi.span, ast::Attribute_ {
+            // The attribute can be a `cfg_attr` itself.
+            self.fold_attribute(respan(mi.span, ast::Attribute_ {
                 id: attr::mk_attr_id(),
                 style: attr.node.style,
                 value: mi.clone(),
@@ -361,6 +491,16 @@ struct StmtExprAttrFeatureVisitor<'a, 'b> {
     discovered: &'b mut Vec<GatedCfgAttr>,
 }
 
+impl<'a, 'b> StmtExprAttrFeatureVisitor<'a, 'b> {
+    // Flags the attributes of a parameter, and tells whether it survives.
+    fn flag_param_attrs(&mut self, attrs: &[ast::Attribute]) -> bool {
+        for attr in attrs {
+            self.discovered.push(GatedCfgAttr::GatedParamAttr(attr.span));
+        }
+        node_survives_cfg(attrs, self.config)
+    }
+}
+
 // Runs the cfg_attr and cfg folders locally in "silent" mode
 // to discover attribute use on stmts or expressions ahead of time
 impl<'v, 'a, 'b> visit::Visitor<'v> for StmtExprAttrFeatureVisitor<'a, 'b> {
@@ -452,6 +592,24 @@ impl<'v, 'a, 'b> visit::Visitor<'v> for StmtExprAttrFeatureVisitor<'a, 'b> {
         }
     }
 
+    fn visit_arg(&mut self, a: &'v ast::Arg) {
+        if self.flag_param_attrs(a.attrs.as_attr_slice()) {
+            visit::walk_arg(self, a);
+        }
+    }
+
+    fn visit_ty_param(&mut self, tp: &'v ast::TyParam) {
+        if self.flag_param_attrs(tp.attrs.as_attr_slice()) {
+            visit::walk_ty_param(self, tp);
+        }
+    }
+
+    fn visit_lifetime_def(&mut self, l: &'v ast::LifetimeDef) {
+        if self.flag_param_attrs(l.attrs.as_attr_slice()) {
+            visit::walk_lifetime_def(self, l);
+        }
+    }
+
     // This visitor runs pre expansion, so we need to prevent
     // the default panic here
     fn visit_mac(&mut self, mac: &'v ast::Mac) {
@@ -16,6 +16,7 @@ use codemap;
 use codemap::{CodeMap, Span, ExpnId, ExpnInfo, NO_EXPANSION};
 use errors::DiagnosticBuilder;
 use ext;
+use ext::decl_macro::DeclMacros;
 use ext::expand;
 use ext::tt::macro_rules;
 use feature_gate::GatedCfgAttr;
@@ -434,6 +435,11 @@ pub enum SyntaxExtension {
 
     /// Represents `macro_rules!` itself.
     MacroRulesTT,
+
+    /// A `#[derive(...)]` mode loaded from a `proc-macro` crate. These
+    /// expand like a `MultiDecorator` but, unlike derives registered by
+    /// plugins, do not require `#![feature(custom_derive)]`.
+    CustomDerive(Box<MultiItemDecorator + 'static>),
 }
 
 pub type NamedSyntaxExtension = (Name, SyntaxExtension);
@@ -536,6 +542,73 @@ fn initial_syntax_expander_table<'feat>(ecfg: &expand::ExpansionConfig<'feat>)
     syntax_expanders
 }
 
+/// Resolves the paths of invocations of `macro` items. Name resolution
+/// proper runs once expansion is over, so the expander tells the resolver
+/// about the items of the crate as it goes: first those of the unexpanded
+/// crate, then those that macros expand to.
+pub trait MacroResolver {
+    /// Records `items`, some of the items of the module at `module`, a path
+    /// from the crate root. The items of child modules are recorded with
+    /// further calls; `macro` items are defined through `add_macro`.
+    fn add_items(&mut self, module: &[Name], items: &[P<ast::Item>]);
+
+    /// Defines `def`, a `macro` item of `module`. For a `macro` exported by
+    /// another crate, `module` is `def.scope`'s and `def.scope` describes
+    /// the module from this crate's point of view, and `vis` is `pub`.
+    /// Returns false if there already is a `macro` of that name in `module`.
+    fn add_macro(&mut self, module: &[Name], def: ast::MacroDef, ext: Rc<SyntaxExtension>,
+                 vis: &ast::Visibility) -> bool;
+
+    /// Whether any `macro` has been defined.
+    fn has_macros(&self) -> bool;
+
+    /// Resolves the path of a macro invocation in `module` to a `macro`,
+    /// returning the macro and the module it is defined in. `Ok(None)`
+    /// means that the path does not name a `macro`; an error is returned if
+    /// it names one that is private to another module.
+    fn resolve_macro(&self, module: &[Name], path: &ast::Path)
+                     -> Result<Option<(Rc<SyntaxExtension>, Vec<Name>)>, String>;
+
+    /// Where a path in the body of a macro defined in `def_module` points
+    /// to, if it names something of that module: returns the path from the
+    /// crate root that replaces the path's first `n` segments, along with
+    /// `n`.
+    fn def_site_path(&self, def_module: &[Name], path: &ast::Path)
+                     -> Option<(Vec<Name>, usize)>;
+
+    /// Whether `target`, the path from the crate root that a `use` of
+    /// `module` imports, only names a `macro`, so that the import is meant
+    /// for the expander alone.
+    fn is_macro_import(&self, module: &[Name], target: &[Name]) -> bool;
+
+    /// The `pub` macros of the crate that other crates can name, each with
+    /// its scope.
+    fn exported_macros(&self) -> Vec<ast::MacroDef>;
+}
+
+/// A `MacroResolver` that knows of no `macro` items, for expanding code
+/// outside of a crate.
+pub struct DummyMacroResolver;
+
+impl MacroResolver for DummyMacroResolver {
+    fn add_items(&mut self, _module: &[Name], _items: &[P<ast::Item>]) {}
+    fn add_macro(&mut self, _module: &[Name], _def: ast::MacroDef, _ext: Rc<SyntaxExtension>,
+                 _vis: &ast::Visibility) -> bool {
+        true
+    }
+    fn has_macros(&self) -> bool { false }
+    fn resolve_macro(&self, _module: &[Name], _path: &ast::Path)
+                     -> Result<Option<(Rc<SyntaxExtension>, Vec<Name>)>, String> {
+        Ok(None)
+    }
+    fn def_site_path(&self, _def_module: &[Name], _path: &ast::Path)
+                     -> Option<(Vec<Name>, usize)> {
+        None
+    }
+    fn is_macro_import(&self, _module: &[Name], _target: &[Name]) -> bool { false }
+    fn exported_macros(&self) -> Vec<ast::MacroDef> { Vec::new() }
+}
+
 /// One of these is made during expansion and incrementally updated as we go;
 /// when a macro expansion occurs, the resulting nodes have the backtrace()
 /// -> expn_info of their expansion context stored into their span.
@@ -551,7 +624,12 @@ pub struct ExtCtxt<'a> {
     pub exported_macros: Vec<ast::MacroDef>,
 
     pub syntax_env: SyntaxEnv,
+    pub resolver: &'a mut MacroResolver,
+    pub decl_macros: DeclMacros,
     pub recursion_count: usize,
+    /// The syntax context of the path of each `macro!(...)` invocation
+    /// expanded so far, i.e. the context its arguments were written in.
+    pub invocation_ctxts: HashMap<ExpnId, ast::SyntaxContext>,
 
     pub filename: Option<String>,
     pub mod_path_stack: Vec<InternedString>,
@@ -561,7 +639,8 @@ pub struct ExtCtxt<'a> {
 impl<'a> ExtCtxt<'a> {
     pub fn new(parse_sess: &'a parse::ParseSess, cfg: ast::CrateConfig,
                ecfg: expand::ExpansionConfig<'a>,
-               feature_gated_cfgs: &'a mut Vec<GatedCfgAttr>) -> ExtCtxt<'a> {
+               feature_gated_cfgs: &'a mut Vec<GatedCfgAttr>,
+               resolver: &'a mut MacroResolver) -> ExtCtxt<'a> {
         let env = initial_syntax_expander_table(&ecfg);
         ExtCtxt {
             parse_sess: parse_sess,
@@ -573,7 +652,10 @@ impl<'a> ExtCtxt<'a> {
             feature_gated_cfgs: feature_gated_cfgs,
             exported_macros: Vec::new(),
             syntax_env: env,
+            resolver: resolver,
+            decl_macros: DeclMacros::new(),
             recursion_count: 0,
+            invocation_ctxts: HashMap::new(),
 
             filename: None,
             mod_path_stack: Vec::new(),
@@ -618,6 +700,26 @@ impl<'a> ExtCtxt<'a> {
         call_site.expect("missing expansion backtrace")
     }
 
+    /// The syntax context the code at `sp` was written in: that of the
+    /// innermost invocation being expanded whose arguments contain `sp`.
+    /// Returns `None` if `sp` isn't part of any invocation's arguments.
+    pub fn syntax_context_of(&self, sp: Span) -> Option<ast::SyntaxContext> {
+        let mut expn_id = self.backtrace;
+        loop {
+            let call_site = match self.codemap().with_expn_info(expn_id,
+                                                                |ei| ei.map(|ei| ei.call_site)) {
+                Some(cs) => cs,
+                None => return None,
+            };
+            if call_site.lo <= sp.lo && sp.hi <= call_site.hi {
+                if let Some(&ctxt) = self.invocation_ctxts.get(&expn_id) {
+                    return Some(ctxt);
+                }
+            }
+            expn_id = call_site.expn_id;
+        }
+    }
+
     /// Returns span for the macro which originally caused the current expansion to happen.
     ///
     /// Stops backtracing at include! boundary.
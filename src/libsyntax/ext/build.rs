@@ -453,7 +453,8 @@ impl<'a> AstBuilder for ExtCtxt<'a> {
             id: ast::DUMMY_NODE_ID,
             bounds: bounds,
             default: default,
-            span: span
+            span: span,
+            attrs: None,
         }
     }
 
@@ -501,7 +502,8 @@ impl<'a> AstBuilder for ExtCtxt<'a> {
                     -> ast::LifetimeDef {
         ast::LifetimeDef {
             lifetime: self.lifetime(span, name),
-            bounds: bounds
+            bounds: bounds,
+            attrs: None,
         }
     }
 
@@ -944,7 +946,8 @@ impl<'a> AstBuilder for ExtCtxt<'a> {
         ast::Arg {
             ty: ty,
             pat: arg_pat,
-            id: ast::DUMMY_NODE_ID
+            id: ast::DUMMY_NODE_ID,
+            attrs: None,
         }
     }
 
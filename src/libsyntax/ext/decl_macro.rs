@@ -0,0 +1,447 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Declarative macros 2.0, i.e. `macro` items.
+//!
+//! Unlike a `macro_rules!` macro, which is in scope textually after its
+//! definition, a `macro` item lives in the module it is defined in, like any
+//! other item: it is invoked by path (`foo!()`, `self::m::foo!()`,
+//! `super::foo!()`, `other_crate::foo!()`) or brought into scope with
+//! `use`, and a macro that is not `pub` is private to its module and the
+//! module's descendants.
+//!
+//! The paths are resolved by the `MacroResolver` of the `ExtCtxt`, which
+//! `librustc_resolve` implements. This module tells it about the items of
+//! the crate, before expansion and as macros expand to more of them, and
+//! removes the imports that only name `macro`s from the expanded crate, so
+//! that the rest of name resolution never sees them. `pub` macros that
+//! other crates can name are exported in the crate's metadata along with
+//! their scope.
+//!
+//! Expansions of `macro`s are also hygienic at the definition site for
+//! items and fields, not just for local variables. Once the crate has been
+//! expanded, the mtwt marks of the expansions tell which identifiers come
+//! from a macro body: paths in the body that name items of the defining
+//! module are made absolute, and items and fields that the body introduces
+//! are renamed so that they can't be named from the invocation site.
+//! Privacy is still checked from the invocation site, though.
+
+use ast::{self, Ident, Mrk, Name, PatKind};
+use attr;
+use codemap::Spanned;
+use ext::base::{ExtCtxt, MacroResolver};
+use ext::mtwt;
+use ext::tt::macro_rules;
+use fold::{self, Folder};
+use parse::token::{self, keywords, special_idents};
+use ptr::P;
+use util::small_vector::SmallVector;
+use visit::{self, Visitor};
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+/// Whether `mac` is the definition of a `macro` item, which the parser
+/// represents as an invocation of the `macro` keyword.
+pub fn is_macro_def(mac: &ast::Mac) -> bool {
+    let path = &mac.node.path;
+    !path.global && path.segments.len() == 1 &&
+        path.segments[0].identifier.name == keywords::Macro.to_name()
+}
+
+/// The expansions of `macro` items, for making them hygienic.
+pub struct DeclMacros {
+    /// The marks of the expansions of `macro`s, with the module the
+    /// expanded macro was defined in.
+    marks: HashMap<Mrk, Vec<Name>>,
+}
+
+impl DeclMacros {
+    pub fn new() -> DeclMacros {
+        DeclMacros {
+            marks: HashMap::new(),
+        }
+    }
+
+    /// Records that the expansion marked with `mark` is the expansion of a
+    /// `macro` defined in `module`.
+    pub fn record_expansion(&mut self, mark: Mrk, module: Vec<Name>) {
+        self.marks.insert(mark, module);
+    }
+
+    /// The mark of the expansion of a `macro` whose body `ident` comes
+    /// from, if any.
+    fn def_site_mark(&self, ident: Ident) -> Option<Mrk> {
+        if ident.ctxt == ast::EMPTY_CTXT {
+            return None;
+        }
+        // Marks are listed from the outermost one in; the marks of
+        // expansions that `ident` was merely passed through cancel out.
+        mtwt::marksof(ident.ctxt, special_idents::invalid.name)
+            .into_iter()
+            .find(|mark| self.marks.contains_key(mark))
+    }
+}
+
+/// Tells the resolver about the items of `krate` and the `macro`s they
+/// define, before the crate is expanded.
+pub fn collect(cx: &mut ExtCtxt, krate: &ast::Crate) {
+    collect_items(cx, &[], &krate.module.items);
+}
+
+/// Tells the resolver about `items`, which belong to `module`, along with
+/// the items of their child modules, and compiles and defines the `macro`s
+/// among them.
+pub fn collect_items(cx: &mut ExtCtxt, module: &[Name], items: &[P<ast::Item>]) {
+    cx.resolver.add_items(module, items);
+    for item in items {
+        match item.node {
+            ast::ItemKind::Mac(ref mac) if is_macro_def(mac) => {
+                let def = ast::MacroDef {
+                    ident: item.ident,
+                    attrs: item.attrs.clone(),
+                    id: ast::DUMMY_NODE_ID,
+                    span: item.span,
+                    imported_from: None,
+                    export: false,
+                    use_locally: true,
+                    allow_internal_unstable: attr::contains_name(&item.attrs,
+                                                                 "allow_internal_unstable"),
+                    body: mac.node.tts.clone(),
+                    scope: None,
+                };
+                let ext = Rc::new(macro_rules::compile(cx, &def));
+                if !cx.resolver.add_macro(module, def, ext, &item.vis) {
+                    cx.span_err(item.span,
+                                &format!("duplicate definition of macro `{}`", item.ident));
+                }
+            }
+            ast::ItemKind::Mod(ref m) if item.ident.name != special_idents::invalid.name => {
+                let mut child = module.to_vec();
+                child.push(item.ident.name);
+                collect_items(cx, &child, &m.items);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Defines `def`, a `macro` exported by another crate, whose scope has
+/// been made relative to this crate when it was loaded.
+pub fn add_extern_macro(cx: &mut ExtCtxt, def: ast::MacroDef) {
+    let module = match def.scope {
+        Some(ref scope) => scope.module.clone(),
+        None => return,
+    };
+    let ext = Rc::new(macro_rules::compile(cx, &def));
+    // A crate may be loaded under the same name more than once, in which
+    // case its macros are already defined.
+    cx.resolver.add_macro(&module, def, ext, &ast::Visibility::Public);
+}
+
+/// The path from the crate root imported by the `use` path `path` of
+/// `module`.
+pub fn import_path(path: &ast::Path, module: &[Name]) -> Vec<Name> {
+    let mut segments = path.segments.iter().map(|segment| segment.identifier.name).peekable();
+    let mut result = Vec::new();
+    if !path.global {
+        if segments.peek() == Some(&keywords::SelfValue.to_name()) {
+            segments.next();
+            result.extend_from_slice(module);
+        } else if segments.peek() == Some(&keywords::Super.to_name()) {
+            result.extend_from_slice(module);
+            while segments.peek() == Some(&keywords::Super.to_name()) {
+                segments.next();
+                result.pop();
+            }
+        }
+    }
+    result.extend(segments);
+    result
+}
+
+/// Removes the imports of `krate` that only name `macro`s, which the
+/// expander has already taken care of.
+pub fn strip_macro_imports(resolver: &MacroResolver, krate: ast::Crate) -> ast::Crate {
+    if !resolver.has_macros() {
+        return krate;
+    }
+    MacroImportStripper {
+        resolver: resolver,
+        module: Vec::new(),
+    }.fold_crate(krate)
+}
+
+struct MacroImportStripper<'a> {
+    resolver: &'a MacroResolver,
+    module: Vec<Name>,
+}
+
+impl<'a> MacroImportStripper<'a> {
+    fn is_macro_import(&self, target: &[Name]) -> bool {
+        self.resolver.is_macro_import(&self.module, target)
+    }
+}
+
+impl<'a> Folder for MacroImportStripper<'a> {
+    fn fold_item(&mut self, item: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        if let ast::ItemKind::Mod(..) = item.node {
+            let valid_ident = item.ident.name != special_idents::invalid.name;
+            if valid_ident {
+                self.module.push(item.ident.name);
+            }
+            let result = fold::noop_fold_item(item, self);
+            if valid_ident {
+                self.module.pop();
+            }
+            return result;
+        }
+
+        let kept = match item.node {
+            ast::ItemKind::Use(ref view_path) => match view_path.node {
+                ast::ViewPathSimple(_, ref path) => {
+                    if self.is_macro_import(&import_path(path, &self.module)) {
+                        return SmallVector::zero();
+                    }
+                    None
+                }
+                ast::ViewPathList(ref prefix, ref items) => {
+                    let prefix = import_path(prefix, &self.module);
+                    let kept = items.iter().filter(|item| match item.node {
+                        ast::PathListItemKind::Ident { name, .. } => {
+                            let mut target = prefix.clone();
+                            target.push(name.name);
+                            !self.is_macro_import(&target)
+                        }
+                        ast::PathListItemKind::Mod { .. } => true,
+                    }).cloned().collect::<Vec<_>>();
+                    if kept.is_empty() {
+                        return SmallVector::zero();
+                    }
+                    if kept.len() < items.len() { Some(kept) } else { None }
+                }
+                ast::ViewPathGlob(..) => None,
+            },
+            _ => None,
+        };
+
+        match kept {
+            None => SmallVector::one(item),
+            Some(kept) => SmallVector::one(item.map(|item| {
+                let node = match item.node {
+                    ast::ItemKind::Use(view_path) => {
+                        ast::ItemKind::Use(view_path.map(|view_path| match view_path.node {
+                            ast::ViewPathList(prefix, _) => Spanned {
+                                node: ast::ViewPathList(prefix, kept),
+                                span: view_path.span,
+                            },
+                            _ => unreachable!(),
+                        }))
+                    }
+                    _ => unreachable!(),
+                };
+                ast::Item { node: node, ..item }
+            })),
+        }
+    }
+}
+
+/// Makes the expansions of `macro` items in `krate` hygienic at the
+/// definition site of the macros.
+pub fn apply_hygiene(decl_macros: &DeclMacros, resolver: &MacroResolver, krate: ast::Crate)
+                     -> ast::Crate {
+    if decl_macros.marks.is_empty() {
+        return krate;
+    }
+
+    let mut names = DefSiteNames {
+        decl_macros: decl_macros,
+        bindings: HashMap::new(),
+        items: HashMap::new(),
+        fields: HashMap::new(),
+    };
+    visit::walk_crate(&mut names, &krate);
+
+    DefSiteHygiene {
+        decl_macros: decl_macros,
+        resolver: resolver,
+        bindings: names.bindings,
+        items: names.items,
+        fields: names.fields,
+    }.fold_crate(krate)
+}
+
+/// Collects, for each expansion of a `macro`, the names its body binds
+/// locally, and the items and fields it introduces along with the fresh
+/// names they are given.
+struct DefSiteNames<'a> {
+    decl_macros: &'a DeclMacros,
+    bindings: HashMap<Mrk, HashSet<Name>>,
+    items: HashMap<Mrk, HashMap<Name, Name>>,
+    fields: HashMap<Mrk, HashMap<Name, Name>>,
+}
+
+fn gensym_name(names: &mut HashMap<Mrk, HashMap<Name, Name>>, mark: Mrk, name: Name) {
+    names.entry(mark)
+         .or_insert_with(HashMap::new)
+         .entry(name)
+         .or_insert_with(|| token::gensym(&name.as_str()));
+}
+
+impl<'a, 'v> Visitor<'v> for DefSiteNames<'a> {
+    fn visit_item(&mut self, item: &'v ast::Item) {
+        match item.node {
+            ast::ItemKind::Static(..) | ast::ItemKind::Const(..) | ast::ItemKind::Fn(..) |
+            ast::ItemKind::Mod(..) | ast::ItemKind::Ty(..) | ast::ItemKind::Enum(..) |
+            ast::ItemKind::Struct(..) | ast::ItemKind::Trait(..) => {
+                if let Some(mark) = self.decl_macros.def_site_mark(item.ident) {
+                    gensym_name(&mut self.items, mark, item.ident.name);
+                }
+            }
+            _ => {}
+        }
+        visit::walk_item(self, item);
+    }
+
+    fn visit_struct_field(&mut self, field: &'v ast::StructField) {
+        if let Some(ident) = field.ident {
+            if let Some(mark) = self.decl_macros.def_site_mark(ident) {
+                gensym_name(&mut self.fields, mark, ident.name);
+            }
+        }
+        visit::walk_struct_field(self, field);
+    }
+
+    fn visit_pat(&mut self, pat: &'v ast::Pat) {
+        if let PatKind::Ident(_, ref ident, _) = pat.node {
+            if let Some(mark) = self.decl_macros.def_site_mark(ident.node) {
+                self.bindings.entry(mark).or_insert_with(HashSet::new).insert(ident.node.name);
+            }
+        }
+        visit::walk_pat(self, pat);
+    }
+
+    fn visit_generics(&mut self, generics: &'v ast::Generics) {
+        for param in generics.ty_params.iter() {
+            if let Some(mark) = self.decl_macros.def_site_mark(param.ident) {
+                self.bindings.entry(mark).or_insert_with(HashSet::new).insert(param.ident.name);
+            }
+        }
+        visit::walk_generics(self, generics);
+    }
+}
+
+struct DefSiteHygiene<'a> {
+    decl_macros: &'a DeclMacros,
+    resolver: &'a MacroResolver,
+    bindings: HashMap<Mrk, HashSet<Name>>,
+    items: HashMap<Mrk, HashMap<Name, Name>>,
+    fields: HashMap<Mrk, HashMap<Name, Name>>,
+}
+
+impl<'a> DefSiteHygiene<'a> {
+    fn renamed(&self, names: &HashMap<Mrk, HashMap<Name, Name>>, ident: Ident) -> Ident {
+        let name = self.decl_macros.def_site_mark(ident)
+                                   .and_then(|mark| names.get(&mark))
+                                   .and_then(|names| names.get(&ident.name));
+        match name {
+            Some(&name) => Ident::new(name, ident.ctxt),
+            None => ident,
+        }
+    }
+}
+
+impl<'a> Folder for DefSiteHygiene<'a> {
+    fn fold_item_simple(&mut self, item: ast::Item) -> ast::Item {
+        let ident = match item.node {
+            ast::ItemKind::Use(..) | ast::ItemKind::ExternCrate(..) => item.ident,
+            _ => self.renamed(&self.items, item.ident),
+        };
+        fold::noop_fold_item_simple(ast::Item { ident: ident, ..item }, self)
+    }
+
+    fn fold_struct_field(&mut self, field: ast::StructField) -> ast::StructField {
+        let ident = field.ident.map(|ident| self.renamed(&self.fields, ident));
+        fold::noop_fold_struct_field(ast::StructField { ident: ident, ..field }, self)
+    }
+
+    fn fold_field(&mut self, field: ast::Field) -> ast::Field {
+        let mut field = fold::noop_fold_field(field, self);
+        field.ident.node = self.renamed(&self.fields, field.ident.node);
+        field
+    }
+
+    fn fold_expr(&mut self, expr: P<ast::Expr>) -> P<ast::Expr> {
+        expr.map(|expr| {
+            let mut expr = fold::noop_fold_expr(expr, self);
+            if let ast::ExprKind::Field(_, ref mut ident) = expr.node {
+                ident.node = self.renamed(&self.fields, ident.node);
+            }
+            expr
+        })
+    }
+
+    fn fold_pat(&mut self, pat: P<ast::Pat>) -> P<ast::Pat> {
+        fold::noop_fold_pat(pat, self).map(|mut pat| {
+            if let PatKind::Struct(_, ref mut fields, _) = pat.node {
+                for field in fields {
+                    let ident = self.renamed(&self.fields, field.node.ident);
+                    if ident.name != field.node.ident.name {
+                        field.node.ident = ident;
+                        // `S { x }` also binds `x`, which keeps its name.
+                        field.node.is_shorthand = false;
+                    }
+                }
+            }
+            pat
+        })
+    }
+
+    fn fold_path(&mut self, path: ast::Path) -> ast::Path {
+        let mut path = fold::noop_fold_path(path, self);
+        if path.global {
+            return path;
+        }
+        let first = path.segments[0].identifier;
+        let mark = match self.decl_macros.def_site_mark(first) {
+            Some(mark) => mark,
+            None => return path,
+        };
+        if first.name == keywords::SelfType.to_name() ||
+           self.bindings.get(&mark).map_or(false, |names| names.contains(&first.name)) {
+            return path;
+        }
+        if let Some(&name) = self.items.get(&mark).and_then(|names| names.get(&first.name)) {
+            path.segments[0].identifier = Ident::new(name, first.ctxt);
+            return path;
+        }
+
+        let def_module = &self.decl_macros.marks[&mark];
+        let (target, replaced) = match self.resolver.def_site_path(def_module, &path) {
+            Some(found) => found,
+            None => return path,
+        };
+        let mut rest = path.segments.split_off(replaced);
+        let parameters = path.segments.pop().unwrap().parameters;
+        let mut segments = target.into_iter().map(|name| ast::PathSegment {
+            identifier: Ident::new(name, ast::EMPTY_CTXT),
+            parameters: ast::PathParameters::none(),
+        }).collect::<Vec<_>>();
+        if let Some(last) = segments.last_mut() {
+            last.parameters = parameters;
+        }
+        segments.append(&mut rest);
+        ast::Path {
+            span: path.span,
+            global: true,
+            segments: segments,
+        }
+    }
+}
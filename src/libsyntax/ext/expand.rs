@@ -13,6 +13,7 @@ use ast::{Local, Ident, Mac_, Name};
 use ast::{MacStmtStyle, Mrk, Stmt, StmtKind, ItemKind};
 use ast::TokenTree;
 use ast;
+use ext::decl_macro;
 use ext::mtwt;
 use ext::build::AstBuilder;
 use attr;
@@ -34,6 +35,7 @@ use std_inject;
 
 use std::collections::HashSet;
 use std::env;
+use std::rc::Rc;
 
 pub fn expand_expr(e: P<ast::Expr>, fld: &mut MacroExpander) -> P<ast::Expr> {
     let expr_span = e.span;
@@ -190,27 +192,13 @@ fn expand_mac_invoc<T, F, G>(mac: ast::Mac,
     // in this file.
 
     let Mac_ { path: pth, tts, .. } = mac.node;
-    if pth.segments.len() > 1 {
-        fld.cx.span_err(pth.span,
-                        "expected macro name without module \
-                        separators");
-        // let compilation continue
-        return None;
-    }
-    let extname = pth.segments[0].identifier.name;
-    match fld.cx.syntax_env.find(extname) {
+    let extname = pth.segments.last().unwrap().identifier.name;
+    match find_macro(fld, &pth) {
         None => {
-            let mut err = fld.cx.struct_span_err(
-                pth.span,
-                &format!("macro undefined: '{}!'",
-                        &extname));
-            fld.cx.suggest_macro_name(&extname.as_str(), pth.span, &mut err);
-            err.emit();
-
             // let compilation continue
             None
         }
-        Some(rc) => match *rc {
+        Some((rc, def_module)) => match *rc {
             NormalTT(ref expandfun, exp_span, allow_internal_unstable) => {
                 fld.cx.bt_push(ExpnInfo {
                         call_site: span,
@@ -220,7 +208,12 @@ fn expand_mac_invoc<T, F, G>(mac: ast::Mac,
                             allow_internal_unstable: allow_internal_unstable,
                         },
                     });
+                let path_ctxt = pth.segments.last().unwrap().identifier.ctxt;
+                fld.cx.invocation_ctxts.insert(fld.cx.backtrace(), path_ctxt);
                 let fm = fresh_mark();
+                if let Some(def_module) = def_module {
+                    fld.cx.decl_macros.record_expansion(fm, def_module);
+                }
                 let marked_before = mark_tts(&tts[..], fm);
 
                 // The span that we pass to the expanders we want to
@@ -259,6 +252,48 @@ fn expand_mac_invoc<T, F, G>(mac: ast::Mac,
     }
 }
 
+/// Find the macro invoked through `path`: either a syntax extension or a
+/// `macro_rules!` macro in scope, or a `macro` item. For the latter, the
+/// module the macro is defined in is returned as well, as its expansions
+/// are hygienic with respect to that module. Errors are reported here.
+fn find_macro(fld: &mut MacroExpander, path: &ast::Path)
+              -> Option<(Rc<SyntaxExtension>, Option<Vec<Name>>)> {
+    if !path.global && path.segments.len() == 1 {
+        if let Some(ext) = fld.cx.syntax_env.find(path.segments[0].identifier.name) {
+            return Some((ext, None));
+        }
+    }
+
+    let module = fld.cx.mod_path.iter().map(|ident| ident.name).collect::<Vec<_>>();
+    match fld.cx.resolver.resolve_macro(&module, path) {
+        Ok(Some((ext, def_module))) => return Some((ext, Some(def_module))),
+        Ok(None) => {}
+        Err(msg) => {
+            fld.cx.span_err(path.span, &msg);
+            return None;
+        }
+    }
+
+    if path.global || path.segments.len() > 1 {
+        if !fld.cx.resolver.has_macros() {
+            fld.cx.span_err(path.span,
+                            "expected macro name without module \
+                            separators");
+        } else {
+            fld.cx.span_err(path.span, &format!("unresolved macro path `{}`", path));
+        }
+    } else {
+        let extname = path.segments[0].identifier.name;
+        let mut err = fld.cx.struct_span_err(
+            path.span,
+            &format!("macro undefined: '{}!'",
+                    &extname));
+        fld.cx.suggest_macro_name(&extname.as_str(), path.span, &mut err);
+        err.emit();
+    }
+    None
+}
+
 /// Rename loop label and expand its loop body
 ///
 /// The renaming procedure for loop is different in the sense that the loop
@@ -361,25 +396,34 @@ fn contains_macro_use(fld: &mut MacroExpander, attrs: &[ast::Attribute]) -> bool
 // logic as for expression-position macro invocations.
 pub fn expand_item_mac(it: P<ast::Item>,
                        fld: &mut MacroExpander) -> SmallVector<P<ast::Item>> {
-    let (extname, path_span, tts, span, attrs, ident) = it.and_then(|it| match it.node {
+    if let ItemKind::Mac(ref mac) = it.node {
+        if decl_macro::is_macro_def(mac) {
+            expand_macro_def(&it, fld);
+            return SmallVector::zero();
+        }
+    }
+
+    let (path, tts, span, attrs, ident) = it.and_then(|it| match it.node {
         ItemKind::Mac(codemap::Spanned { node: Mac_ { path, tts, .. }, .. }) =>
-            (path.segments[0].identifier.name, path.span, tts, it.span, it.attrs, it.ident),
+            (path, tts, it.span, it.attrs, it.ident),
         _ => fld.cx.span_bug(it.span, "invalid item macro invocation")
     });
+    let extname = path.segments.last().unwrap().identifier.name;
+    let path_span = path.span;
 
     let fm = fresh_mark();
     let items = {
-        let expanded = match fld.cx.syntax_env.find(extname) {
+        let expanded = match find_macro(fld, &path) {
             None => {
-                fld.cx.span_err(path_span,
-                                &format!("macro undefined: '{}!'",
-                                        extname));
                 // let compilation continue
                 return SmallVector::zero();
             }
 
-            Some(rc) => match *rc {
+            Some((rc, def_module)) => match *rc {
                 NormalTT(ref expander, tt_span, allow_internal_unstable) => {
+                    if let Some(def_module) = def_module {
+                        fld.cx.decl_macros.record_expansion(fm, def_module);
+                    }
                     if ident.name != parse::token::special_idents::invalid.name {
                         fld.cx
                             .span_err(path_span,
@@ -464,6 +508,7 @@ pub fn expand_item_mac(it: P<ast::Item>,
                         use_locally: true,
                         allow_internal_unstable: allow_internal_unstable,
                         body: tts,
+                        scope: None,
                     };
                     fld.cx.insert_macro(def);
 
@@ -485,8 +530,14 @@ pub fn expand_item_mac(it: P<ast::Item>,
 
     let items = match items {
         Some(items) => {
+            let items = items.into_iter().map(|i| mark_item(i, fm)).collect::<Vec<_>>();
+            // The items belong to the current module unless they're in a
+            // block, and `macro`s they define can be invoked right away.
+            if !fld.cx.in_block {
+                let module = fld.cx.mod_path.iter().map(|ident| ident.name).collect::<Vec<_>>();
+                decl_macro::collect_items(fld.cx, &module, &items);
+            }
             items.into_iter()
-                .map(|i| mark_item(i, fm))
                 .flat_map(|i| fld.fold_item(i).into_iter())
                 .collect()
         }
@@ -502,6 +553,26 @@ pub fn expand_item_mac(it: P<ast::Item>,
     items
 }
 
+/// Check a `macro` item. The macros of the crate's modules are defined
+/// before expansion starts, or as soon as a macro expands to them, so there
+/// is nothing else to do.
+fn expand_macro_def(it: &ast::Item, fld: &mut MacroExpander) {
+    if !fld.cx.ecfg.enable_decl_macro() {
+        feature_gate::emit_feature_err(&fld.cx.parse_sess.span_diagnostic,
+                                       "decl_macro",
+                                       it.span,
+                                       feature_gate::GateIssue::Language,
+                                       feature_gate::EXPLAIN_DECL_MACRO);
+        return;
+    }
+
+    if fld.cx.in_block {
+        fld.cx.span_err(it.span,
+                        "`macro` items can currently only be defined in a module, \
+                         not in a block");
+    }
+}
+
 /// Expand a stmt
 fn expand_stmt(stmt: Stmt, fld: &mut MacroExpander) -> SmallVector<Stmt> {
     // perform all pending renames
@@ -978,7 +1049,7 @@ fn expand_decorators(a: Annotatable,
         let mname = intern(&attr.name());
         match fld.cx.syntax_env.find(mname) {
             Some(rc) => match *rc {
-                MultiDecorator(ref dec) => {
+                MultiDecorator(ref dec) | CustomDerive(ref dec) => {
                     attr::mark_used(&attr);
 
                     fld.cx.bt_push(ExpnInfo {
@@ -1353,6 +1424,8 @@ impl<'feat> ExpansionConfig<'feat> {
         fn enable_allow_internal_unstable = allow_internal_unstable,
         fn enable_custom_derive = allow_custom_derive,
         fn enable_pushpop_unsafe = allow_pushpop_unsafe,
+        fn enable_decl_macro = decl_macro,
+        fn enable_format_args_capture = format_args_capture,
     }
 }
 
@@ -1372,7 +1445,11 @@ pub fn expand_crate(mut cx: ExtCtxt,
         let mut expander = MacroExpander::new(&mut cx);
 
         for def in imported_macros {
-            expander.cx.insert_macro(def);
+            if def.scope.is_some() {
+                decl_macro::add_extern_macro(expander.cx, def);
+            } else {
+                expander.cx.insert_macro(def);
+            }
         }
 
         for (name, extension) in user_exts {
@@ -1380,8 +1457,12 @@ pub fn expand_crate(mut cx: ExtCtxt,
         }
 
         let err_count = cx.parse_sess.span_diagnostic.err_count();
+        decl_macro::collect(expander.cx, &c);
         let mut ret = expander.fold_crate(c);
+        ret = decl_macro::strip_macro_imports(expander.cx.resolver, ret);
+        ret = decl_macro::apply_hygiene(&expander.cx.decl_macros, expander.cx.resolver, ret);
         ret.exported_macros = expander.cx.exported_macros.clone();
+        ret.exported_macros.extend(expander.cx.resolver.exported_macros());
 
         if cx.parse_sess.span_diagnostic.err_count() > err_count {
             cx.parse_sess.span_diagnostic.abort_if_errors();
@@ -1482,7 +1563,7 @@ mod tests {
     use ast;
     use ast::Name;
     use codemap;
-    use ext::base::ExtCtxt;
+    use ext::base::{DummyMacroResolver, ExtCtxt};
     use ext::mtwt;
     use fold::Folder;
     use parse;
@@ -1554,7 +1635,8 @@ mod tests {
             Vec::new(), &sess).unwrap();
         // should fail:
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1569,7 +1651,8 @@ mod tests {
             src,
             Vec::new(), &sess).unwrap();
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1583,7 +1666,8 @@ mod tests {
             src,
             Vec::new(), &sess).unwrap();
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&sess, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast);
     }
 
@@ -1592,7 +1676,8 @@ mod tests {
         let crate_ast = panictry!(string_to_parser(&ps, crate_str).parse_crate_mod());
         // the cfg argument actually does matter, here...
         let mut gated_cfgs = vec![];
-        let ecx = ExtCtxt::new(&ps, vec![], test_ecfg(), &mut gated_cfgs);
+        let mut resolver = DummyMacroResolver;
+        let ecx = ExtCtxt::new(&ps, vec![], test_ecfg(), &mut gated_cfgs, &mut resolver);
         expand_crate(ecx, vec![], vec![], crate_ast).0
     }
 
@@ -93,13 +93,13 @@ pub fn expand_include<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree
         Some(f) => f,
         None => return DummyResult::expr(sp),
     };
+    let file = res_rel_file(cx, sp, Path::new(&file));
+    cx.parse_sess().track_file(&file.display().to_string(), cx.original_span());
     // The file will be added to the code map by the parser
     let p =
         parse::new_sub_parser_from_file(cx.parse_sess(),
                                         cx.cfg(),
-                                        &res_rel_file(cx,
-                                                      sp,
-                                                      Path::new(&file)),
+                                        &file,
                                         true,
                                         None,
                                         sp);
@@ -137,6 +137,7 @@ pub fn expand_include_str(cx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         None => return DummyResult::expr(sp)
     };
     let file = res_rel_file(cx, sp, Path::new(&file));
+    cx.parse_sess().track_file(&file.display().to_string(), cx.original_span());
     let mut bytes = Vec::new();
     match File::open(&file).and_then(|mut f| f.read_to_end(&mut bytes)) {
         Ok(..) => {}
@@ -174,6 +175,7 @@ pub fn expand_include_bytes(cx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         None => return DummyResult::expr(sp)
     };
     let file = res_rel_file(cx, sp, Path::new(&file));
+    cx.parse_sess().track_file(&file.display().to_string(), cx.original_span());
     let mut bytes = Vec::new();
     match File::open(&file).and_then(|mut f| f.read_to_end(&mut bytes)) {
         Err(e) => {
@@ -79,7 +79,6 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Option<u32>, Status
     ("unboxed_closures", "1.0.0", Some(29625), Active),
     ("reflect", "1.0.0", Some(27749), Active),
     ("import_shadowing", "1.0.0", None, Removed),
-    ("advanced_slice_patterns", "1.0.0", Some(23121), Active),
     ("tuple_indexing", "1.0.0", None, Accepted),
     ("associated_types", "1.0.0", None, Accepted),
     ("visible_private_types", "1.0.0", None, Removed),
@@ -168,9 +167,6 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Option<u32>, Status
     // rustc internal
     ("allow_internal_unstable", "1.0.0", None, Active),
 
-    // #23121. Array patterns have some hazards yet.
-    ("slice_patterns", "1.0.0", Some(23121), Active),
-
     // Allows use of unary negate on unsigned integers, e.g. -e for e: u8
     ("negate_unsigned", "1.0.0", Some(29645), Removed),
 
@@ -223,6 +219,10 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Option<u32>, Status
     // allow overloading augmented assignment operations like `a += b`
     ("augmented_assignments", "1.8.0", Some(28235), Accepted),
 
+    // Allows slice and array patterns, with subslices anywhere in them
+    ("slice_patterns", "1.10.0", Some(23121), Accepted),
+    ("advanced_slice_patterns", "1.10.0", Some(23121), Accepted),
+
     // allow `#[no_debug]`
     ("no_debug", "1.5.0", Some(29721), Active),
 
@@ -259,6 +259,18 @@ const KNOWN_FEATURES: &'static [(&'static str, &'static str, Option<u32>, Status
 
     // pub(restricted) visibilities (RFC 1422)
     ("pub_restricted", "1.9.0", Some(32409), Active),
+
+    // The `proc-macro` crate type and `#[proc_macro_derive]`
+    ("proc_macro", "1.10.0", Some(35900), Active),
+
+    // Declarative macros 2.0 (`macro` items)
+    ("decl_macro", "1.10.0", Some(39412), Active),
+
+    // `format_args!("{x}")` using a local `x`
+    ("format_args_capture", "1.10.0", None, Active),
+
+    // Attributes, such as `#[cfg]`, on parameters and generic parameters
+    ("param_attrs", "1.10.0", None, Active),
 ];
 // (changing above list without updating src/doc/reference.md makes @cmr sad)
 
@@ -395,6 +407,14 @@ pub const KNOWN_ATTRIBUTES: &'static [(&'static str, AttributeType, AttributeGat
                                   "the `#[linked_from]` attribute \
                                    is an experimental feature")),
 
+    ("proc_macro_derive", Normal, Gated("proc_macro",
+                                        "the `#[proc_macro_derive]` attribute \
+                                         is an experimental feature")),
+    ("rustc_derive_registrar", Normal, Gated("rustc_attrs",
+                                             "the `#[rustc_derive_registrar]` attribute \
+                                              is used internally by the compiler \
+                                              and will never be stable")),
+
     // FIXME: #14408 whitelist docs since rustdoc looks at them
     ("doc", Whitelisted, Ungated),
 
@@ -477,6 +497,10 @@ const GATED_CFGS: &'static [(&'static str, &'static str, fn(&Features) -> bool)]
 pub enum GatedCfgAttr {
     GatedCfg(GatedCfg),
     GatedAttr(Span),
+    GatedParamAttr(Span),
+    /// A `cfg` name and value, which aren't gated but are checked against
+    /// the expected ones, see `config::CheckCfg`.
+    UsedCfg(Span, InternedString, Option<InternedString>),
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -494,6 +518,12 @@ impl Ord for GatedCfgAttr {
             GatedCfgAttr::GatedAttr(ref span) => {
                 (span.lo.0, span.hi.0, GATED_CFGS.len())
             }
+            GatedCfgAttr::GatedParamAttr(ref span) => {
+                (span.lo.0, span.hi.0, GATED_CFGS.len() + 1)
+            }
+            GatedCfgAttr::UsedCfg(ref span, _, _) => {
+                (span.lo.0, span.hi.0, GATED_CFGS.len() + 2)
+            }
         };
         to_tup(self).cmp(&to_tup(other))
     }
@@ -523,6 +553,16 @@ impl GatedCfgAttr {
                                      EXPLAIN_STMT_ATTR_SYNTAX);
                 }
             }
+            GatedCfgAttr::GatedParamAttr(span) => {
+                if !features.param_attrs {
+                    emit_feature_err(diagnostic,
+                                     "param_attrs",
+                                     span,
+                                     GateIssue::Language,
+                                     EXPLAIN_PARAM_ATTR_SYNTAX);
+                }
+            }
+            GatedCfgAttr::UsedCfg(..) => {}
         }
     }
 }
@@ -612,6 +652,10 @@ pub struct Features {
     pub question_mark: bool,
     pub specialization: bool,
     pub pub_restricted: bool,
+    pub proc_macro: bool,
+    pub decl_macro: bool,
+    pub format_args_capture: bool,
+    pub param_attrs: bool,
 }
 
 impl Features {
@@ -649,6 +693,10 @@ impl Features {
             question_mark: false,
             specialization: false,
             pub_restricted: false,
+            proc_macro: false,
+            decl_macro: false,
+            format_args_capture: false,
+            param_attrs: false,
         }
     }
 }
@@ -665,6 +713,9 @@ const EXPLAIN_PUSHPOP_UNSAFE: &'static str =
 const EXPLAIN_STMT_ATTR_SYNTAX: &'static str =
     "attributes on non-item statements and expressions are experimental.";
 
+const EXPLAIN_PARAM_ATTR_SYNTAX: &'static str =
+    "attributes on parameters are experimental.";
+
 pub fn check_for_box_syntax(f: Option<&Features>, diag: &Handler, span: Span) {
     if let Some(&Features { allow_box: true, .. }) = f {
         return;
@@ -815,6 +866,12 @@ pub const EXPLAIN_ALLOW_INTERNAL_UNSTABLE: &'static str =
 pub const EXPLAIN_CUSTOM_DERIVE: &'static str =
     "`#[derive]` for custom traits is not stable enough for use and is subject to change";
 
+pub const EXPLAIN_PROC_MACRO: &'static str =
+    "the `proc-macro` crate type is experimental";
+
+pub const EXPLAIN_DECL_MACRO: &'static str =
+    "`macro` is experimental";
+
 struct MacroVisitor<'a> {
     context: &'a Context<'a>
 }
@@ -832,7 +889,7 @@ impl<'a, 'v> Visitor<'v> for MacroVisitor<'a> {
         // catch uses of these macros within conditionally-compiled
         // code, e.g. `#[cfg]`-guarded functions.
 
-        if name == "asm" {
+        if name == "asm" || name == "asm_operands" {
             self.context.gate_feature("asm", path.span, EXPLAIN_ASM);
         }
 
@@ -1057,18 +1114,6 @@ impl<'a, 'v> Visitor<'v> for PostExpansionVisitor<'a> {
 
     fn visit_pat(&mut self, pattern: &ast::Pat) {
         match pattern.node {
-            PatKind::Vec(_, Some(_), ref last) if !last.is_empty() => {
-                self.gate_feature("advanced_slice_patterns",
-                                  pattern.span,
-                                  "multiple-element slice matches anywhere \
-                                   but at the end of a slice (e.g. \
-                                   `[0, ..xs, 0]`) are experimental")
-            }
-            PatKind::Vec(..) => {
-                self.gate_feature("slice_patterns",
-                                  pattern.span,
-                                  "slice pattern syntax is experimental");
-            }
             PatKind::Box(..) => {
                 self.gate_feature("box_patterns",
                                   pattern.span,
@@ -1271,6 +1316,10 @@ fn check_crate_inner<F>(cm: &CodeMap, span_handler: &Handler,
         question_mark: cx.has_feature("question_mark"),
         specialization: cx.has_feature("specialization"),
         pub_restricted: cx.has_feature("pub_restricted"),
+        proc_macro: cx.has_feature("proc_macro"),
+        decl_macro: cx.has_feature("decl_macro"),
+        format_args_capture: cx.has_feature("format_args_capture"),
+        param_attrs: cx.has_feature("param_attrs"),
     }
 }
 
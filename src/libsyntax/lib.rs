@@ -113,6 +113,7 @@ pub mod print {
 pub mod ext {
     pub mod base;
     pub mod build;
+    pub mod decl_macro;
     pub mod expand;
     pub mod mtwt;
     pub mod quote;
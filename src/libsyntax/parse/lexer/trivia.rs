@@ -0,0 +1,316 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A lossless view of a source file, for tools that rewrite code.
+//!
+//! The parser only sees the tokens that matter to the grammar, and the AST
+//! only links back to the source through spans. A `TriviaFile` keeps every
+//! byte of a file instead: each token carries the whitespace and comments
+//! before it as trivia, and the final `Eof` token carries whatever follows
+//! the last real token. The tokens can also be viewed as a tree of
+//! delimited groups, see `TriviaFile::tree`.
+//!
+//! AST nodes are attached to this layer through their spans, see
+//! `TriviaFile::tokens_in` and `TriviaFile::leading_trivia`. Changes are made
+//! with a `Rewriter`, which replaces ranges of the file and keeps everything
+//! else, including formatting and comments, byte for byte.
+
+use codemap::{BytePos, FileMap, Pos, Span, mk_sp};
+use errors::Handler;
+use parse::lexer::{Reader, StringReader, TokenAndSpan};
+use parse::token;
+
+use std::mem;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriviaKind {
+    Whitespace,
+    /// A comment other than a doc comment; doc comments are tokens.
+    Comment,
+    /// The `#!` line at the start of a file.
+    Shebang,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub sp: Span,
+}
+
+/// A token and the trivia before it.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TriviaToken {
+    pub leading: Vec<Trivia>,
+    pub tok: token::Token,
+    pub sp: Span,
+}
+
+/// The tokens of a file grouped by their delimiters.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum TriviaTree<'a> {
+    Token(&'a TriviaToken),
+    /// The opening delimiter, the trees inside and the closing delimiter,
+    /// which is missing if the file ends before it.
+    Delimited(&'a TriviaToken, Vec<TriviaTree<'a>>, Option<&'a TriviaToken>),
+}
+
+/// A source file as tokens with their trivia.
+pub struct TriviaFile {
+    filemap: Rc<FileMap>,
+    /// Ordered by position, ending with `Eof`.
+    tokens: Vec<TriviaToken>,
+}
+
+impl TriviaFile {
+    /// Lexes `filemap`, keeping its trivia. Lexical errors are reported to
+    /// `span_diagnostic`, as when parsing.
+    pub fn new(span_diagnostic: &Handler, filemap: Rc<FileMap>) -> TriviaFile {
+        let mut reader = StringReader::new(span_diagnostic, filemap.clone());
+        let mut tokens = vec![];
+        let mut leading = vec![];
+        loop {
+            let TokenAndSpan { tok, sp } = reader.next_token();
+            let kind = match tok {
+                token::Whitespace => TriviaKind::Whitespace,
+                token::Comment => TriviaKind::Comment,
+                token::Shebang(_) => TriviaKind::Shebang,
+                _ => {
+                    let is_eof = tok == token::Eof;
+                    tokens.push(TriviaToken {
+                        leading: mem::replace(&mut leading, vec![]),
+                        tok: tok,
+                        sp: sp,
+                    });
+                    if is_eof {
+                        break;
+                    }
+                    continue;
+                }
+            };
+            leading.push(Trivia { kind: kind, sp: sp });
+        }
+        TriviaFile { filemap: filemap, tokens: tokens }
+    }
+
+    pub fn filemap(&self) -> &Rc<FileMap> {
+        &self.filemap
+    }
+
+    /// All the tokens of the file, ending with `Eof`.
+    pub fn tokens(&self) -> &[TriviaToken] {
+        &self.tokens
+    }
+
+    /// The complete source of the file.
+    pub fn source(&self) -> &str {
+        self.filemap.src.as_ref().map_or("", |src| &src[..])
+    }
+
+    /// The source in `sp`, which must be within the file.
+    pub fn text(&self, sp: Span) -> &str {
+        &self.source()[self.offset(sp.lo)..self.offset(sp.hi)]
+    }
+
+    /// The tokens within `sp`, e.g. those of an AST node. The trivia before
+    /// the first of them is what precedes the node, such as its comments.
+    pub fn tokens_in(&self, sp: Span) -> &[TriviaToken] {
+        let lo = self.token_index(sp.lo);
+        let hi = self.token_index(sp.hi);
+        &self.tokens[lo..hi]
+    }
+
+    /// The whitespace and comments directly before `sp`.
+    pub fn leading_trivia(&self, sp: Span) -> &[Trivia] {
+        &self.tokens[self.token_index(sp.lo)].leading
+    }
+
+    /// The tokens grouped into trees by their delimiters. The `Eof` token
+    /// is the last tree.
+    pub fn tree(&self) -> Vec<TriviaTree> {
+        // The trees of each open delimiter, innermost last.
+        let mut open: Vec<(&TriviaToken, Vec<TriviaTree>)> = vec![];
+        let mut trees = vec![];
+        for t in &self.tokens {
+            match t.tok {
+                token::OpenDelim(_) => {
+                    open.push((t, vec![]));
+                    continue;
+                }
+                token::CloseDelim(_) if !open.is_empty() => {
+                    let (open_tok, inner) = open.pop().unwrap();
+                    let tree = TriviaTree::Delimited(open_tok, inner, Some(t));
+                    match open.last_mut() {
+                        Some(&mut (_, ref mut outer)) => outer.push(tree),
+                        None => trees.push(tree),
+                    }
+                    continue;
+                }
+                token::Eof => {
+                    // Close the delimiters the file left open.
+                    while let Some((open_tok, inner)) = open.pop() {
+                        let tree = TriviaTree::Delimited(open_tok, inner, None);
+                        match open.last_mut() {
+                            Some(&mut (_, ref mut outer)) => outer.push(tree),
+                            None => trees.push(tree),
+                        }
+                    }
+                }
+                _ => {}
+            }
+            match open.last_mut() {
+                Some(&mut (_, ref mut inner)) => inner.push(TriviaTree::Token(t)),
+                None => trees.push(TriviaTree::Token(t)),
+            }
+        }
+        trees
+    }
+
+    fn offset(&self, pos: BytePos) -> usize {
+        (pos - self.filemap.start_pos).to_usize()
+    }
+
+    // The index of the first token that starts at or after `pos`.
+    fn token_index(&self, pos: BytePos) -> usize {
+        match self.tokens.binary_search_by(|t| t.sp.lo.cmp(&pos)) {
+            Ok(i) | Err(i) => i,
+        }
+    }
+}
+
+/// Rewrites parts of a file, keeping the rest of it byte for byte.
+pub struct Rewriter<'a> {
+    file: &'a TriviaFile,
+    /// Replacements of non-overlapping ranges, in no particular order.
+    edits: Vec<(Span, String)>,
+}
+
+impl<'a> Rewriter<'a> {
+    pub fn new(file: &'a TriviaFile) -> Rewriter<'a> {
+        Rewriter { file: file, edits: vec![] }
+    }
+
+    /// Replaces the source in `sp` with `text`. Panics if `sp` overlaps a
+    /// range that was already replaced.
+    pub fn replace(&mut self, sp: Span, text: &str) {
+        assert!(self.edits.iter().all(|&(edit, _)| sp.hi <= edit.lo || edit.hi <= sp.lo),
+                "overlapping rewrite of {:?}", sp);
+        self.edits.push((sp, text.to_string()));
+    }
+
+    /// Inserts `text` at `pos`, after any text already inserted there.
+    pub fn insert(&mut self, pos: BytePos, text: &str) {
+        self.replace(mk_sp(pos, pos), text);
+    }
+
+    pub fn remove(&mut self, sp: Span) {
+        self.replace(sp, "");
+    }
+
+    /// The rewritten source of the file.
+    pub fn finish(mut self) -> String {
+        // A stable sort keeps insertions at the same position in order.
+        self.edits.sort_by_key(|&(sp, _)| (sp.lo, sp.hi));
+        let source = self.file.source();
+        let mut result = String::with_capacity(source.len());
+        let mut pos = 0;
+        for (sp, text) in self.edits {
+            let (lo, hi) = (self.file.offset(sp.lo), self.file.offset(sp.hi));
+            result.push_str(&source[pos..lo]);
+            result.push_str(&text);
+            pos = hi;
+        }
+        result.push_str(&source[pos..]);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use codemap::{BytePos, CodeMap, mk_sp};
+    use errors;
+    use parse::token;
+    use std::io;
+    use std::rc::Rc;
+
+    fn mk_sh(cm: Rc<CodeMap>) -> errors::Handler {
+        let emitter = errors::emitter::EmitterWriter::new(Box::new(io::sink()), None, cm);
+        errors::Handler::with_emitter(true, false, Box::new(emitter))
+    }
+
+    fn with_file<F: FnOnce(&TriviaFile)>(src: &str, f: F) {
+        let cm = Rc::new(CodeMap::new());
+        let sh = mk_sh(cm.clone());
+        let fm = cm.new_filemap("zebra.rs".to_string(), src.to_string());
+        f(&TriviaFile::new(&sh, fm));
+    }
+
+    #[test]
+    fn every_byte_is_kept() {
+        let src = "#!/usr/bin/env rustx\n// first\nfn main() { /* two */ f(1,  2); }\n\n";
+        with_file(src, |file| {
+            let mut text = String::new();
+            for t in file.tokens() {
+                for trivia in &t.leading {
+                    text.push_str(file.text(trivia.sp));
+                }
+                text.push_str(file.text(t.sp));
+            }
+            assert_eq!(text, src);
+        });
+    }
+
+    #[test]
+    fn comments_lead_their_node() {
+        let src = "struct A;\n\n// about b\nfn b() {}\n";
+        with_file(src, |file| {
+            let b = mk_sp(file.filemap().start_pos + BytePos(22),
+                          file.filemap().start_pos + BytePos(31));
+            assert_eq!(file.text(b), "fn b() {}");
+            let comments: Vec<_> = file.leading_trivia(b).iter()
+                .filter(|t| t.kind == TriviaKind::Comment)
+                .map(|t| file.text(t.sp))
+                .collect();
+            assert_eq!(comments, ["// about b"]);
+            assert_eq!(file.tokens_in(b).len(), 6);
+        });
+    }
+
+    #[test]
+    fn delimiters_nest() {
+        with_file("f(a, [b]) }", |file| {
+            let tree = file.tree();
+            // `f`, `(...)`, the stray `}` and `Eof`
+            assert_eq!(tree.len(), 4);
+            match tree[1] {
+                TriviaTree::Delimited(open, ref inner, Some(close)) => {
+                    assert_eq!(open.tok, token::OpenDelim(token::Paren));
+                    assert_eq!(close.tok, token::CloseDelim(token::Paren));
+                    assert_eq!(inner.len(), 3);
+                }
+                ref tree => panic!("expected a delimited tree, found {:?}", tree),
+            }
+        });
+    }
+
+    #[test]
+    fn rewrites_keep_formatting() {
+        let src = "fn  f( x: u32 ) { // keep\n    x\n}\n";
+        with_file(src, |file| {
+            let start = file.filemap().start_pos;
+            let mut rewriter = Rewriter::new(file);
+            rewriter.replace(mk_sp(start + BytePos(4), start + BytePos(5)), "g");
+            rewriter.insert(start + BytePos(0), "pub ");
+            assert_eq!(rewriter.finish(), "pub fn  g( x: u32 ) { // keep\n    x\n}\n");
+        });
+    }
+}
@@ -18,7 +18,7 @@ use parse::token::InternedString;
 use ptr::P;
 use str::char_at;
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::iter;
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -42,6 +42,17 @@ pub struct ParseSess {
     pub span_diagnostic: Handler, // better be the same as the one in the reader!
     /// Used to determine and report recursive mod inclusions
     included_mod_stack: RefCell<Vec<PathBuf>>,
+    /// Set when parser recovery drops an item or puts an error node in place
+    /// of an expression
+    incomplete_ast: Cell<bool>,
+    /// Files read by macros such as `include_str!`, for dep-info output
+    file_depinfo: RefCell<Vec<String>>,
+    /// Environment variables read by `env!` and `option_env!`, along with
+    /// the value seen (if any), for dep-info output
+    env_depinfo: RefCell<Vec<(String, Option<String>)>>,
+    /// The file or environment variable read by each of those macros, with
+    /// the source span that caused the macro to be expanded
+    input_uses: RefCell<Vec<(MacroInput, Span)>>,
     code_map: Rc<CodeMap>,
 }
 
@@ -56,6 +67,10 @@ impl ParseSess {
         ParseSess {
             span_diagnostic: handler,
             included_mod_stack: RefCell::new(vec![]),
+            incomplete_ast: Cell::new(false),
+            file_depinfo: RefCell::new(vec![]),
+            env_depinfo: RefCell::new(vec![]),
+            input_uses: RefCell::new(vec![]),
             code_map: code_map
         }
     }
@@ -63,6 +78,54 @@ impl ParseSess {
     pub fn codemap(&self) -> &CodeMap {
         &self.code_map
     }
+
+    /// Whether parser recovery left out parts of the AST. The errors have
+    /// been reported; what remains can be expanded, but not analysed.
+    pub fn ast_is_incomplete(&self) -> bool {
+        self.incomplete_ast.get()
+    }
+
+    /// Record that a macro expanded because of the source at `use_span`
+    /// read `path`, so that a change to it triggers a rebuild.
+    pub fn track_file(&self, path: &str, use_span: Span) {
+        let mut files = self.file_depinfo.borrow_mut();
+        if !files.iter().any(|f| f == path) {
+            files.push(path.to_string());
+        }
+        self.input_uses.borrow_mut().push((MacroInput::File(path.to_string()), use_span));
+    }
+
+    /// Record that a macro expanded because of the source at `use_span`
+    /// read the environment variable `name`, and whether it was set at the
+    /// time.
+    pub fn track_env_var(&self, name: &str, value: Option<&str>, use_span: Span) {
+        let mut vars = self.env_depinfo.borrow_mut();
+        if !vars.iter().any(|&(ref n, _)| n == name) {
+            vars.push((name.to_string(), value.map(|v| v.to_string())));
+        }
+        self.input_uses.borrow_mut().push((MacroInput::EnvVar(name.to_string()), use_span));
+    }
+
+    pub fn tracked_files(&self) -> Vec<String> {
+        self.file_depinfo.borrow().clone()
+    }
+
+    pub fn tracked_env_vars(&self) -> Vec<(String, Option<String>)> {
+        self.env_depinfo.borrow().clone()
+    }
+
+    /// Every read of a tracked file or environment variable, with the span
+    /// given to `track_file` or `track_env_var`.
+    pub fn tracked_input_uses(&self) -> Vec<(MacroInput, Span)> {
+        self.input_uses.borrow().clone()
+    }
+}
+
+/// A file or an environment variable read by a macro.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MacroInput {
+    File(String),
+    EnvVar(String),
 }
 
 // a bunch of utility functions of the form parse_<thing>_from_<source>
@@ -927,7 +990,8 @@ mod tests {
                                                     ),
                                             span: sp(6,7)
                                     }),
-                                        id: ast::DUMMY_NODE_ID
+                                        id: ast::DUMMY_NODE_ID,
+                                        attrs: None,
                                     }),
                                 output: ast::FunctionRetTy::Default(sp(15, 15)),
                                 variadic: false
@@ -270,6 +270,8 @@ pub struct Parser<'a> {
     pub mod_path_stack: Vec<InternedString>,
     /// Stack of open delimiters and their spans. Used for error message.
     pub open_braces: Vec<(token::DelimToken, Span)>,
+    /// The number of `{` consumed and not yet closed. Used for error recovery.
+    brace_depth: usize,
     /// Flag if this parser "owns" the directory that it is currently parsing
     /// in. This will affect how nested files are looked up.
     pub owns_directory: bool,
@@ -382,6 +384,7 @@ impl<'a> Parser<'a> {
             mod_path_stack: Vec::new(),
             filename: filename,
             open_braces: Vec::new(),
+            brace_depth: 0,
             owns_directory: true,
             root_module_name: None,
             expected_tokens: Vec::new(),
@@ -997,8 +1000,13 @@ impl<'a> Parser<'a> {
             self.bug("attempted to bump the parser past EOF (may be stuck in a loop)");
         }
 
-        if self.token == token::Eof {
-            self.last_token_eof = true;
+        match self.token {
+            token::Eof => self.last_token_eof = true,
+            token::OpenDelim(token::Brace) => self.brace_depth += 1,
+            token::CloseDelim(token::Brace) => {
+                self.brace_depth = self.brace_depth.saturating_sub(1);
+            }
+            _ => {}
         }
 
         self.last_span = self.span;
@@ -1261,7 +1269,7 @@ impl<'a> Parser<'a> {
             let lo = p.span.lo;
 
             let (name, node) = if p.eat_keyword(keywords::Type) {
-                let TyParam {ident, bounds, default, ..} = p.parse_ty_param()?;
+                let TyParam {ident, bounds, default, ..} = p.parse_ty_param(Vec::new())?;
                 p.expect(&token::Semi)?;
                 (ident, TraitItemKind::Type(bounds, default))
             } else if p.is_const_item() {
@@ -1554,6 +1562,7 @@ impl<'a> Parser<'a> {
     pub fn parse_arg_general(&mut self, require_name: bool) -> PResult<'a, Arg> {
         maybe_whole!(no_clone self, NtArg);
 
+        let attrs = self.parse_outer_attributes()?;
         let pat = if require_name || self.is_named_argument() {
             debug!("parse_arg_general parse_pat (require_name:{})",
                    require_name);
@@ -1579,6 +1588,7 @@ impl<'a> Parser<'a> {
             ty: t,
             pat: pat,
             id: ast::DUMMY_NODE_ID,
+            attrs: attrs.into_thin_attrs(),
         })
     }
 
@@ -1589,6 +1599,7 @@ impl<'a> Parser<'a> {
 
     /// Parse an argument in a lambda header e.g. |arg, arg|
     pub fn parse_fn_block_arg(&mut self) -> PResult<'a, Arg> {
+        let attrs = self.parse_outer_attributes()?;
         let pat = self.parse_pat()?;
         let t = if self.eat(&token::Colon) {
             self.parse_ty_sum()?
@@ -1602,7 +1613,8 @@ impl<'a> Parser<'a> {
         Ok(Arg {
             ty: t,
             pat: pat,
-            id: ast::DUMMY_NODE_ID
+            id: ast::DUMMY_NODE_ID,
+            attrs: attrs.into_thin_attrs(),
         })
     }
 
@@ -1963,10 +1975,19 @@ impl<'a> Parser<'a> {
 
     /// Parses `lifetime_defs = [ lifetime_defs { ',' lifetime_defs } ]` where `lifetime_def  =
     /// lifetime [':' lifetimes]`
-    pub fn parse_lifetime_defs(&mut self) -> PResult<'a, Vec<ast::LifetimeDef>> {
+    ///
+    /// With `following_attrs`, each definition can have outer attributes, and the attributes
+    /// of whatever follows the definitions are left there.
+    pub fn parse_lifetime_defs(&mut self, following_attrs: Option<&mut Vec<Attribute>>)
+                               -> PResult<'a, Vec<ast::LifetimeDef>> {
 
         let mut res = Vec::new();
         loop {
+            let attrs = if following_attrs.is_some() {
+                self.parse_outer_attributes()?
+            } else {
+                Vec::new()
+            };
             match self.token {
                 token::Lifetime(_) => {
                     let lifetime = self.parse_lifetime()?;
@@ -1977,10 +1998,14 @@ impl<'a> Parser<'a> {
                             Vec::new()
                         };
                     res.push(ast::LifetimeDef { lifetime: lifetime,
-                                                bounds: bounds });
+                                                bounds: bounds,
+                                                attrs: attrs.into_thin_attrs() });
                 }
 
                 _ => {
+                    if let Some(following_attrs) = following_attrs {
+                        *following_attrs = attrs;
+                    }
                     return Ok(res);
                 }
             }
@@ -2434,11 +2459,31 @@ impl<'a> Parser<'a> {
 
                     hi = pth.span.hi;
                     ex = ExprKind::Path(None, pth);
+                } else if self.recover_from_errors() && self.token_ends_expr() {
+                    // Leave the token to the enclosing construct and put an
+                    // error node in place of the missing expression.
+                    let (span, token_str) = (self.span, self.this_token_to_string());
+                    self.span_err(span, &format!("unexpected token: `{}`", token_str));
+                    self.sess.incomplete_ast.set(true);
+                    hi = lo;
+                    ex = ExprKind::Err;
                 } else {
                     // other literal expression
-                    let lit = self.parse_lit()?;
-                    hi = lit.span.hi;
-                    ex = ExprKind::Lit(P(lit));
+                    match self.parse_lit() {
+                        Ok(lit) => {
+                            hi = lit.span.hi;
+                            ex = ExprKind::Lit(P(lit));
+                        }
+                        Err(mut e) => {
+                            if !self.recover_from_errors() {
+                                return Err(e);
+                            }
+                            e.emit();
+                            self.sess.incomplete_ast.set(true);
+                            hi = self.last_span.hi;
+                            ex = ExprKind::Err;
+                        }
+                    }
                 }
             }
         }
@@ -3929,6 +3974,54 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Whether errors in items and expressions are reported and recovered from
+    /// instead of ending the parse. rustc only recovers with
+    /// `-Z continue-parse-after-error`; other users of the parser, such as
+    /// IDE tools, keep the handler's default and always get a recovered crate.
+    fn recover_from_errors(&self) -> bool {
+        self.sess.span_diagnostic.continue_after_error()
+    }
+
+    // Whether the current token ends an expression rather than continuing it.
+    fn token_ends_expr(&self) -> bool {
+        match self.token {
+            token::Semi | token::Comma | token::FatArrow |
+            token::CloseDelim(_) | token::Eof => true,
+            _ => false,
+        }
+    }
+
+    // Whether the current token can start an item, or an item in an impl.
+    fn token_starts_item(&self) -> bool {
+        self.token == token::Pound ||
+        [keywords::Pub, keywords::Fn, keywords::Struct, keywords::Enum, keywords::Type,
+         keywords::Trait, keywords::Impl, keywords::Mod, keywords::Use, keywords::Extern,
+         keywords::Static, keywords::Const, keywords::Unsafe]
+            .iter().any(|&kw| self.token.is_keyword(kw))
+    }
+
+    // Eat tokens after an item that started at `item_lo`, inside `brace_depth`
+    // braces, failed to parse. We stop at the start of the next item or the `}`
+    // closing the enclosing block, or at the end of the file. If the item
+    // failed at its first token, that token is skipped so that parsing makes
+    // progress. The failed item is left out of the AST.
+    fn recover_item(&mut self, item_lo: BytePos, brace_depth: usize) {
+        self.sess.incomplete_ast.set(true);
+        if self.span.lo == item_lo && self.token != token::Eof &&
+           self.token != token::CloseDelim(token::Brace) {
+            self.bump();
+        }
+        loop {
+            let at_item_boundary = self.token_starts_item() ||
+                                   self.token == token::CloseDelim(token::Brace);
+            if self.token == token::Eof ||
+               self.brace_depth == brace_depth && at_item_boundary {
+                return;
+            }
+            self.bump();
+        }
+    }
+
     fn parse_stmt_(&mut self) -> Option<Stmt> {
         self.parse_stmt_without_recovery().unwrap_or_else(|mut e| {
             e.emit();
@@ -4298,7 +4391,7 @@ impl<'a> Parser<'a> {
     }
 
     /// Matches typaram = IDENT (`?` unbound)? optbounds ( EQ ty )?
-    fn parse_ty_param(&mut self) -> PResult<'a, TyParam> {
+    fn parse_ty_param(&mut self, attrs: Vec<Attribute>) -> PResult<'a, TyParam> {
         let span = self.span;
         let ident = self.parse_ident()?;
 
@@ -4317,6 +4410,7 @@ impl<'a> Parser<'a> {
             bounds: bounds,
             default: default,
             span: span,
+            attrs: attrs.into_thin_attrs(),
         })
     }
 
@@ -4331,11 +4425,16 @@ impl<'a> Parser<'a> {
         maybe_whole!(self, NtGenerics);
 
         if self.eat(&token::Lt) {
-            let lifetime_defs = self.parse_lifetime_defs()?;
+            let mut attrs = Vec::new();
+            let lifetime_defs = self.parse_lifetime_defs(Some(&mut attrs))?;
             let mut seen_default = false;
             let ty_params = self.parse_seq_to_gt(Some(token::Comma), |p| {
+                // The attributes of the first type parameter were parsed
+                // after the lifetimes.
+                let mut param_attrs = mem::replace(&mut attrs, Vec::new());
+                param_attrs.extend(p.parse_outer_attributes()?);
                 p.forbid_lifetime()?;
-                let ty_param = p.parse_ty_param()?;
+                let ty_param = p.parse_ty_param(param_attrs)?;
                 if ty_param.default.is_some() {
                     seen_default = true;
                 } else if seen_default {
@@ -4345,6 +4444,9 @@ impl<'a> Parser<'a> {
                 }
                 Ok(ty_param)
             })?;
+            if let Some(attr) = attrs.first() {
+                self.span_err(attr.span, "expected a generic parameter after the attributes");
+            }
             Ok(ast::Generics {
                 lifetimes: lifetime_defs,
                 ty_params: ty_params,
@@ -4497,7 +4599,7 @@ impl<'a> Parser<'a> {
                     let bound_lifetimes = if self.eat_keyword(keywords::For) {
                         // Higher ranked constraint.
                         self.expect(&token::Lt)?;
-                        let lifetime_defs = self.parse_lifetime_defs()?;
+                        let lifetime_defs = self.parse_lifetime_defs(None)?;
                         self.expect_gt()?;
                         lifetime_defs
                     } else {
@@ -5125,7 +5227,20 @@ impl<'a> Parser<'a> {
 
             let mut impl_items = vec![];
             while !self.eat(&token::CloseDelim(token::Brace)) {
-                impl_items.push(self.parse_impl_item()?);
+                let (item_lo, brace_depth) = (self.span.lo, self.brace_depth);
+                match self.parse_impl_item() {
+                    Ok(item) => impl_items.push(item),
+                    Err(mut e) => {
+                        if !self.recover_from_errors() {
+                            return Err(e);
+                        }
+                        e.emit();
+                        self.recover_item(item_lo, brace_depth);
+                        if self.token == token::Eof {
+                            break;
+                        }
+                    }
+                }
             }
 
             Ok((special_idents::invalid,
@@ -5145,7 +5260,7 @@ impl<'a> Parser<'a> {
     fn parse_late_bound_lifetime_defs(&mut self) -> PResult<'a, Vec<ast::LifetimeDef>> {
         if self.eat_keyword(keywords::For) {
             self.expect(&token::Lt)?;
-            let lifetime_defs = self.parse_lifetime_defs()?;
+            let lifetime_defs = self.parse_lifetime_defs(None)?;
             self.expect_gt()?;
             Ok(lifetime_defs)
         } else {
@@ -5287,21 +5402,39 @@ impl<'a> Parser<'a> {
         self.parse_single_struct_field(vis, attrs)
     }
 
+    /// Parse `pub`, `pub(crate)`, `pub(self)`, `pub(super)`, `pub(in path)` or
+    /// nothing. `pub(path)` is only accepted when `allow_restricted` is true, since
+    /// the parenthesized path could be the start of a type in tuple struct fields.
     fn parse_visibility(&mut self, allow_restricted: bool) -> PResult<'a, Visibility> {
         if !self.eat_keyword(keywords::Pub) {
-            Ok(Visibility::Inherited)
-        } else if !allow_restricted || !self.eat(&token::OpenDelim(token::Paren)) {
-            Ok(Visibility::Public)
-        } else if self.eat_keyword(keywords::Crate) {
+            return Ok(Visibility::Inherited);
+        }
+        if !self.check(&token::OpenDelim(token::Paren)) {
+            return Ok(Visibility::Public);
+        }
+
+        let is_keyword_restriction = self.look_ahead(1, |t| {
+            t.is_keyword(keywords::Crate) || t.is_keyword(keywords::SelfValue) ||
+            t.is_keyword(keywords::Super)
+        }) && self.look_ahead(2, |t| *t == token::CloseDelim(token::Paren));
+        let is_in_path = self.look_ahead(1, |t| t.is_keyword(keywords::In));
+        if !allow_restricted && !is_keyword_restriction && !is_in_path {
+            return Ok(Visibility::Public);
+        }
+
+        self.bump();
+        if self.eat_keyword(keywords::Crate) {
             let span = self.last_span;
             self.expect(&token::CloseDelim(token::Paren))?;
-            Ok(Visibility::Crate(span))
-        } else {
-            let path = self.with_res(Restrictions::ALLOW_MODULE_PATHS,
-                                     |this| this.parse_path(NoTypesAllowed))?;
-            self.expect(&token::CloseDelim(token::Paren))?;
-            Ok(Visibility::Restricted { path: P(path), id: ast::DUMMY_NODE_ID })
+            return Ok(Visibility::Crate(span));
+        }
+        if is_in_path {
+            self.bump();
         }
+        let path = self.with_res(Restrictions::ALLOW_MODULE_PATHS,
+                                 |this| this.parse_path(NoTypesAllowed))?;
+        self.expect(&token::CloseDelim(token::Paren))?;
+        Ok(Visibility::Restricted { path: P(path), id: ast::DUMMY_NODE_ID })
     }
 
     /// Parse defaultness: DEFAULT or nothing
@@ -5316,8 +5449,19 @@ impl<'a> Parser<'a> {
     /// Given a termination token, parse all of the items in a module
     fn parse_mod_items(&mut self, term: &token::Token, inner_lo: BytePos) -> PResult<'a, Mod> {
         let mut items = vec![];
-        while let Some(item) = self.parse_item()? {
-            items.push(item);
+        loop {
+            let (item_lo, brace_depth) = (self.span.lo, self.brace_depth);
+            match self.parse_item() {
+                Ok(Some(item)) => items.push(item),
+                Ok(None) => break,
+                Err(mut e) => {
+                    if !self.recover_from_errors() {
+                        return Err(e);
+                    }
+                    e.emit();
+                    self.recover_item(item_lo, brace_depth);
+                }
+            }
         }
 
         if !self.eat(term) {
@@ -6006,9 +6150,63 @@ impl<'a> Parser<'a> {
                                     maybe_append(attrs, extra_attrs));
             return Ok(Some(item));
         }
+        if self.eat_keyword(keywords::Macro) {
+            // MACRO 2.0 ITEM
+            let (ident, item_) = self.parse_item_macro_def()?;
+            let last_span = self.last_span;
+            let item = self.mk_item(lo,
+                                    last_span.hi,
+                                    ident,
+                                    item_,
+                                    visibility,
+                                    attrs);
+            return Ok(Some(item));
+        }
         self.parse_macro_use_or_failure(attrs,macros_allowed,attributes_allowed,lo,visibility)
     }
 
+    /// Parse a `macro` item, either with a single rule,
+    /// `macro foo($e:expr) { ... }`, or with several comma-separated ones,
+    /// `macro foo { ($e:expr) => { ... }, () => { ... } }`.
+    ///
+    /// Like `macro_rules!`, the definition is represented as an item macro
+    /// invocation, of the `macro` keyword, whose token trees are the rules in
+    /// `macro_rules!` syntax; the expander then takes care of scoping it.
+    fn parse_item_macro_def(&mut self) -> PResult<'a, (Ident, ItemKind)> {
+        let mac_lo = self.last_span.lo;
+        let ident = self.parse_ident()?;
+        let tts = match self.token {
+            token::OpenDelim(token::Brace) => {
+                match self.parse_token_tree()? {
+                    TokenTree::Delimited(_, ref delimed) => {
+                        delimed.tts.iter().map(|tt| match *tt {
+                            TokenTree::Token(sp, token::Comma) => {
+                                TokenTree::Token(sp, token::Semi)
+                            }
+                            ref tt => tt.clone(),
+                        }).collect()
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            token::OpenDelim(token::Paren) => {
+                let args = self.parse_token_tree()?;
+                let arrow_span = self.span;
+                if self.token != token::OpenDelim(token::Brace) {
+                    return self.unexpected();
+                }
+                let body = self.parse_token_tree()?;
+                vec![args, TokenTree::Token(arrow_span, token::FatArrow), body]
+            }
+            _ => return self.unexpected(),
+        };
+
+        let span = mk_sp(mac_lo, self.last_span.hi);
+        let path = ast::Path::from_ident(span, token::str_to_ident("macro"));
+        let m = Mac_ { path: path, tts: tts, ctxt: EMPTY_CTXT };
+        Ok((ident, ItemKind::Mac(codemap::Spanned { node: m, span: span })))
+    }
+
     /// Parse a foreign item.
     fn parse_foreign_item(&mut self) -> PResult<'a, Option<ForeignItem>> {
         let attrs = self.parse_outer_attributes()?;
@@ -79,7 +79,10 @@ pub struct BreakToken {
 #[derive(Clone, Copy)]
 pub struct BeginToken {
     offset: isize,
-    breaks: Breaks
+    breaks: Breaks,
+    /// Whether `offset` is relative to the indentation of the line the block
+    /// begins on rather than to the column it begins at.
+    block: bool,
 }
 
 #[derive(Clone)]
@@ -186,7 +189,8 @@ pub fn mk_printer<'a>(out: Box<io::Write+'a>, linewidth: usize) -> Printer<'a> {
         top: 0,
         bottom: 0,
         print_stack: Vec::new(),
-        pending_indentation: 0
+        pending_indentation: 0,
+        line_indentation: 0,
     }
 }
 
@@ -302,6 +306,8 @@ pub struct Printer<'a> {
     print_stack: Vec<PrintStackElem> ,
     /// Buffered indentation to avoid writing trailing whitespace
     pending_indentation: isize,
+    /// Indentation of the current line
+    line_indentation: isize,
 }
 
 impl<'a> Printer<'a> {
@@ -511,6 +517,7 @@ impl<'a> Printer<'a> {
         debug!("NEWLINE {}", amount);
         let ret = write!(self.out, "\n");
         self.pending_indentation = 0;
+        self.line_indentation = amount;
         self.indent(amount);
         return ret;
     }
@@ -548,7 +555,11 @@ impl<'a> Printer<'a> {
         match token {
           Token::Begin(b) => {
             if l > self.space {
-                let col = self.margin - self.space + b.offset;
+                let col = if b.block {
+                    self.line_indentation + b.offset
+                } else {
+                    self.margin - self.space + b.offset
+                };
                 debug!("print Begin -> push broken block at col {}", col);
                 self.print_stack.push(PrintStackElem {
                     offset: col,
@@ -624,7 +635,19 @@ impl<'a> Printer<'a> {
 pub fn rbox(p: &mut Printer, indent: usize, b: Breaks) -> io::Result<()> {
     p.pretty_print(Token::Begin(BeginToken {
         offset: indent as isize,
-        breaks: b
+        breaks: b,
+        block: false,
+    }))
+}
+
+/// Like `rbox`, but when the box is broken its contents are indented relative
+/// to the indentation of the line the box starts on, not the column it starts
+/// at. This gives block indentation instead of visual alignment.
+pub fn bbox(p: &mut Printer, indent: usize, b: Breaks) -> io::Result<()> {
+    p.pretty_print(Token::Begin(BeginToken {
+        offset: indent as isize,
+        breaks: b,
+        block: true,
     }))
 }
 
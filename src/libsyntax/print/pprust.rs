@@ -67,6 +67,8 @@ pub struct State<'a> {
     cur_cmnt_and_lit: CurrentCommentAndLiteral,
     boxes: Vec<pp::Breaks>,
     ann: &'a (PpAnn+'a),
+    /// Whether the output is meant to replace the source, see `format_crate`.
+    formatting: bool,
 }
 
 pub fn rust_printer<'a>(writer: Box<Write+'a>) -> State<'a> {
@@ -87,6 +89,7 @@ pub fn rust_printer_annotated<'a>(writer: Box<Write+'a>,
         },
         boxes: Vec::new(),
         ann: ann,
+        formatting: false,
     }
 }
 
@@ -94,6 +97,9 @@ pub const INDENT_UNIT: usize = 4;
 
 pub const DEFAULT_COLUMNS: usize = 78;
 
+/// The default maximum line width of `format_crate`.
+pub const DEFAULT_FORMAT_COLUMNS: usize = 100;
+
 /// Requires you to pass an input filename and reader so that
 /// it can scan the input text for comments and literals to
 /// copy forward.
@@ -136,6 +142,41 @@ pub fn print_crate<'a>(cm: &'a CodeMap,
     eof(&mut s.s)
 }
 
+/// Prints a crate as source code meant to replace its input, for use as a
+/// formatter. Unlike `print_crate`, comments inside macro invocations and
+/// after function arguments stay where they were written, lines are kept
+/// within `max_width` columns where possible by breaking method call chains
+/// and block indenting argument lists, and formatting the output again
+/// doesn't change it.
+pub fn format_crate<'a>(cm: &'a CodeMap,
+                        span_diagnostic: &errors::Handler,
+                        krate: &ast::Crate,
+                        filename: String,
+                        input: &mut Read,
+                        out: Box<Write+'a>,
+                        max_width: usize) -> io::Result<()> {
+    static NO_ANN: NoAnn = NoAnn;
+    let (cmnts, lits) = comments::gather_comments_and_literals(span_diagnostic,
+                                                               filename,
+                                                               input);
+    let mut s = State {
+        s: pp::mk_printer(out, max_width),
+        cm: Some(cm),
+        comments: Some(cmnts),
+        literals: Some(lits),
+        cur_cmnt_and_lit: CurrentCommentAndLiteral {
+            cur_cmnt: 0,
+            cur_lit: 0
+        },
+        boxes: Vec::new(),
+        ann: &NO_ANN,
+        formatting: true,
+    };
+    s.print_mod(&krate.module, &krate.attrs)?;
+    s.print_remaining_comments()?;
+    eof(&mut s.s)
+}
+
 impl<'a> State<'a> {
     pub fn new_from_input(cm: &'a CodeMap,
                           span_diagnostic: &errors::Handler,
@@ -176,6 +217,7 @@ impl<'a> State<'a> {
             },
             boxes: Vec::new(),
             ann: ann,
+            formatting: false,
         }
     }
 }
@@ -436,11 +478,27 @@ pub fn visibility_qualified(vis: &ast::Visibility, s: &str) -> String {
     match *vis {
         ast::Visibility::Public => format!("pub {}", s),
         ast::Visibility::Crate(_) => format!("pub(crate) {}", s),
-        ast::Visibility::Restricted { ref path, .. } => format!("pub({}) {}", path, s),
+        ast::Visibility::Restricted { ref path, .. } => {
+            format!("{} {}", restricted_visibility_to_string(path), s)
+        }
         ast::Visibility::Inherited => s.to_string()
     }
 }
 
+/// Prints `pub(self)` and `pub(super)` in their short form and any other
+/// restriction as `pub(in path)`.
+fn restricted_visibility_to_string(path: &ast::Path) -> String {
+    let is_keyword = !path.global && path.segments.len() == 1 && {
+        let name = path.segments[0].identifier.name;
+        name == token::keywords::SelfValue.to_name() || name == token::keywords::Super.to_name()
+    };
+    if is_keyword {
+        format!("pub({})", path)
+    } else {
+        format!("pub(in {})", path)
+    }
+}
+
 fn needs_parentheses(expr: &ast::Expr) -> bool {
     match expr.node {
         ast::ExprKind::Assign(..) | ast::ExprKind::Binary(..) |
@@ -458,6 +516,9 @@ pub trait PrintState<'a> {
     fn cur_cmnt_and_lit(&mut self) -> &mut CurrentCommentAndLiteral;
     fn literals(&self) -> &Option<Vec<comments::Literal>>;
 
+    /// Whether the output is meant to replace the source, see `format_crate`.
+    fn formatting(&self) -> bool { false }
+
     fn word_space(&mut self, w: &str) -> io::Result<()> {
         word(self.writer(), w)?;
         space(self.writer())
@@ -565,7 +626,13 @@ pub trait PrintState<'a> {
                 assert_eq!(cmnt.lines.len(), 1);
                 zerobreak(self.writer())?;
                 word(self.writer(), &cmnt.lines[0])?;
-                zerobreak(self.writer())
+                // When formatting, keep the comment apart from the token
+                // after it, as in `f(/* x */ 1)`.
+                if self.formatting() {
+                    space(self.writer())
+                } else {
+                    zerobreak(self.writer())
+                }
             }
             comments::Isolated => {
                 self.hardbreak_if_not_bol()?;
@@ -817,6 +884,10 @@ impl<'a> PrintState<'a> for State<'a> {
     fn literals(&self) -> &Option<Vec<comments::Literal>> {
         &self.literals
     }
+
+    fn formatting(&self) -> bool {
+        self.formatting
+    }
 }
 
 impl<'a> State<'a> {
@@ -842,6 +913,42 @@ impl<'a> State<'a> {
         Ok(())
     }
 
+    // "block box", see `pp::bbox`
+    fn bbox(&mut self, u: usize, b: pp::Breaks) -> io::Result<()> {
+        self.boxes.push(b);
+        pp::bbox(&mut self.s, u, b)
+    }
+
+    // When formatting, a parenthesized list that doesn't fit on the line is
+    // broken after the `(`, block indented and closed on a line of its own.
+    fn popen_list(&mut self) -> io::Result<()> {
+        self.popen()?;
+        if self.formatting {
+            self.bbox(INDENT_UNIT, Consistent)?;
+            zerobreak(&mut self.s)?;
+        }
+        Ok(())
+    }
+
+    // Comments in front of `close_pos` stay inside the list, and the `)` goes
+    // on a line of its own after them.
+    fn pclose_list(&mut self, close_pos: Option<BytePos>) -> io::Result<()> {
+        if self.formatting {
+            if let Some(pos) = close_pos {
+                self.maybe_print_comment(pos)?;
+            }
+            self.break_offset_if_not_bol(0, -(INDENT_UNIT as isize))?;
+            self.end()?;
+        }
+        self.pclose()
+    }
+
+    // The elements of a list that doesn't fit on one line get a line each
+    // when formatting.
+    fn list_breaks(&self) -> Breaks {
+        if self.formatting { Consistent } else { Inconsistent }
+    }
+
     pub fn bopen(&mut self) -> io::Result<()> {
         word(&mut self.s, "{")?;
         self.end() // close the head-box
@@ -911,7 +1018,10 @@ impl<'a> State<'a> {
         let len = elts.len();
         let mut i = 0;
         for elt in elts {
-            self.maybe_print_comment(get_span(elt).hi)?;
+            // When formatting, comments inside an element are printed where
+            // they are instead of all in front of it.
+            let span = get_span(elt);
+            self.maybe_print_comment(if self.formatting { span.lo } else { span.hi })?;
             op(self, elt)?;
             i += 1;
             if i < len {
@@ -1313,6 +1423,23 @@ impl<'a> State<'a> {
                 }
                 self.bclose(item.span)?;
             }
+            ast::ItemKind::Mac(codemap::Spanned { ref node, .. })
+                    if node.path.segments.len() == 1 &&
+                       node.path.segments[0].identifier.name ==
+                           token::keywords::Macro.to_name() => {
+                // a `macro` item; its rules are stored in `macro_rules!` form
+                self.print_visibility(&item.vis)?;
+                self.word_nbsp("macro")?;
+                self.print_ident(item.ident)?;
+                self.nbsp()?;
+                self.bopen()?;
+                let tts = node.tts.iter().map(|tt| match *tt {
+                    TokenTree::Token(sp, token::Semi) => TokenTree::Token(sp, token::Comma),
+                    ref tt => tt.clone(),
+                }).collect::<Vec<_>>();
+                self.print_tts(&tts[..])?;
+                self.bclose(item.span)?;
+            }
             ast::ItemKind::Mac(codemap::Spanned { ref node, .. }) => {
                 self.print_visibility(&item.vis)?;
                 self.print_path(&node.path, false, 0)?;
@@ -1388,7 +1515,7 @@ impl<'a> State<'a> {
             ast::Visibility::Public => self.word_nbsp("pub"),
             ast::Visibility::Crate(_) => self.word_nbsp("pub(crate)"),
             ast::Visibility::Restricted { ref path, .. } =>
-                self.word_nbsp(&format!("pub({})", path)),
+                self.word_nbsp(&restricted_visibility_to_string(path)),
             ast::Visibility::Inherited => Ok(())
         }
     }
@@ -1463,6 +1590,9 @@ impl<'a> State<'a> {
                 word(&mut self.s, &token_to_string(&delimed.open_token()))?;
                 space(&mut self.s)?;
                 self.print_tts(&delimed.tts)?;
+                if self.formatting {
+                    self.maybe_print_comment(delimed.close_span.lo)?;
+                }
                 space(&mut self.s)?;
                 word(&mut self.s, &token_to_string(&delimed.close_token()))
             },
@@ -1493,6 +1623,9 @@ impl<'a> State<'a> {
             if i != 0 && !suppress_space {
                 space(&mut self.s)?;
             }
+            if self.formatting {
+                self.maybe_print_comment(tt.get_span().lo)?;
+            }
             self.print_tt(tt)?;
             // There should be no space between the module name and the following `::` in paths,
             // otherwise imported macros get re-parsed from crate metadata incorrectly (#20701)
@@ -1763,7 +1896,7 @@ impl<'a> State<'a> {
         self.print_path(&m.node.path, false, 0)?;
         word(&mut self.s, "!")?;
         match delim {
-            token::Paren => self.popen()?,
+            token::Paren => self.popen_list()?,
             token::Bracket => word(&mut self.s, "[")?,
             token::Brace => {
                 self.head("")?;
@@ -1771,18 +1904,24 @@ impl<'a> State<'a> {
             }
         }
         self.print_tts(&m.node.tts)?;
+        if self.formatting && delim == token::Bracket {
+            // Comments after the last token tree belong inside the delimiters.
+            self.maybe_print_comment(m.span.hi)?;
+        }
         match delim {
-            token::Paren => self.pclose(),
+            token::Paren => self.pclose_list(Some(m.span.hi)),
             token::Bracket => word(&mut self.s, "]"),
             token::Brace => self.bclose(m.span),
         }
     }
 
 
-    fn print_call_post(&mut self, args: &[P<ast::Expr>]) -> io::Result<()> {
-        self.popen()?;
-        self.commasep_exprs(Inconsistent, args)?;
-        self.pclose()
+    fn print_call_post(&mut self, args: &[P<ast::Expr>],
+                       close_pos: BytePos) -> io::Result<()> {
+        self.popen_list()?;
+        let breaks = self.list_breaks();
+        self.commasep_exprs(breaks, args)?;
+        self.pclose_list(Some(close_pos))
     }
 
     pub fn check_expr_bin_needs_paren(&mut self, sub_expr: &ast::Expr,
@@ -1896,17 +2035,23 @@ impl<'a> State<'a> {
 
     fn print_expr_call(&mut self,
                        func: &ast::Expr,
-                       args: &[P<ast::Expr>]) -> io::Result<()> {
+                       args: &[P<ast::Expr>],
+                       close_pos: BytePos) -> io::Result<()> {
         self.print_expr_maybe_paren(func)?;
-        self.print_call_post(args)
+        self.print_call_post(args, close_pos)
     }
 
     fn print_expr_method_call(&mut self,
                               ident: ast::SpannedIdent,
                               tys: &[P<ast::Ty>],
-                              args: &[P<ast::Expr>]) -> io::Result<()> {
+                              args: &[P<ast::Expr>],
+                              close_pos: BytePos) -> io::Result<()> {
         let base_args = &args[1..];
         self.print_expr(&args[0])?;
+        if self.formatting {
+            // Long method call chains are broken before the `.`.
+            zerobreak(&mut self.s)?;
+        }
         word(&mut self.s, ".")?;
         self.print_ident(ident.node)?;
         if !tys.is_empty() {
@@ -1915,7 +2060,7 @@ impl<'a> State<'a> {
                           |s, ty| s.print_type(&ty))?;
             word(&mut self.s, ">")?;
         }
-        self.print_call_post(base_args)
+        self.print_call_post(base_args, close_pos)
     }
 
     fn print_expr_binary(&mut self,
@@ -1990,10 +2135,10 @@ impl<'a> State<'a> {
                 self.print_expr_tup(&exprs[..], attrs)?;
             }
             ast::ExprKind::Call(ref func, ref args) => {
-                self.print_expr_call(&func, &args[..])?;
+                self.print_expr_call(&func, &args[..], expr.span.hi)?;
             }
             ast::ExprKind::MethodCall(ident, ref tys, ref args) => {
-                self.print_expr_method_call(ident, &tys[..], &args[..])?;
+                self.print_expr_method_call(ident, &tys[..], &args[..], expr.span.hi)?;
             }
             ast::ExprKind::Binary(op, ref lhs, ref rhs) => {
                 self.print_expr_binary(op, &lhs, &rhs)?;
@@ -2277,6 +2422,11 @@ impl<'a> State<'a> {
                 self.print_expr(e)?;
                 word(&mut self.s, "?")?
             }
+            ast::ExprKind::Err => {
+                self.popen()?;
+                word(&mut self.s, "/*ERROR*/")?;
+                self.pclose()?
+            }
         }
         self.ann.post(self, NodeExpr(expr))?;
         self.end()
@@ -2669,7 +2819,8 @@ impl<'a> State<'a> {
                          is_closure: bool) -> io::Result<()> {
         // It is unfortunate to duplicate the commasep logic, but we want the
         // self type and the args all in the same box.
-        self.rbox(0, Inconsistent)?;
+        let breaks = if is_closure { Inconsistent } else { self.list_breaks() };
+        self.rbox(0, breaks)?;
         let mut first = true;
         if let Some(explicit_self) = opt_explicit_self {
             let m = match *explicit_self {
@@ -2689,9 +2840,29 @@ impl<'a> State<'a> {
             &decl.inputs[1..]
         };
 
+        let mut prev_arg: Option<&ast::Arg> = None;
         for arg in args {
-            if first { first = false; } else { self.word_space(",")?; }
+            if first {
+                first = false;
+            } else {
+                word(&mut self.s, ",")?;
+                match prev_arg {
+                    Some(prev_arg) if self.formatting => {
+                        self.maybe_print_trailing_comment(arg_span(prev_arg),
+                                                          Some(arg.pat.span.lo))?;
+                    }
+                    _ => {}
+                }
+                self.space_if_not_bol()?;
+            }
             self.print_arg(arg, is_closure)?;
+            prev_arg = Some(arg);
+        }
+        match prev_arg {
+            Some(last_arg) if self.formatting && !is_closure => {
+                self.maybe_print_trailing_comment(arg_span(last_arg), None)?;
+            }
+            _ => {}
         }
 
         self.end()
@@ -2700,12 +2871,12 @@ impl<'a> State<'a> {
     pub fn print_fn_args_and_ret(&mut self, decl: &ast::FnDecl,
                                  opt_explicit_self: Option<&ast::SelfKind>)
         -> io::Result<()> {
-        self.popen()?;
+        self.popen_list()?;
         self.print_fn_args(decl, opt_explicit_self, false)?;
         if decl.variadic {
             word(&mut self.s, ", ...")?;
         }
-        self.pclose()?;
+        self.pclose_list(None)?;
 
         self.print_fn_output(decl)
     }
@@ -2790,6 +2961,7 @@ impl<'a> State<'a> {
                               lifetime: &ast::LifetimeDef)
                               -> io::Result<()>
     {
+        self.print_outer_attributes_inline(lifetime.attrs.as_attr_slice())?;
         self.print_lifetime(&lifetime.lifetime)?;
         let mut sep = ":";
         for v in &lifetime.bounds {
@@ -2832,6 +3004,7 @@ impl<'a> State<'a> {
     }
 
     pub fn print_ty_param(&mut self, param: &ast::TyParam) -> io::Result<()> {
+        self.print_outer_attributes_inline(param.attrs.as_attr_slice())?;
         self.print_ident(param.ident)?;
         self.print_bounds(":", &param.bounds)?;
         match param.default {
@@ -2962,6 +3135,7 @@ impl<'a> State<'a> {
 
     pub fn print_arg(&mut self, input: &ast::Arg, is_closure: bool) -> io::Result<()> {
         self.ibox(INDENT_UNIT)?;
+        self.print_outer_attributes_inline(input.attrs.as_attr_slice())?;
         match input.ty.node {
             ast::TyKind::Infer if is_closure => self.print_pat(&input.pat)?,
             _ => {
@@ -3137,6 +3311,14 @@ impl<'a> State<'a> {
 
 fn repeat(s: &str, n: usize) -> String { iter::repeat(s).take(n).collect() }
 
+// The span of a function argument, which doesn't record one itself.
+fn arg_span(arg: &ast::Arg) -> codemap::Span {
+    match arg.ty.node {
+        ast::TyKind::Infer => arg.pat.span,
+        _ => codemap::mk_sp(arg.pat.span.lo, arg.ty.span.hi),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -10,7 +10,7 @@
 
 use ast::Name;
 use std::cmp;
-use parse::token::InternedString;
+use parse::token::{intern_and_get_ident, InternedString};
 
 /// To find the Levenshtein distance between two strings
 pub fn lev_distance(a: &str, b: &str) -> usize {
@@ -50,12 +50,23 @@ pub fn find_best_match_for_name<'a, T>(iter_names: T,
                                        lookup: &str,
                                        dist: Option<usize>) -> Option<InternedString>
     where T: Iterator<Item = &'a Name> {
+    let names = iter_names.map(|name| name.as_str()).collect::<Vec<_>>();
+    find_best_match_for_str(names.iter().map(|name| &name[..]), lookup, dist)
+        .map(intern_and_get_ident)
+}
+
+/// Like `find_best_match_for_name`, for candidates that are plain strings
+/// rather than interned names, e.g. the names of lints.
+pub fn find_best_match_for_str<'a, T>(candidates: T,
+                                      lookup: &str,
+                                      dist: Option<usize>) -> Option<&'a str>
+    where T: Iterator<Item = &'a str> {
     let max_dist = dist.map_or_else(|| cmp::max(lookup.len(), 3) / 3, |d| d);
-    iter_names
-    .filter_map(|name| {
-        let dist = lev_distance(lookup, &name.as_str());
+    candidates
+    .filter_map(|candidate| {
+        let dist = lev_distance(lookup, candidate);
         match dist <= max_dist {    // filter the unwanted cases
-            true => Some((name.as_str(), dist)),
+            true => Some((candidate, dist)),
             false => None,
         }
     })
@@ -69,9 +69,11 @@ pub trait Visitor<'v> : Sized {
     fn visit_expr_post(&mut self, _ex: &'v Expr) { }
     fn visit_ty(&mut self, t: &'v Ty) { walk_ty(self, t) }
     fn visit_generics(&mut self, g: &'v Generics) { walk_generics(self, g) }
+    fn visit_ty_param(&mut self, t: &'v TyParam) { walk_ty_param(self, t) }
     fn visit_fn(&mut self, fk: FnKind<'v>, fd: &'v FnDecl, b: &'v Block, s: Span, _: NodeId) {
         walk_fn(self, fk, fd, b, s)
     }
+    fn visit_arg(&mut self, a: &'v Arg) { walk_arg(self, a) }
     fn visit_trait_item(&mut self, ti: &'v TraitItem) { walk_trait_item(self, ti) }
     fn visit_impl_item(&mut self, ii: &'v ImplItem) { walk_impl_item(self, ii) }
     fn visit_trait_ref(&mut self, t: &'v TraitRef) { walk_trait_ref(self, t) }
@@ -194,6 +196,7 @@ pub fn walk_lifetime_def<'v, V: Visitor<'v>>(visitor: &mut V,
                                               lifetime_def: &'v LifetimeDef) {
     visitor.visit_lifetime(&lifetime_def.lifetime);
     walk_list!(visitor, visit_lifetime, &lifetime_def.bounds);
+    walk_list!(visitor, visit_attribute, lifetime_def.attrs.as_attr_slice());
 }
 
 pub fn walk_explicit_self<'v, V: Visitor<'v>>(visitor: &mut V,
@@ -498,11 +501,7 @@ pub fn walk_ty_param_bound<'v, V: Visitor<'v>>(visitor: &mut V,
 }
 
 pub fn walk_generics<'v, V: Visitor<'v>>(visitor: &mut V, generics: &'v Generics) {
-    for param in &generics.ty_params {
-        visitor.visit_ident(param.span, param.ident);
-        walk_list!(visitor, visit_ty_param_bound, &param.bounds);
-        walk_list!(visitor, visit_ty, &param.default);
-    }
+    walk_list!(visitor, visit_ty_param, &generics.ty_params);
     walk_list!(visitor, visit_lifetime_def, &generics.lifetimes);
     for predicate in &generics.where_clause.predicates {
         match *predicate {
@@ -537,14 +536,24 @@ pub fn walk_fn_ret_ty<'v, V: Visitor<'v>>(visitor: &mut V, ret_ty: &'v FunctionR
     }
 }
 
+pub fn walk_ty_param<'v, V: Visitor<'v>>(visitor: &mut V, param: &'v TyParam) {
+    visitor.visit_ident(param.span, param.ident);
+    walk_list!(visitor, visit_ty_param_bound, &param.bounds);
+    walk_list!(visitor, visit_ty, &param.default);
+    walk_list!(visitor, visit_attribute, param.attrs.as_attr_slice());
+}
+
 pub fn walk_fn_decl<'v, V: Visitor<'v>>(visitor: &mut V, function_declaration: &'v FnDecl) {
-    for argument in &function_declaration.inputs {
-        visitor.visit_pat(&argument.pat);
-        visitor.visit_ty(&argument.ty)
-    }
+    walk_list!(visitor, visit_arg, &function_declaration.inputs);
     walk_fn_ret_ty(visitor, &function_declaration.output)
 }
 
+pub fn walk_arg<'v, V: Visitor<'v>>(visitor: &mut V, argument: &'v Arg) {
+    visitor.visit_pat(&argument.pat);
+    visitor.visit_ty(&argument.ty);
+    walk_list!(visitor, visit_attribute, argument.attrs.as_attr_slice());
+}
+
 pub fn walk_fn_kind<'v, V: Visitor<'v>>(visitor: &mut V,
                                         function_kind: FnKind<'v>) {
     match function_kind {
@@ -803,6 +812,7 @@ pub fn walk_expr<'v, V: Visitor<'v>>(visitor: &mut V, expression: &'v Expr) {
         ExprKind::Try(ref subexpression) => {
             visitor.visit_expr(subexpression)
         }
+        ExprKind::Err => {}
     }
 
     visitor.visit_expr_post(expression)
@@ -237,14 +237,7 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         }
     }
 
-    let expn_id = cx.codemap().record_expansion(codemap::ExpnInfo {
-        call_site: sp,
-        callee: codemap::NameAndSpan {
-            format: codemap::MacroBang(intern("asm")),
-            span: None,
-            allow_internal_unstable: false,
-        },
-    });
+    let expn_id = asm_expn_id(cx, sp);
 
     MacEager::expr(P(ast::Expr {
         id: ast::DUMMY_NODE_ID,
@@ -258,8 +251,22 @@ pub fn expand_asm<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
             alignstack: alignstack,
             dialect: dialect,
             expn_id: expn_id,
+            operand_classes: vec![],
         }),
         span: sp,
         attrs: None,
     }))
 }
+
+/// Records the expansion of the `asm!` at `sp`, which lets errors from LLVM
+/// about the assembly be mapped back to it.
+pub fn asm_expn_id(cx: &ExtCtxt, sp: Span) -> codemap::ExpnId {
+    cx.codemap().record_expansion(codemap::ExpnInfo {
+        call_site: sp,
+        callee: codemap::NameAndSpan {
+            format: codemap::MacroBang(intern("asm")),
+            span: None,
+            allow_internal_unstable: false,
+        },
+    })
+}
@@ -0,0 +1,863 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Inline assembly with typed operands:
+//!
+//! ```ignore
+//! asm_operands!("add {0}, {step}", inout(reg) x, step = in(reg) 4);
+//! ```
+//!
+//! The template refers to operands the way a format string does, with `{}`,
+//! `{0}` or `{name}`, and `{0:e}` picks how a register is printed. The
+//! operands are
+//!
+//! - `in(<reg>) expr`,
+//! - `out(<reg>) place` and `lateout(<reg>) place`, where a late output
+//!   may share a register with an input, and the place can be `_` for an
+//!   explicit register that is just clobbered,
+//! - `inout(<reg>) place`, or `inout(<reg>) expr => place` to put the
+//!   result elsewhere, where the place can be `_` to discard it,
+//! - `const expr` for an immediate,
+//!
+//! where `<reg>` is a register class such as `reg`, or an explicit register
+//! such as `"eax"`. Explicit registers are implied by the operand, so they
+//! can't appear in the template. A final `options(...)` can hold `pure`,
+//! `nomem`, `readonly`, `preserves_flags`, `nostack` and `att_syntax`.
+//! Unlike `asm!`, the assembly is volatile unless it is `pure`, and uses
+//! Intel syntax on x86.
+//!
+//! Registers are checked against the target architecture, currently x86_64
+//! and aarch64, and everything is lowered to the LLVM constraints `asm!`
+//! spells out by hand. The class of each operand is kept with the lowered
+//! assembly, and the types of the operands are checked against it after
+//! type checking, in `middle::intrinsicck`.
+
+use asm;
+use format;
+
+use fmt_macros as parse;
+
+use syntax::ast;
+use syntax::ast::{AsmDialect, AsmOperandClass};
+use syntax::attr::AttrMetaMethods;
+use syntax::codemap::{Span, mk_sp};
+use syntax::ext::base::*;
+use syntax::ext::base;
+use syntax::ext::build::AstBuilder;
+use syntax::feature_gate;
+use syntax::parse::parser::{Parser, PathParsingMode};
+use syntax::parse::token::{self, InternedString};
+use syntax::ptr::P;
+
+use std::collections::HashMap;
+
+/// A class of registers an operand can be allocated to.
+struct RegClass {
+    name: &'static str,
+    /// The LLVM constraint for the class.
+    constraint: &'static str,
+    /// The template modifiers of the class, each with the LLVM modifier it
+    /// stands for.
+    modifiers: &'static [(char, char)],
+}
+
+const X86_64_CLASSES: &'static [RegClass] = &[
+    RegClass {
+        name: "reg",
+        constraint: "r",
+        modifiers: &[('l', 'b'), ('x', 'w'), ('e', 'k'), ('r', 'q')],
+    },
+    RegClass {
+        name: "reg_abcd",
+        constraint: "Q",
+        modifiers: &[('l', 'b'), ('h', 'h'), ('x', 'w'), ('e', 'k'), ('r', 'q')],
+    },
+    RegClass { name: "reg_byte", constraint: "q", modifiers: &[] },
+    RegClass { name: "xmm_reg", constraint: "x", modifiers: &[('x', 'x'), ('y', 't')] },
+    RegClass { name: "ymm_reg", constraint: "x", modifiers: &[('x', 'x'), ('y', 't')] },
+];
+
+const AARCH64_CLASSES: &'static [RegClass] = &[
+    RegClass { name: "reg", constraint: "r", modifiers: &[('w', 'w'), ('x', 'x')] },
+    RegClass {
+        name: "vreg",
+        constraint: "w",
+        modifiers: &[('b', 'b'), ('h', 'h'), ('s', 's'), ('d', 'd'), ('q', 'q')],
+    },
+    RegClass {
+        name: "vreg_low16",
+        constraint: "x",
+        modifiers: &[('b', 'b'), ('h', 'h'), ('s', 's'), ('d', 'd'), ('q', 'q')],
+    },
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Arch {
+    X86_64,
+    AArch64,
+}
+
+impl Arch {
+    fn classes(self) -> &'static [RegClass] {
+        match self {
+            Arch::X86_64 => X86_64_CLASSES,
+            Arch::AArch64 => AARCH64_CLASSES,
+        }
+    }
+
+    /// Identifies the explicit register `name` by a number shared with the
+    /// registers overlapping it. Fails with what the register is if it
+    /// can't be an operand, and gives `None` for unknown registers.
+    fn register(self, name: &str) -> Result<Option<usize>, &'static str> {
+        match self {
+            Arch::X86_64 => x86_64_register(name),
+            Arch::AArch64 => aarch64_register(name),
+        }
+    }
+
+    /// The name of the class the explicit register `name`, numbered `n` by
+    /// `register`, belongs to.
+    fn explicit_class(self, name: &str, n: usize) -> &'static str {
+        match self {
+            Arch::X86_64 if n < 16 => {
+                let byte = name.ends_with('l') || name.ends_with('h') ||
+                           name.starts_with('r') && name.ends_with('b');
+                if byte { "reg_byte" } else { "reg" }
+            }
+            Arch::X86_64 if name.starts_with("ymm") => "ymm_reg",
+            Arch::X86_64 => "xmm_reg",
+            Arch::AArch64 if n < 32 => "reg",
+            Arch::AArch64 => "vreg",
+        }
+    }
+}
+
+fn x86_64_register(name: &str) -> Result<Option<usize>, &'static str> {
+    const LEGACY: &'static [&'static [&'static str]] = &[
+        &["rax", "eax", "ax", "al", "ah"],
+        &["rbx", "ebx", "bx", "bl", "bh"],
+        &["rcx", "ecx", "cx", "cl", "ch"],
+        &["rdx", "edx", "dx", "dl", "dh"],
+        &["rsi", "esi", "si", "sil"],
+        &["rdi", "edi", "di", "dil"],
+    ];
+    match name {
+        "rsp" | "esp" | "sp" | "spl" => return Err("the stack pointer"),
+        "rbp" | "ebp" | "bp" | "bpl" => return Err("the frame pointer"),
+        "rip" | "eip" => return Err("the instruction pointer"),
+        _ => {}
+    }
+    if let Some(i) = LEGACY.iter().position(|names| names.contains(&name)) {
+        return Ok(Some(i));
+    }
+    for suffix in &["", "d", "w", "b"] {
+        if name.ends_with(suffix) {
+            match register_number(&name[..name.len() - suffix.len()], "r") {
+                Some(n) if 8 <= n && n < 16 => return Ok(Some(n)),
+                _ => {}
+            }
+        }
+    }
+    match register_number(name, "xmm").or_else(|| register_number(name, "ymm")) {
+        Some(n) if n < 16 => Ok(Some(16 + n)),
+        _ => Ok(None),
+    }
+}
+
+fn aarch64_register(name: &str) -> Result<Option<usize>, &'static str> {
+    match name {
+        "sp" | "wsp" => return Err("the stack pointer"),
+        "x29" | "w29" | "fp" => return Err("the frame pointer"),
+        "xzr" | "wzr" => return Err("the zero register"),
+        "x18" | "w18" => return Err("the platform register"),
+        "x19" | "w19" => return Err("a register reserved by LLVM"),
+        "lr" => return Ok(Some(30)),
+        _ => {}
+    }
+    match register_number(name, "x").or_else(|| register_number(name, "w")) {
+        Some(n) if n < 31 => return Ok(Some(n)),
+        _ => {}
+    }
+    for prefix in &["v", "q", "d", "s", "h", "b"] {
+        match register_number(name, prefix) {
+            Some(n) if n < 32 => return Ok(Some(32 + n)),
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+/// Parses `name` as `prefix` followed by a decimal number.
+fn register_number(name: &str, prefix: &str) -> Option<usize> {
+    if !name.starts_with(prefix) {
+        return None;
+    }
+    let digits = &name[prefix.len()..];
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(10)) ||
+       digits.len() > 1 && digits.starts_with('0') {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+enum Reg {
+    Class(&'static RegClass),
+    /// An explicit register, with the number from `Arch::register`.
+    Explicit(InternedString, usize),
+}
+
+impl Reg {
+    fn constraint(&self) -> String {
+        match *self {
+            Reg::Class(class) => class.constraint.to_string(),
+            Reg::Explicit(ref name, _) => format!("{{{}}}", name),
+        }
+    }
+}
+
+enum OperandKind {
+    In(Reg, P<ast::Expr>),
+    /// `out`, or `lateout` if the flag is set. There is no place for `_`.
+    Out(Reg, Option<P<ast::Expr>>, bool),
+    /// `inout` with the place, or with an expression and the place after
+    /// `=>`, if any.
+    InOut(Reg, P<ast::Expr>, Option<Option<P<ast::Expr>>>),
+    Const(P<ast::Expr>),
+}
+
+struct Operand {
+    kind: OperandKind,
+    span: Span,
+}
+
+impl Operand {
+    fn reg(&self) -> Option<&Reg> {
+        match self.kind {
+            OperandKind::In(ref reg, _) |
+            OperandKind::Out(ref reg, _, _) |
+            OperandKind::InOut(ref reg, _, _) => Some(reg),
+            OperandKind::Const(..) => None,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Options {
+    pure: bool,
+    nomem: bool,
+    readonly: bool,
+    preserves_flags: bool,
+    nostack: bool,
+    att_syntax: bool,
+}
+
+const OPTIONS: &'static [&'static str] = &[
+    "pure", "nomem", "readonly", "preserves_flags", "nostack", "att_syntax",
+];
+
+/// Where the value of an operand goes among the LLVM operands.
+#[derive(Clone, Copy)]
+enum Slot {
+    Output(usize),
+    Input(usize),
+}
+
+/// An operand as the template sees it.
+struct TemplateOperand {
+    span: Span,
+    /// `None` for explicit registers that are only clobbered.
+    slot: Option<Slot>,
+    /// The class of a register operand that isn't explicit.
+    class: Option<&'static RegClass>,
+    explicit: bool,
+}
+
+pub fn expand_asm_operands<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
+                                -> Box<base::MacResult+'cx> {
+    if !cx.ecfg.enable_asm() {
+        feature_gate::emit_feature_err(
+            &cx.parse_sess.span_diagnostic, "asm", sp,
+            feature_gate::GateIssue::Language,
+            feature_gate::EXPLAIN_ASM);
+        return DummyResult::expr(sp);
+    }
+
+    let target_arch = cx.cfg.iter()
+        .find(|mi| mi.name() == "target_arch")
+        .and_then(|mi| mi.value_str());
+    let arch = match target_arch.as_ref().map(|arch| &arch[..]) {
+        Some("x86_64") => Arch::X86_64,
+        Some("aarch64") => Arch::AArch64,
+        _ => {
+            cx.span_err(sp, "`asm!` operands are not supported on this target");
+            return DummyResult::expr(sp);
+        }
+    };
+
+    let mut p = cx.new_parser_from_tts(tts);
+    let template = panictry!(p.parse_expr());
+    let fmtsp = template.span;
+    let (template, style) = match expr_to_string(cx, template,
+                                                 "inline assembly must be a string literal") {
+        Some(template) => template,
+        None => return DummyResult::expr(sp),
+    };
+
+    let mut operands: Vec<Operand> = vec![];
+    let mut names = HashMap::new();
+    let mut options = Options::default();
+    let mut options_span = None;
+    // The explicit registers of inputs and of outputs, by number.
+    let mut input_regs = HashMap::new();
+    let mut output_regs = HashMap::new();
+    let mut valid = true;
+
+    while p.eat(&token::Comma) {
+        if p.token == token::Eof {
+            break;
+        }
+        if let Some(options_span) = options_span {
+            cx.struct_span_err(p.span, "operands can't follow the options")
+              .span_note(options_span, "the options are here")
+              .emit();
+            return DummyResult::expr(sp);
+        }
+        let lo = p.span.lo;
+        if token_name(&p).map_or(false, |name| name == "options") &&
+           p.look_ahead(1, |t| *t == token::OpenDelim(token::Paren)) {
+            if parse_options(cx, &mut p, &mut options).is_err() {
+                return DummyResult::expr(sp);
+            }
+            options_span = Some(mk_sp(lo, p.last_span.hi));
+            continue;
+        }
+
+        let name = if p.token.is_ident() && p.look_ahead(1, |t| *t == token::Eq) {
+            let name = token_name(&p).unwrap();
+            p.bump();
+            p.bump();
+            Some(name)
+        } else {
+            if !names.is_empty() {
+                cx.span_err(p.span, "positional operands cannot follow named operands");
+                valid = false;
+            }
+            None
+        };
+
+        let kind = match parse_operand(cx, &mut p, arch) {
+            Ok(Some(kind)) => kind,
+            Ok(None) => {
+                valid = false;
+                continue;
+            }
+            Err(()) => return DummyResult::expr(sp),
+        };
+        let operand = Operand { kind: kind, span: mk_sp(lo, p.last_span.hi) };
+
+        if let Some(&Reg::Explicit(ref reg, n)) = operand.reg() {
+            let (is_input, is_output) = match operand.kind {
+                OperandKind::In(..) => (true, false),
+                OperandKind::Out(..) => (false, true),
+                _ => (true, true),
+            };
+            if is_input && !check_conflict(cx, &mut input_regs, reg, n, operand.span) {
+                valid = false;
+            }
+            if is_output && !check_conflict(cx, &mut output_regs, reg, n, operand.span) {
+                valid = false;
+            }
+        }
+
+        if let Some(name) = name {
+            if let Some(&prev) = names.get(&name[..]) {
+                let prev: &Operand = &operands[prev];
+                cx.struct_span_err(operand.span,
+                                   &format!("duplicate argument named `{}`", name))
+                  .span_note(prev.span, "previously here")
+                  .emit();
+                valid = false;
+                continue;
+            }
+            names.insert(name.to_string(), operands.len());
+        }
+        operands.push(operand);
+    }
+    if p.token != token::Eof {
+        cx.span_err(p.span, &format!("expected `,`, found `{}`", p.this_token_to_string()));
+        return DummyResult::expr(sp);
+    }
+    if !valid {
+        return DummyResult::expr(sp);
+    }
+
+    // Lower the operands to LLVM constraints: the outputs come first, and
+    // the inputs after them.
+    let mut outputs = vec![];
+    let mut inputs = vec![];
+    let mut output_classes = vec![];
+    let mut input_classes = vec![];
+    let mut clobbers = vec![];
+    let mut stmts = vec![];
+    let mut lowered = vec![];
+    let positional = operands.len() - names.len();
+    for (i, operand) in operands.into_iter().enumerate() {
+        let span = operand.span;
+        let (class, explicit) = match operand.reg() {
+            Some(&Reg::Class(class)) => (Some(class), false),
+            Some(&Reg::Explicit(..)) => (None, true),
+            None => (None, false),
+        };
+        let operand_class = match operand.reg() {
+            Some(&Reg::Class(class)) => AsmOperandClass::Reg(InternedString::new(class.name)),
+            Some(&Reg::Explicit(ref name, n)) => {
+                AsmOperandClass::Reg(InternedString::new(arch.explicit_class(name, n)))
+            }
+            None => AsmOperandClass::Const,
+        };
+        let output = |constraint: String, expr, is_rw| {
+            ast::InlineAsmOutput {
+                constraint: token::intern_and_get_ident(&constraint),
+                expr: expr,
+                is_rw: is_rw,
+                is_indirect: false,
+            }
+        };
+        let slot = match operand.kind {
+            OperandKind::In(reg, expr) => {
+                inputs.push((token::intern_and_get_ident(&reg.constraint()), expr));
+                input_classes.push(operand_class);
+                Some(Slot::Input(inputs.len() - 1))
+            }
+            OperandKind::Out(Reg::Explicit(name, _), None, _) => {
+                clobbers.push(name);
+                None
+            }
+            OperandKind::Out(reg, place, late) => {
+                // Unless it is late, an output can't share a register with
+                // an input. Explicit registers don't need to be told.
+                let early = match reg {
+                    Reg::Class(_) if !late => "&",
+                    _ => "",
+                };
+                let place = place.expect("`_` output of a register class");
+                outputs.push(output(format!("={}{}", early, reg.constraint()), place, false));
+                output_classes.push(operand_class);
+                Some(Slot::Output(outputs.len() - 1))
+            }
+            OperandKind::InOut(reg, place, None) => {
+                outputs.push(output(format!("={}", reg.constraint()), place, true));
+                output_classes.push(operand_class);
+                Some(Slot::Output(outputs.len() - 1))
+            }
+            OperandKind::InOut(reg, expr, Some(Some(place))) => {
+                // The input is tied to the output by its index.
+                let index = outputs.len();
+                outputs.push(output(format!("={}", reg.constraint()), place, false));
+                inputs.push((token::intern_and_get_ident(&index.to_string()), expr));
+                output_classes.push(operand_class.clone());
+                input_classes.push(operand_class);
+                Some(Slot::Output(index))
+            }
+            OperandKind::InOut(reg, expr, Some(None)) => {
+                // The result goes to a temporary that is never read.
+                let tmp = cx.ident_of(&format!("__asm_discard{}", i));
+                stmts.push(cx.stmt_let(span, true, tmp, expr));
+                outputs.push(output(format!("={}", reg.constraint()),
+                                    cx.expr_ident(span, tmp),
+                                    true));
+                output_classes.push(operand_class);
+                Some(Slot::Output(outputs.len() - 1))
+            }
+            OperandKind::Const(expr) => {
+                inputs.push((InternedString::new("i"), expr));
+                input_classes.push(operand_class);
+                Some(Slot::Input(inputs.len() - 1))
+            }
+        };
+        lowered.push(TemplateOperand {
+            span: span,
+            slot: slot,
+            class: class,
+            explicit: explicit,
+        });
+    }
+    let num_outputs = outputs.len();
+
+    let fmt_offset = format::find_fmt_offset(cx, fmtsp, &template, style);
+    let mut parser = parse::Parser::new(&template);
+    let mut asm = String::new();
+    let mut used = vec![false; lowered.len()];
+    let mut next_arg = 0;
+    while let Some(piece) = parser.next() {
+        if !parser.errors.is_empty() {
+            break;
+        }
+        let arg = match piece {
+            parse::String(s) => {
+                // `$` starts an operand in LLVM templates.
+                asm.push_str(&s.replace("$", "$$"));
+                continue;
+            }
+            parse::NextArgument(arg) => arg,
+        };
+        let place = format::placeholder_span(fmtsp, fmt_offset,
+                                             *parser.arg_places.last().unwrap());
+
+        let index = match arg.position {
+            parse::ArgumentNext | parse::ArgumentIs(_) => {
+                let i = match arg.position {
+                    parse::ArgumentIs(i) => i,
+                    _ => {
+                        next_arg += 1;
+                        next_arg - 1
+                    }
+                };
+                if i >= positional {
+                    let given = match positional {
+                        0 => "no arguments given".to_string(),
+                        1 => "there is 1 argument".to_string(),
+                        n => format!("there are {} arguments", n),
+                    };
+                    cx.span_err(place, &format!("invalid reference to argument `{}` ({})",
+                                                i, given));
+                    valid = false;
+                    continue;
+                }
+                i
+            }
+            parse::ArgumentNamed(name) => match names.get(name) {
+                Some(&i) => i,
+                None => {
+                    cx.span_err(place, &format!("there is no argument named `{}`", name));
+                    valid = false;
+                    continue;
+                }
+            },
+        };
+        used[index] = true;
+        let operand = &lowered[index];
+
+        let modifier_only = parse::FormatSpec {
+            fill: None,
+            align: parse::AlignUnknown,
+            flags: 0,
+            precision: parse::CountImplied,
+            width: parse::CountImplied,
+            ty: arg.format.ty,
+        };
+        if arg.format != modifier_only {
+            cx.span_err(place, "asm template placeholders can only have a modifier, \
+                                as in `{0:e}`");
+            valid = false;
+            continue;
+        }
+        if operand.explicit {
+            cx.struct_span_err(place, "explicit registers can't be used in the template")
+              .span_note(operand.span, "the register is given here")
+              .emit();
+            valid = false;
+            continue;
+        }
+
+        let modifier = match (arg.format.ty, operand.class) {
+            // Immediates are printed bare, without the `$` of AT&T syntax
+            // or the `offset` of Intel syntax.
+            ("", None) if arch == Arch::X86_64 => Some('c'),
+            ("", _) => None,
+            (m, None) => {
+                cx.span_err(place, &format!("invalid modifier `{}`: `const` operands have \
+                                             no modifiers", m));
+                valid = false;
+                continue;
+            }
+            (m, Some(class)) => {
+                match class.modifiers.iter().find(|&&(c, _)| m.len() == 1 && m.starts_with(c)) {
+                    Some(&(_, llvm)) => Some(llvm),
+                    None => {
+                        let mut err = cx.struct_span_err(place,
+                            &format!("invalid modifier `{}` for register class `{}`",
+                                     m, class.name));
+                        if class.modifiers.is_empty() {
+                            err.help("the class has no modifiers");
+                        } else {
+                            let modifiers: Vec<_> = class.modifiers.iter()
+                                .map(|&(c, _)| format!("`{}`", c))
+                                .collect();
+                            err.help(&format!("the valid modifiers are {}",
+                                              modifiers.join(", ")));
+                        }
+                        err.emit();
+                        valid = false;
+                        continue;
+                    }
+                }
+            }
+        };
+        let llvm_index = match operand.slot {
+            Some(Slot::Output(i)) => i,
+            Some(Slot::Input(i)) => num_outputs + i,
+            None => unreachable!(),
+        };
+        match modifier {
+            Some(m) => asm.push_str(&format!("${{{}:{}}}", llvm_index, m)),
+            None => asm.push_str(&format!("${{{}}}", llvm_index)),
+        }
+    }
+    if !parser.errors.is_empty() {
+        cx.span_err(fmtsp, &format!("invalid asm template: {}", parser.errors.remove(0)));
+        return DummyResult::expr(sp);
+    }
+    for (operand, &used) in lowered.iter().zip(&used) {
+        if !used && !operand.explicit {
+            cx.span_err(operand.span, "argument never used");
+            valid = false;
+        }
+    }
+
+    if let Some(options_span) = options_span {
+        if options.nomem && options.readonly {
+            cx.span_err(options_span,
+                        "the `nomem` and `readonly` options are mutually exclusive");
+            valid = false;
+        }
+        if options.pure && outputs.is_empty() {
+            cx.span_err(options_span,
+                        "asm with the `pure` option must have at least one output");
+            valid = false;
+        }
+        if options.pure && !options.nomem && !options.readonly {
+            cx.span_err(options_span,
+                        "the `pure` option must be combined with `nomem` or `readonly`");
+            valid = false;
+        }
+        if options.att_syntax && arch != Arch::X86_64 {
+            cx.span_err(options_span, "the `att_syntax` option is only supported on x86");
+            valid = false;
+        }
+    }
+    if !valid {
+        return DummyResult::expr(sp);
+    }
+
+    if !options.nomem && !options.readonly {
+        clobbers.push(InternedString::new("memory"));
+    }
+    // x86 always clobbers the flags, see `trans::asm`.
+    if arch == Arch::AArch64 && !options.preserves_flags {
+        clobbers.push(InternedString::new("cc"));
+    }
+    let dialect = if arch == Arch::X86_64 && !options.att_syntax {
+        AsmDialect::Intel
+    } else {
+        AsmDialect::Att
+    };
+
+    let expr = P(ast::Expr {
+        id: ast::DUMMY_NODE_ID,
+        node: ast::ExprKind::InlineAsm(ast::InlineAsm {
+            asm: token::intern_and_get_ident(&asm),
+            asm_str_style: style,
+            outputs: outputs,
+            inputs: inputs,
+            clobbers: clobbers,
+            volatile: !options.pure,
+            alignstack: !options.nostack,
+            dialect: dialect,
+            expn_id: asm::asm_expn_id(cx, sp),
+            operand_classes: output_classes.into_iter().chain(input_classes).collect(),
+        }),
+        span: sp,
+        attrs: None,
+    });
+    if stmts.is_empty() {
+        MacEager::expr(expr)
+    } else {
+        MacEager::expr(cx.expr_block(cx.block(sp, stmts, Some(expr))))
+    }
+}
+
+/// The name of an identifier token, including keywords such as `in`.
+fn token_name(p: &Parser) -> Option<InternedString> {
+    match p.token {
+        token::Ident(ident, _) => Some(ident.name.as_str()),
+        _ => None,
+    }
+}
+
+/// Parses an operand after its name, if any. Gives `None` for operands with
+/// errors that were reported, and fails if the operand can't be parsed.
+fn parse_operand(cx: &ExtCtxt, p: &mut Parser, arch: Arch)
+                 -> Result<Option<OperandKind>, ()> {
+    let word = match token_name(p) {
+        Some(word) => word,
+        None => {
+            cx.span_err(p.span, &format!("expected an operand, found `{}`",
+                                         p.this_token_to_string()));
+            return Err(());
+        }
+    };
+    match &word[..] {
+        "in" | "out" | "lateout" | "inout" => {}
+        "const" => {
+            p.bump();
+            return Ok(Some(OperandKind::Const(panictry!(p.parse_expr()))));
+        }
+        "sym" => {
+            // LLVM has no operand for the address of a symbol that works in
+            // every relocation model, so `sym` is reserved for now.
+            let span = p.span;
+            p.bump();
+            panictry!(p.parse_path(PathParsingMode::LifetimeAndTypesWithColons));
+            cx.struct_span_err(mk_sp(span.lo, p.last_span.hi),
+                               "`sym` operands are not supported")
+              .help("pass the address of the function with `in(reg)` instead")
+              .emit();
+            return Ok(None);
+        }
+        _ => {
+            cx.span_err(p.span, &format!("expected one of `in`, `out`, `lateout`, `inout` \
+                                          or `const`, found `{}`", word));
+            return Err(());
+        }
+    }
+    p.bump();
+    panictry!(p.expect(&token::OpenDelim(token::Paren)));
+    let reg = parse_reg(cx, p, arch);
+    panictry!(p.expect(&token::CloseDelim(token::Paren)));
+
+    let kind = match &word[..] {
+        "in" => {
+            let expr = panictry!(p.parse_expr());
+            reg.map(|reg| OperandKind::In(reg, expr))
+        }
+        "out" | "lateout" => {
+            let place = if p.eat(&token::Underscore) {
+                None
+            } else {
+                Some(panictry!(p.parse_expr()))
+            };
+            match reg {
+                Some(Reg::Class(class)) if place.is_none() => {
+                    cx.span_err(p.last_span,
+                                &format!("`_` can't be the output of register class `{}`, \
+                                          only of explicit registers", class.name));
+                    None
+                }
+                reg => reg.map(|reg| OperandKind::Out(reg, place, word == "lateout")),
+            }
+        }
+        _ => {
+            let expr = panictry!(p.parse_expr());
+            let place = if p.eat(&token::FatArrow) {
+                if p.eat(&token::Underscore) {
+                    Some(None)
+                } else {
+                    Some(Some(panictry!(p.parse_expr())))
+                }
+            } else {
+                None
+            };
+            reg.map(|reg| OperandKind::InOut(reg, expr, place))
+        }
+    };
+    Ok(kind)
+}
+
+/// Parses a register class or an explicit register, reporting the ones
+/// that don't exist on `arch`.
+fn parse_reg(cx: &ExtCtxt, p: &mut Parser, arch: Arch) -> Option<Reg> {
+    let span = p.span;
+    if let Some(name) = token_name(p) {
+        p.bump();
+        return match arch.classes().iter().find(|class| class.name == &name[..]) {
+            Some(class) => Some(Reg::Class(class)),
+            None => {
+                let classes: Vec<_> = arch.classes().iter()
+                    .map(|class| format!("`{}`", class.name))
+                    .collect();
+                cx.struct_span_err(span, &format!("invalid register class `{}`", name))
+                  .help(&format!("the register classes are {}", classes.join(", ")))
+                  .emit();
+                None
+            }
+        };
+    }
+    let (name, _) = panictry!(p.parse_str());
+    match arch.register(&name) {
+        Ok(Some(n)) => Some(Reg::Explicit(name, n)),
+        Ok(None) => {
+            cx.span_err(span, &format!("invalid register `{}`", name));
+            None
+        }
+        Err(what) => {
+            cx.span_err(span, &format!("invalid register `{}`: {} cannot be used as an \
+                                        operand", name, what));
+            None
+        }
+    }
+}
+
+/// Parses `options(...)` into `options`. Fails if an option is unknown.
+fn parse_options(cx: &ExtCtxt, p: &mut Parser, options: &mut Options) -> Result<(), ()> {
+    let mut result = Ok(());
+    p.bump();
+    panictry!(p.expect(&token::OpenDelim(token::Paren)));
+    while p.token != token::CloseDelim(token::Paren) {
+        let name = match token_name(p) {
+            Some(name) => name,
+            None => {
+                cx.span_err(p.span, &format!("expected an option, found `{}`",
+                                             p.this_token_to_string()));
+                return Err(());
+            }
+        };
+        match &name[..] {
+            "pure" => options.pure = true,
+            "nomem" => options.nomem = true,
+            "readonly" => options.readonly = true,
+            "preserves_flags" => options.preserves_flags = true,
+            "nostack" => options.nostack = true,
+            "att_syntax" => options.att_syntax = true,
+            _ => {
+                let options: Vec<_> = OPTIONS.iter().map(|o| format!("`{}`", o)).collect();
+                cx.struct_span_err(p.span, &format!("unknown option `{}`", name))
+                  .help(&format!("the options are {}", options.join(", ")))
+                  .emit();
+                result = Err(());
+            }
+        }
+        p.bump();
+        if !p.eat(&token::Comma) {
+            break;
+        }
+    }
+    panictry!(p.expect(&token::CloseDelim(token::Paren)));
+    result
+}
+
+/// Records the explicit register `reg`, numbered `n`, unless it overlaps a
+/// register in `regs`, which is reported.
+fn check_conflict(cx: &ExtCtxt,
+                  regs: &mut HashMap<usize, (InternedString, Span)>,
+                  reg: &InternedString,
+                  n: usize,
+                  span: Span)
+                  -> bool {
+    if let Some(&(ref prev, prev_span)) = regs.get(&n) {
+        cx.struct_span_err(span, &format!("register `{}` conflicts with register `{}`",
+                                          reg, prev))
+          .span_note(prev_span, &format!("register `{}` is used here", prev))
+          .emit();
+        return false;
+    }
+    regs.insert(n, (reg.clone(), span));
+    true
+}
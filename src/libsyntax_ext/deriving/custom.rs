@@ -0,0 +1,121 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! `#[derive]` modes defined by `proc-macro` crates.
+//!
+//! The item being derived is pretty-printed and handed to the derive
+//! function as a `TokenStream`; the stream it returns is parsed back as a
+//! sequence of items that are added next to the original one. No AST types
+//! cross the boundary, so derive crates do not depend on `libsyntax`.
+
+use std::panic;
+
+use proc_macro::TokenStream;
+use syntax::ast::{ItemKind, MetaItem};
+use syntax::attr::AttrMetaMethods;
+use syntax::codemap::Span;
+use syntax::ext::base::{Annotatable, ExtCtxt, MultiItemDecorator};
+use syntax::parse::{self, token};
+use syntax::print::pprust;
+
+pub struct CustomDerive {
+    inner: fn(TokenStream) -> TokenStream,
+}
+
+impl CustomDerive {
+    pub fn new(inner: fn(TokenStream) -> TokenStream) -> CustomDerive {
+        CustomDerive { inner: inner }
+    }
+}
+
+impl MultiItemDecorator for CustomDerive {
+    fn expand(&self,
+              cx: &mut ExtCtxt,
+              span: Span,
+              meta_item: &MetaItem,
+              item: &Annotatable,
+              push: &mut FnMut(Annotatable)) {
+        let item = match *item {
+            Annotatable::Item(ref item) => item.clone(),
+            _ => {
+                cx.span_err(span, "custom derive attributes may only be \
+                                   applied to struct/enum items");
+                return
+            }
+        };
+        match item.node {
+            ItemKind::Struct(..) |
+            ItemKind::Enum(..) => {}
+            _ => {
+                cx.span_err(span, "custom derive attributes may only be \
+                                   applied to struct/enum items");
+                return
+            }
+        }
+
+        // `#[derive(A, B)]` has been split into `#[derive_A] #[derive_B]`
+        // by now; none of those are meaningful to the derive function.
+        let item = item.map(|mut item| {
+            item.attrs.retain(|a| !a.name().starts_with("derive_"));
+            item
+        });
+
+        let trait_name = meta_item.name();
+        let trait_name = trait_name.trim_left_matches("derive_");
+
+        let input = match pprust::item_to_string(&item).parse::<TokenStream>() {
+            Ok(input) => input,
+            Err(_) => {
+                cx.span_bug(span, "pretty-printed item has unbalanced delimiters")
+            }
+        };
+
+        let inner = self.inner;
+        let output = match panic::catch_unwind(panic::AssertUnwindSafe(|| inner(input))) {
+            Ok(output) => output,
+            Err(e) => {
+                let mut err = cx.struct_span_err(span,
+                                                 &format!("custom derive `{}` panicked",
+                                                          trait_name));
+                if let Some(s) = e.downcast_ref::<String>() {
+                    err.help(&format!("message: {}", s));
+                } else if let Some(s) = e.downcast_ref::<&'static str>() {
+                    err.help(&format!("message: {}", s));
+                }
+                err.emit();
+                return
+            }
+        };
+
+        let mut parser = parse::new_parser_from_source_str(cx.parse_sess,
+                                                           cx.cfg(),
+                                                           format!("<derive({})>", trait_name),
+                                                           output.to_string());
+        while parser.token != token::Eof {
+            match parser.parse_item() {
+                Ok(Some(item)) => push(Annotatable::Item(item)),
+                Ok(None) => {
+                    cx.span_err(span,
+                                &format!("custom derive `{}` produced something other \
+                                          than items",
+                                         trait_name));
+                    return
+                }
+                Err(mut err) => {
+                    err.span_note(span,
+                                  &format!("in the output of custom derive `{}`",
+                                           trait_name));
+                    err.emit();
+                    return
+                }
+            }
+        }
+    }
+}
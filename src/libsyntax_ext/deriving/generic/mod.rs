@@ -258,6 +258,12 @@ pub struct MethodDef<'a> {
     // Is it an `unsafe fn`?
     pub is_unsafe: bool,
 
+    /// Can the bodies for all fieldless enum variants be shared? This is
+    /// the case when `combine_substructure` builds the same expression for
+    /// each of them, i.e. doesn't look at the variant itself, and saves
+    /// a match arm per variant, e.g. for large C-like enums.
+    pub unify_fieldless_variants: bool,
+
     pub combine_substructure: RefCell<CombineSubstructureFunc<'a>>,
 }
 
@@ -1130,12 +1136,19 @@ impl<'a> MethodDef<'a> {
         let catch_all_substructure = EnumNonMatchingCollapsed(
             self_arg_idents, &variants[..], &vi_idents[..]);
 
+        // The first fieldless variant, whose body stands for those of all the
+        // fieldless variants if they are unified.
+        let first_fieldless = variants.iter().enumerate()
+            .find(|&(_, v)| v.node.data.fields().is_empty());
+        let unify_fieldless = self.unify_fieldless_variants && first_fieldless.is_some();
+
         // These arms are of the form:
         // (Variant1, Variant1, ...) => Body1
         // (Variant2, Variant2, ...) => Body2
         // ...
         // where each tuple has length = self_args.len()
         let mut match_arms: Vec<ast::Arm> = variants.iter().enumerate()
+            .filter(|&(_, v)| !(unify_fieldless && v.node.data.fields().is_empty()))
             .map(|(index, variant)| {
                 let mk_self_pat = |cx: &mut ExtCtxt, self_arg_name: &str| {
                     let (p, idents) = trait_.create_enum_variant_pattern(
@@ -1218,6 +1231,22 @@ impl<'a> MethodDef<'a> {
 
                 cx.arm(sp, vec![single_pat], arm_expr)
             }).collect();
+
+        // The fieldless variants, if unified, are all matched by a final
+        // `_` arm:
+        // _ => BodyF
+        // which is built from the first of them, as they have no fields.
+        let unified_arm = first_fieldless.and_then(|(index, variant)| {
+            if !unify_fieldless {
+                return None;
+            }
+            let substructure = EnumMatching(index, variant, Vec::new());
+            let arm_expr = self.call_substructure_method(
+                cx, trait_, type_ident, &self_args[..], nonself_args,
+                &substructure);
+            Some(cx.arm(sp, vec![cx.pat_wild(sp)], arm_expr))
+        });
+
         // We will usually need the catch-all after matching the
         // tuples `(VariantK, VariantK, ...)` for each VariantK of the
         // enum.  But:
@@ -1294,17 +1323,17 @@ impl<'a> MethodDef<'a> {
             //Since we know that all the arguments will match if we reach the match expression we
             //add the unreachable intrinsics as the result of the catch all which should help llvm
             //in optimizing it
-            match_arms.push(cx.arm(sp,
-                                   vec![cx.pat_wild(sp)],
-                                   deriving::call_intrinsic(cx, sp, "unreachable", vec![])));
-
-            // Final wrinkle: the self_args are expressions that deref
-            // down to desired l-values, but we cannot actually deref
-            // them when they are fed as r-values into a tuple
-            // expression; here add a layer of borrowing, turning
-            // `(*self, *__arg_0, ...)` into `(&*self, &*__arg_0, ...)`.
-            let borrowed_self_args = self_args.move_map(|self_arg| cx.expr_addr_of(sp, self_arg));
-            let match_arg = cx.expr(sp, ast::ExprKind::Tup(borrowed_self_args));
+            match unified_arm {
+                Some(arm) => match_arms.push(arm),
+                None => {
+                    match_arms.push(cx.arm(sp,
+                                           vec![cx.pat_wild(sp)],
+                                           deriving::call_intrinsic(cx,
+                                                                    sp,
+                                                                    "unreachable",
+                                                                    vec![])));
+                }
+            }
 
             //Lastly we create an expression which branches on all discriminants being equal
             //  if discriminant_test {
@@ -1318,7 +1347,11 @@ impl<'a> MethodDef<'a> {
             //  else {
             //      <delegated expression referring to __self0_vi, et al.>
             //  }
-            let all_match = cx.expr_match(sp, match_arg, match_arms);
+            //
+            // If the fieldless variants are unified, `BodyF` replaces the
+            // unreachable arm, and is used on its own if all variants are
+            // fieldless.
+            let all_match = MethodDef::match_self_args(cx, sp, self_args, match_arms);
             let arm_expr = cx.expr_if(sp, discriminant_test, all_match, Some(arm_expr));
             cx.expr_block(
                 cx.block_all(sp, index_let_stmts, Some(arm_expr)))
@@ -1375,16 +1408,29 @@ impl<'a> MethodDef<'a> {
             deriving::call_intrinsic(cx, sp, "unreachable", vec![])
         }
         else {
+            match_arms.extend(unified_arm);
+            MethodDef::match_self_args(cx, sp, self_args, match_arms)
+        }
+    }
 
-            // Final wrinkle: the self_args are expressions that deref
-            // down to desired l-values, but we cannot actually deref
-            // them when they are fed as r-values into a tuple
-            // expression; here add a layer of borrowing, turning
-            // `(*self, *__arg_0, ...)` into `(&*self, &*__arg_0, ...)`.
-            let borrowed_self_args = self_args.move_map(|self_arg| cx.expr_addr_of(sp, self_arg));
-            let match_arg = cx.expr(sp, ast::ExprKind::Tup(borrowed_self_args));
-            cx.expr_match(sp, match_arg, match_arms)
+    /// Builds `match (&*self, &*__arg_0, ...) { <match_arms> }`, or just the
+    /// body of the only arm if that is a `_` arm.
+    fn match_self_args(cx: &mut ExtCtxt,
+                       sp: Span,
+                       self_args: Vec<P<Expr>>,
+                       mut match_arms: Vec<ast::Arm>) -> P<Expr> {
+        if match_arms.len() == 1 && match_arms[0].pats[0].node == PatKind::Wild {
+            return match_arms.pop().unwrap().body;
         }
+
+        // Final wrinkle: the self_args are expressions that deref
+        // down to desired l-values, but we cannot actually deref
+        // them when they are fed as r-values into a tuple
+        // expression; here add a layer of borrowing, turning
+        // `(*self, *__arg_0, ...)` into `(&*self, &*__arg_0, ...)`.
+        let borrowed_self_args = self_args.move_map(|self_arg| cx.expr_addr_of(sp, self_arg));
+        let match_arg = cx.expr(sp, ast::ExprKind::Tup(borrowed_self_args));
+        cx.expr_match(sp, match_arg, match_arms)
     }
 
     fn expand_static_enum_method_body(&self,
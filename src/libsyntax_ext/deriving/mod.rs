@@ -13,7 +13,7 @@
 use syntax::ast::{MetaItem, MetaItemKind, self};
 use syntax::attr::AttrMetaMethods;
 use syntax::ext::base::{ExtCtxt, SyntaxEnv, Annotatable};
-use syntax::ext::base::{MultiDecorator, MultiItemDecorator, MultiModifier};
+use syntax::ext::base::{MultiDecorator, MultiItemDecorator, MultiModifier, CustomDerive};
 use syntax::ext::build::AstBuilder;
 use syntax::feature_gate;
 use syntax::codemap::Span;
@@ -61,6 +61,7 @@ pub mod decodable;
 pub mod hash;
 pub mod debug;
 pub mod default;
+pub mod custom;
 
 #[path="cmp/partial_eq.rs"]
 pub mod partial_eq;
@@ -105,7 +106,18 @@ fn expand_derive(cx: &mut ExtCtxt,
                     }
                 };
 
-                if !(is_builtin_trait(tname) || cx.ecfg.enable_custom_derive()) {
+                let derive_name = intern(&format!("derive_{}", tname));
+                let is_proc_macro_derive = match cx.syntax_env.find(derive_name) {
+                    Some(ext) => match *ext {
+                        CustomDerive(..) => true,
+                        _ => false,
+                    },
+                    None => false,
+                };
+
+                if !(is_builtin_trait(tname) ||
+                     is_proc_macro_derive ||
+                     cx.ecfg.enable_custom_derive()) {
                     feature_gate::emit_feature_err(&cx.parse_sess.span_diagnostic,
                                                    "custom_derive",
                                                    titem.span,
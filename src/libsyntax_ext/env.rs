@@ -30,7 +30,9 @@ pub fn expand_option_env<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenT
         Some(v) => v
     };
 
-    let e = match env::var(&var[..]) {
+    let value = env::var(&var[..]);
+    cx.parse_sess().track_env_var(&var, value.as_ref().ok().map(|s| &s[..]), cx.original_span());
+    let e = match value {
       Err(..) => {
           cx.expr_path(cx.path_all(sp,
                                    true,
@@ -95,7 +97,9 @@ pub fn expand_env<'cx>(cx: &'cx mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
         }
     }
 
-    let e = match env::var(&var[..]) {
+    let value = env::var(&var[..]);
+    cx.parse_sess().track_env_var(&var, value.as_ref().ok().map(|s| &s[..]), cx.original_span());
+    let e = match value {
         Err(_) => {
             cx.span_err(sp, &msg);
             cx.expr_usize(sp, 0)
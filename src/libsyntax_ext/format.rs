@@ -14,7 +14,7 @@ use self::Position::*;
 use fmt_macros as parse;
 
 use syntax::ast;
-use syntax::codemap::{Span, respan, DUMMY_SP};
+use syntax::codemap::{BytePos, MultiSpan, Span, respan, DUMMY_SP};
 use syntax::ext::base::*;
 use syntax::ext::base;
 use syntax::ext::build::AstBuilder;
@@ -43,17 +43,26 @@ struct Context<'a, 'b:'a> {
     macsp: Span,
     /// The span of the format string literal.
     fmtsp: Span,
-
-    /// Parsed argument expressions and the types that we've found so far for
-    /// them.
+    /// Where the format string starts in the source of the literal, if the
+    /// literal is written out as exactly the format string.
+    fmt_offset: Option<usize>,
+    /// The syntax context the format string was written in, which captured
+    /// variables are resolved in.
+    fmt_ctxt: Option<ast::SyntaxContext>,
+
+    /// Parsed argument expressions, and the types that we've found so far for
+    /// them with the placeholder that first used them.
     args: Vec<P<ast::Expr>>,
-    arg_types: Vec<Option<ArgumentType>>,
+    arg_types: Vec<Option<(ArgumentType, Span)>>,
     /// Parsed named expressions and the types that we've found for them so far.
     /// Note that we keep a side-array of the ordering of the named arguments
     /// found to be sure that we can translate them in the same order that they
     /// were declared in.
+    ///
+    /// Names without an argument capture the variable of that name, if the
+    /// `format_args_capture` feature is enabled.
     names: HashMap<String, P<ast::Expr>>,
-    name_types: HashMap<String, ArgumentType>,
+    name_types: HashMap<String, (ArgumentType, Span)>,
     name_ordering: Vec<String>,
 
     /// The latest consecutive literal strings, or empty if there weren't any.
@@ -150,15 +159,15 @@ fn parse_args(ecx: &mut ExtCtxt, sp: Span, tts: &[ast::TokenTree])
 impl<'a, 'b> Context<'a, 'b> {
     /// Verifies one piece of a parse string. All errors are not emitted as
     /// fatal so we can continue giving errors about this and possibly other
-    /// format strings.
-    fn verify_piece(&mut self, p: &parse::Piece) {
+    /// format strings. `sp` is the span of the placeholder, if `p` is one.
+    fn verify_piece(&mut self, p: &parse::Piece, sp: Span) {
         match *p {
             parse::String(..) => {}
             parse::NextArgument(ref arg) => {
                 // width/precision first, if they have implicit positional
                 // parameters it makes more sense to consume them first.
-                self.verify_count(arg.format.width);
-                self.verify_count(arg.format.precision);
+                self.verify_count(arg.format.width, sp);
+                self.verify_count(arg.format.precision, sp);
 
                 // argument second, if it's an implicit positional parameter
                 // it's written second, so it should come after width/precision.
@@ -175,24 +184,24 @@ impl<'a, 'b> Context<'a, 'b> {
                 };
 
                 let ty = Known(arg.format.ty.to_string());
-                self.verify_arg_type(pos, ty);
+                self.verify_arg_type(pos, ty, sp);
             }
         }
     }
 
-    fn verify_count(&mut self, c: parse::Count) {
+    fn verify_count(&mut self, c: parse::Count, sp: Span) {
         match c {
             parse::CountImplied | parse::CountIs(..) => {}
             parse::CountIsParam(i) => {
-                self.verify_arg_type(Exact(i), Unsigned);
+                self.verify_arg_type(Exact(i), Unsigned, sp);
             }
             parse::CountIsName(s) => {
-                self.verify_arg_type(Named(s.to_string()), Unsigned);
+                self.verify_arg_type(Named(s.to_string()), Unsigned, sp);
             }
             parse::CountIsNextParam => {
                 if self.check_positional_ok() {
                     let next_arg = self.next_arg;
-                    self.verify_arg_type(Exact(next_arg), Unsigned);
+                    self.verify_arg_type(Exact(next_arg), Unsigned, sp);
                     self.next_arg += 1;
                 }
             }
@@ -217,40 +226,55 @@ impl<'a, 'b> Context<'a, 'b> {
         }
     }
 
-    fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType) {
+    /// The span of the placeholder at `place` in the format string, or of the
+    /// whole literal if the placeholder can't be found in the source.
+    fn placeholder_span(&self, place: (usize, usize)) -> Span {
+        placeholder_span(self.fmtsp, self.fmt_offset, place)
+    }
+
+    fn verify_arg_type(&mut self, arg: Position, ty: ArgumentType, sp: Span) {
         match arg {
             Exact(arg) => {
                 if self.args.len() <= arg {
                     let msg = format!("invalid reference to argument `{}` ({})",
                                       arg, self.describe_num_args());
 
-                    self.ecx.span_err(self.fmtsp, &msg[..]);
+                    self.ecx.span_err(sp, &msg[..]);
                     return;
                 }
-                {
-                    let arg_type = match self.arg_types[arg] {
-                        None => None,
-                        Some(ref x) => Some(x)
-                    };
-                    self.verify_same(self.args[arg].span, &ty, arg_type);
-                }
+                self.verify_same(self.args[arg].span, &ty, sp, self.arg_types[arg].as_ref());
                 if self.arg_types[arg].is_none() {
-                    self.arg_types[arg] = Some(ty);
+                    self.arg_types[arg] = Some((ty, sp));
                 }
             }
 
             Named(name) => {
-                let span = match self.names.get(&name) {
-                    Some(e) => e.span,
+                let span = self.names.get(&name).map(|e| e.span);
+                let span = match span {
+                    Some(span) => span,
+                    None if self.ecx.ecfg.enable_format_args_capture() => {
+                        match self.capture(&name, sp) {
+                            Some(span) => span,
+                            None => return,
+                        }
+                    }
                     None => {
                         let msg = format!("there is no argument named `{}`", name);
-                        self.ecx.span_err(self.fmtsp, &msg[..]);
+                        let mut err = self.ecx.struct_span_err(sp, &msg[..]);
+                        // #23973: do not suggest `#![feature(...)]` if we are in beta/stable
+                        if option_env!("CFG_DISABLE_UNSTABLE_FEATURES").is_none() {
+                            err.fileline_help(sp, &format!("add #![feature(format_args_capture)] \
+                                                            to the crate attributes to use a \
+                                                            variable named `{}`",
+                                                           name));
+                        }
+                        err.emit();
                         return;
                     }
                 };
-                self.verify_same(span, &ty, self.name_types.get(&name));
+                self.verify_same(span, &ty, sp, self.name_types.get(&name));
                 if !self.name_types.contains_key(&name) {
-                    self.name_types.insert(name.clone(), ty);
+                    self.name_types.insert(name.clone(), (ty, sp));
                 }
                 // Assign this named argument a slot in the arguments array if
                 // it hasn't already been assigned a slot.
@@ -262,6 +286,29 @@ impl<'a, 'b> Context<'a, 'b> {
         }
     }
 
+    /// Uses the variable `name` in scope for a placeholder that names no
+    /// argument, returning the span of the new argument.
+    ///
+    /// The variable is resolved as if `name` had been written where the
+    /// format string was, so a format string in a macro's body sees the
+    /// macro's own local variables and not those at the macro's call site.
+    fn capture(&mut self, name: &str, sp: Span) -> Option<Span> {
+        let ctxt = match self.fmt_ctxt {
+            Some(ctxt) => ctxt,
+            None => {
+                self.ecx.span_err(sp, &format!("cannot capture `{}` from a format string \
+                                                that was not written in the source",
+                                               name));
+                return None;
+            }
+        };
+        let ident = ast::Ident::new(token::intern(name), ctxt);
+        let e = self.ecx.expr_ident(sp, ident);
+        self.name_ordering.push(name.to_string());
+        self.names.insert(name.to_string(), e);
+        Some(sp)
+    }
+
     /// When we're keeping track of the types that are declared for certain
     /// arguments, we assume that `None` means we haven't seen this argument
     /// yet, `Some(None)` means that we've seen the argument, but no format was
@@ -270,41 +317,46 @@ impl<'a, 'b> Context<'a, 'b> {
     ///
     /// Obviously `Some(Some(x)) != Some(Some(y))`, but we consider it true
     /// that: `Some(None) == Some(Some(x))`
+    ///
+    /// `sp` is the span of the argument and `place` that of the placeholder
+    /// using it; a mismatch points at both placeholders involved.
     fn verify_same(&self,
                    sp: Span,
                    ty: &ArgumentType,
-                   before: Option<&ArgumentType>) {
-        let cur = match before {
+                   place: Span,
+                   before: Option<&(ArgumentType, Span)>) {
+        let (cur, prev_place) = match before {
             None => return,
-            Some(t) => t,
+            Some(&(ref t, prev_place)) => (t, prev_place),
         };
         if *ty == *cur {
             return
         }
-        match (cur, ty) {
+        let msg = match (cur, ty) {
             (&Known(ref cur), &Known(ref ty)) => {
-                self.ecx.span_err(sp,
-                                  &format!("argument redeclared with type `{}` when \
-                                           it was previously `{}`",
-                                          *ty,
-                                          *cur));
+                format!("argument redeclared with type `{}` when \
+                         it was previously `{}`",
+                        *ty,
+                        *cur)
             }
             (&Known(ref cur), _) => {
-                self.ecx.span_err(sp,
-                                  &format!("argument used to format with `{}` was \
-                                           attempted to not be used for formatting",
-                                           *cur));
+                format!("argument used to format with `{}` was \
+                         attempted to not be used for formatting",
+                        *cur)
             }
             (_, &Known(ref ty)) => {
-                self.ecx.span_err(sp,
-                                  &format!("argument previously used as a format \
-                                           argument attempted to be used as `{}`",
-                                           *ty));
+                format!("argument previously used as a format \
+                         argument attempted to be used as `{}`",
+                        *ty)
             }
-            (_, _) => {
-                self.ecx.span_err(sp, "argument declared with multiple formats");
-            }
-        }
+            (_, _) => "argument declared with multiple formats".to_string(),
+        };
+        let mut places = MultiSpan::new();
+        places.push_merge(prev_place);
+        places.push_merge(place);
+        self.ecx.struct_span_err(sp, &msg)
+            .span_note(places, "the argument is used by these placeholders")
+            .emit();
     }
 
     fn rtpath(ecx: &ExtCtxt, s: &str) -> Vec<ast::Ident> {
@@ -495,8 +547,8 @@ impl<'a, 'b> Context<'a, 'b> {
         // of each variable because we don't want to move out of the arguments
         // passed to this function.
         for (i, e) in self.args.into_iter().enumerate() {
-            let arg_ty = match self.arg_types[i].as_ref() {
-                Some(ty) => ty,
+            let arg_ty = match self.arg_types[i] {
+                Some((ref ty, _)) => ty,
                 None => continue // error already generated
             };
 
@@ -512,7 +564,7 @@ impl<'a, 'b> Context<'a, 'b> {
                 None => continue
             };
             let arg_ty = match self.name_types.get(name) {
-                Some(ty) => ty,
+                Some(&(ref ty, _)) => ty,
                 None => continue
             };
 
@@ -634,6 +686,22 @@ pub fn expand_format_args<'cx>(ecx: &'cx mut ExtCtxt, sp: Span,
     }
 }
 
+/// The span the text of a format string was written at. `println!` and the
+/// like build their format string with `concat!`, so for a macro invocation
+/// this is the span of its first argument.
+fn format_string_source(efmt: &ast::Expr) -> Span {
+    if let ast::ExprKind::Mac(ref mac) = efmt.node {
+        match mac.node.tts.first() {
+            Some(&ast::TokenTree::Token(_, token::Interpolated(token::NtExpr(ref e)))) => {
+                return format_string_source(e);
+            }
+            Some(tt) => return tt.get_span(),
+            None => {}
+        }
+    }
+    efmt.span
+}
+
 /// Take the various parts of `format_args!(efmt, args..., name=names...)`
 /// and construct the appropriate formatting expression.
 pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
@@ -644,6 +712,7 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
                                     -> P<ast::Expr> {
     let arg_types: Vec<_> = (0..args.len()).map(|_| None).collect();
     let macsp = ecx.call_site();
+    let fmt_ctxt = ecx.syntax_context_of(format_string_source(&efmt));
     // Expand the format literal so that efmt.span will have a backtrace. This
     // is essential for locating a bug when the format literal is generated in
     // a macro. (e.g. println!("{}"), which uses concat!($fmt, "\n")).
@@ -664,11 +733,16 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
         all_pieces_simple: true,
         macsp: macsp,
         fmtsp: efmt.span,
+        fmt_offset: None,
+        fmt_ctxt: fmt_ctxt,
     };
     let fmt = match expr_to_string(cx.ecx,
                                    efmt,
                                    "format argument must be a string literal.") {
-        Some((fmt, _)) => fmt,
+        Some((fmt, style)) => {
+            cx.fmt_offset = find_fmt_offset(cx.ecx, cx.fmtsp, &fmt, style);
+            fmt
+        }
         None => return DummyResult::raw_expr(sp)
     };
 
@@ -678,7 +752,11 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
         match parser.next() {
             Some(piece) => {
                 if !parser.errors.is_empty() { break }
-                cx.verify_piece(&piece);
+                let place = match parser.arg_places.last() {
+                    Some(&place) => cx.placeholder_span(place),
+                    None => cx.fmtsp,
+                };
+                cx.verify_piece(&piece, place);
                 match cx.trans_piece(&piece) {
                     Some(piece) => {
                         let s = cx.trans_literal_string();
@@ -702,16 +780,70 @@ pub fn expand_preparsed_format_args(ecx: &mut ExtCtxt, sp: Span,
     }
 
     // Make sure that all arguments were used and all arguments have types.
+    let mut unused = vec![];
     for (i, ty) in cx.arg_types.iter().enumerate() {
         if ty.is_none() {
-            cx.ecx.span_err(cx.args[i].span, "argument never used");
+            unused.push((cx.args[i].span, "argument never used"));
         }
     }
-    for (name, e) in &cx.names {
+    for name in &cx.name_ordering {
         if !cx.name_types.contains_key(name) {
-            cx.ecx.span_err(e.span, "named argument never used");
+            unused.push((cx.names[name].span, "named argument never used"));
+        }
+    }
+    if unused.len() == 1 {
+        cx.ecx.span_err(unused[0].0, unused[0].1);
+    } else if !unused.is_empty() {
+        let mut spans = MultiSpan::new();
+        for &(sp, _) in &unused {
+            spans.push_merge(sp);
         }
+        cx.ecx.parse_sess.span_diagnostic
+            .struct_span_err(spans, "multiple unused formatting arguments")
+            .span_note(cx.fmtsp, "no placeholder in this format string uses them")
+            .emit();
     }
 
     cx.into_expr()
 }
+
+/// Finds where the format string starts in the source of its literal, if the
+/// literal is written out as exactly the format string. Escapes and literals
+/// built by macros such as `concat!` make them differ, in which case
+/// diagnostics point at the whole literal instead of a placeholder.
+pub fn find_fmt_offset(ecx: &ExtCtxt, sp: Span, fmt: &str, style: ast::StrStyle)
+                       -> Option<usize> {
+    let snippet = match ecx.codemap().span_to_snippet(sp) {
+        Ok(snippet) => snippet,
+        Err(_) => return None,
+    };
+    // The lengths of the opening and closing quotes
+    let (open, close) = match style {
+        ast::StrStyle::Cooked => (1, 1),
+        ast::StrStyle::Raw(n) => (n + 2, n + 1),
+    };
+    let end = open + fmt.len();
+    if snippet.len() == end + close && snippet.is_char_boundary(open) &&
+       snippet.is_char_boundary(end) && &snippet[open..end] == fmt {
+        Some(open)
+    } else {
+        None
+    }
+}
+
+/// The span of the placeholder at `place` in a format string with the span
+/// `fmtsp` and the offset found by `find_fmt_offset`, or `fmtsp` if the
+/// placeholder can't be found in the source.
+pub fn placeholder_span(fmtsp: Span, fmt_offset: Option<usize>, place: (usize, usize)) -> Span {
+    match fmt_offset {
+        Some(offset) => {
+            let (lo, hi) = (offset + place.0, offset + place.1);
+            Span {
+                lo: fmtsp.lo + BytePos(lo as u32),
+                hi: fmtsp.lo + BytePos(hi as u32),
+                expn_id: fmtsp.expn_id,
+            }
+        }
+        None => fmtsp,
+    }
+}
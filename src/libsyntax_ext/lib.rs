@@ -19,10 +19,12 @@
        html_root_url = "https://doc.rust-lang.org/nightly/")]
 #![cfg_attr(not(stage0), deny(warnings))]
 
+#![feature(proc_macro_lib)]
 #![feature(rustc_private)]
 #![feature(staged_api)]
 
 extern crate fmt_macros;
+extern crate proc_macro;
 #[macro_use] extern crate log;
 #[macro_use]
 extern crate syntax;
@@ -33,6 +35,7 @@ use syntax::parse::token::intern;
 
 
 mod asm;
+mod asm_operands;
 mod cfg;
 mod concat;
 mod concat_idents;
@@ -44,6 +47,8 @@ mod trace_macros;
 // for custom_derive
 pub mod deriving;
 
+pub mod proc_macro_registrar;
+
 pub fn register_builtins(env: &mut SyntaxEnv) {
     // utility function to simplify creating NormalTT syntax extensions
     fn builtin_normal_expander(f: MacroExpanderFn) -> SyntaxExtension {
@@ -52,6 +57,8 @@ pub fn register_builtins(env: &mut SyntaxEnv) {
 
     env.insert(intern("asm"),
                builtin_normal_expander(asm::expand_asm));
+    env.insert(intern("asm_operands"),
+               builtin_normal_expander(asm_operands::expand_asm_operands));
     env.insert(intern("cfg"),
                builtin_normal_expander(cfg::expand_cfg));
     env.insert(intern("concat"),
@@ -0,0 +1,281 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Used by `rustc` when compiling a `proc-macro` crate: collects the
+//! `#[proc_macro_derive]` functions and generates the registrar function
+//! through which they are loaded into the compiler.
+
+use std::mem;
+
+use syntax::ast::{self, Ident, NodeId};
+use syntax::attr::{self, AttrMetaMethods};
+use syntax::codemap::{DUMMY_SP, Span, ExpnInfo, NameAndSpan, MacroAttribute};
+use syntax::errors;
+use syntax::ext::base::{DummyMacroResolver, ExtCtxt};
+use syntax::ext::build::AstBuilder;
+use syntax::ext::expand::ExpansionConfig;
+use syntax::feature_gate::{self, Features};
+use syntax::parse::ParseSess;
+use syntax::parse::token::{self, InternedString};
+use syntax::print::pprust;
+use syntax::ptr::P;
+use syntax::visit::{self, Visitor};
+
+struct CustomDerive {
+    trait_name: InternedString,
+    function_name: Ident,
+    span: Span,
+}
+
+struct CollectCustomDerives<'a> {
+    derives: Vec<CustomDerive>,
+    in_root: bool,
+    handler: &'a errors::Handler,
+    is_proc_macro_crate: bool,
+}
+
+pub fn modify(sess: &ParseSess,
+              mut krate: ast::Crate,
+              is_proc_macro_crate: bool,
+              num_crate_types: usize,
+              handler: &errors::Handler,
+              features: &Features) -> ast::Crate {
+    let derives = {
+        let mut collect = CollectCustomDerives {
+            derives: Vec::new(),
+            in_root: true,
+            handler: handler,
+            is_proc_macro_crate: is_proc_macro_crate,
+        };
+        visit::walk_crate(&mut collect, &krate);
+        collect.derives
+    };
+
+    if !is_proc_macro_crate {
+        return krate
+    }
+
+    if !features.proc_macro {
+        feature_gate::emit_feature_err(handler,
+                                       "proc_macro",
+                                       krate.span,
+                                       feature_gate::GateIssue::Language,
+                                       feature_gate::EXPLAIN_PROC_MACRO);
+        return krate
+    }
+
+    if num_crate_types > 1 {
+        handler.err("cannot mix `proc-macro` crate type with others");
+    }
+
+    if !krate.exported_macros.is_empty() {
+        handler.err("cannot export macro_rules! macros from a `proc-macro` \
+                     crate type currently");
+    }
+
+    let mut feature_gated_cfgs = vec![];
+    let mut resolver = DummyMacroResolver;
+    let cx = ExtCtxt::new(sess,
+                          krate.config.clone(),
+                          ExpansionConfig::default("proc_macro".to_string()),
+                          &mut feature_gated_cfgs,
+                          &mut resolver);
+    krate.module.items.push(mk_registrar(&cx, &derives));
+    krate
+}
+
+impl<'a> CollectCustomDerives<'a> {
+    fn check_not_pub_in_root(&self, vis: &ast::Visibility, sp: Span) {
+        if self.is_proc_macro_crate &&
+           self.in_root &&
+           *vis == ast::Visibility::Public {
+            self.handler.span_err(sp,
+                                  "`proc-macro` crate types cannot \
+                                   export any items other than functions \
+                                   tagged with `#[proc_macro_derive]` \
+                                   currently");
+        }
+    }
+}
+
+impl<'a, 'v> Visitor<'v> for CollectCustomDerives<'a> {
+    fn visit_item(&mut self, item: &ast::Item) {
+        let mut attrs = item.attrs.iter().filter(|a| a.check_name("proc_macro_derive"));
+        let attr = match attrs.next() {
+            Some(attr) => attr,
+            None => {
+                self.check_not_pub_in_root(&item.vis, item.span);
+                return visit::walk_item(self, item)
+            }
+        };
+        attr::mark_used(attr);
+
+        if let Some(a) = attrs.next() {
+            self.handler.span_err(a.span, "multiple `#[proc_macro_derive]` \
+                                           attributes found");
+        }
+
+        if !self.is_proc_macro_crate {
+            self.handler.span_err(attr.span,
+                                  "the `#[proc_macro_derive]` attribute is \
+                                   only usable with crates of the `proc-macro` \
+                                   crate type");
+            return
+        }
+
+        match item.node {
+            ast::ItemKind::Fn(..) => {}
+            _ => {
+                self.handler.span_err(item.span,
+                                      "the `#[proc_macro_derive]` attribute \
+                                       may only be applied to functions");
+                return
+            }
+        }
+
+        if !self.in_root {
+            self.handler.span_err(item.span,
+                                  "functions tagged with `#[proc_macro_derive]` \
+                                   must currently reside in the root of the crate");
+            return
+        }
+
+        if item.vis != ast::Visibility::Public {
+            self.handler.span_err(item.span,
+                                  "functions tagged with `#[proc_macro_derive]` \
+                                   must be `pub`");
+            return
+        }
+
+        let trait_name = match attr.meta_item_list() {
+            Some(list) if list.len() == 1 => {
+                match list[0].node {
+                    ast::MetaItemKind::Word(ref name) => Some(name.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        match trait_name {
+            Some(trait_name) => {
+                self.derives.push(CustomDerive {
+                    trait_name: trait_name,
+                    function_name: item.ident,
+                    span: item.span,
+                });
+            }
+            None => {
+                self.handler.span_err(attr.span,
+                                      "attribute must be of form: \
+                                       #[proc_macro_derive(TraitName)]");
+            }
+        }
+    }
+
+    fn visit_mod(&mut self, m: &ast::Mod, _s: Span, id: NodeId) {
+        let mut prev_in_root = self.in_root;
+        if id != ast::CRATE_NODE_ID {
+            prev_in_root = mem::replace(&mut self.in_root, false);
+        }
+        visit::walk_mod(self, m);
+        self.in_root = prev_in_root;
+    }
+
+    fn visit_mac(&mut self, _mac: &ast::Mac) {
+        // no macro invocations are left after expansion
+    }
+}
+
+// Creates a new module which looks like:
+//
+//      #[doc(hidden)]
+//      pub mod $gensym {
+//          extern crate proc_macro;
+//
+//          #[rustc_derive_registrar]
+//          pub fn registrar(registrar: &mut proc_macro::__internal::Registry) {
+//              registrar.register_custom_derive($name_trait1, ::$name1);
+//              registrar.register_custom_derive($name_trait2, ::$name2);
+//              // ...
+//          }
+//      }
+//
+// The module is public so that the registrar is exported from the dylib;
+// the compiler records it in the crate metadata and calls it when loading
+// the crate.
+fn mk_registrar(cx: &ExtCtxt, derives: &[CustomDerive]) -> P<ast::Item> {
+    let eid = cx.codemap().record_expansion(ExpnInfo {
+        call_site: DUMMY_SP,
+        callee: NameAndSpan {
+            format: MacroAttribute(token::intern("proc_macro")),
+            span: None,
+            // the registrar uses the unstable `proc_macro::__internal`
+            allow_internal_unstable: true,
+        }
+    });
+    let span = Span { expn_id: eid, ..DUMMY_SP };
+
+    let proc_macro = token::str_to_ident("proc_macro");
+    let krate = cx.item(span,
+                        proc_macro,
+                        Vec::new(),
+                        ast::ItemKind::ExternCrate(None));
+
+    let __internal = token::str_to_ident("__internal");
+    let registry = token::str_to_ident("Registry");
+    let registrar = token::str_to_ident("registrar");
+    let register_custom_derive = token::str_to_ident("register_custom_derive");
+    let stmts = derives.iter().map(|cd| {
+        let path = cx.path_global(cd.span, vec![cd.function_name]);
+        let trait_name = cx.expr_str(cd.span, cd.trait_name.clone());
+        let call = cx.expr_method_call(span,
+                                       cx.expr_ident(span, registrar),
+                                       register_custom_derive,
+                                       vec![trait_name, cx.expr_path(path)]);
+        cx.stmt_expr(call)
+    }).collect::<Vec<_>>();
+
+    let path = cx.path(span, vec![proc_macro, __internal, registry]);
+    let registrar_path = cx.ty_path(path);
+    let arg_ty = cx.ty_rptr(span, registrar_path, None, ast::Mutability::Mutable);
+    let func = cx.item_fn(span,
+                          registrar,
+                          vec![cx.arg(span, registrar, arg_ty)],
+                          cx.ty(span, ast::TyKind::Tup(Vec::new())),
+                          cx.block(span, stmts, None));
+
+    let derive_registrar = token::intern_and_get_ident("rustc_derive_registrar");
+    let derive_registrar = cx.meta_word(span, derive_registrar);
+    let derive_registrar = cx.attribute(span, derive_registrar);
+    let func = func.map(|mut i| {
+        i.attrs.push(derive_registrar);
+        i.vis = ast::Visibility::Public;
+        i
+    });
+
+    let module = cx.item_mod(span,
+                             span,
+                             token::gensym_ident("registrar"),
+                             Vec::new(),
+                             vec![krate, func]);
+    let doc_hidden = cx.meta_list(span,
+                                  token::intern_and_get_ident("doc"),
+                                  vec![cx.meta_word(span,
+                                                    token::intern_and_get_ident("hidden"))]);
+    let module = module.map(|mut i| {
+        i.attrs.push(cx.attribute(span, doc_hidden));
+        i.vis = ast::Visibility::Public;
+        i
+    });
+
+    debug!("generated proc-macro registrar:\n{}", pprust::item_to_string(&module));
+
+    module
+}
@@ -0,0 +1,42 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(decl_macro)]
+
+pub fn helper() -> i32 {
+    40
+}
+
+pub macro add_helper(:expr) {
+    helper() + 
+}
+
+pub mod inner {
+    pub const TWO: i32 = 2;
+
+    pub macro two() {
+        TWO
+    }
+
+    macro private() {
+        0
+    }
+
+    pub fn zero() -> i32 {
+        private!()
+    }
+}
+
+mod hidden {
+    // Not reachable from other crates, so not exported.
+    pub macro hidden() {
+        1
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![crate_type="lib"]
+
+extern crate import_candidates_dep;
+
+mod internal {
+    pub struct Widget;
+
+    pub fn make_widget() -> Widget { Widget }
+}
+
+pub mod widgets {
+    // the only public paths to these items
+    pub use internal::{Widget, make_widget};
+}
+
+pub use import_candidates_dep::util::helper;
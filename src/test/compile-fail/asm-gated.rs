@@ -11,5 +11,6 @@
 fn main() {
     unsafe {
         asm!(""); //~ ERROR inline assembly is not stable enough
+        asm_operands!(""); //~ ERROR inline assembly is not stable enough
     }
 }
@@ -0,0 +1,37 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-arm
+// ignore-asmjs
+// ignore-hexagon
+// ignore-mips
+// ignore-msp430
+// ignore-powerpc
+// ignore-powerpc64
+// ignore-sparc
+// ignore-systemz
+// ignore-x86
+// ignore-x86_64
+// ignore-xcore
+
+#![feature(asm)]
+
+fn main() {
+    let x: u64 = 0;
+    unsafe {
+        asm_operands!("", in("x18") x);
+        //~^ ERROR invalid register `x18`: the platform register cannot be used as an operand
+        asm_operands!("", in("w19") x);
+        //~^ ERROR invalid register `w19`: a register reserved by LLVM cannot be used
+        asm_operands!("", in("x29") x);
+        //~^ ERROR invalid register `x29`: the frame pointer cannot be used as an operand
+        asm_operands!("", in("x17") x);
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-aarch64
+// ignore-arm
+// ignore-asmjs
+// ignore-hexagon
+// ignore-mips
+// ignore-msp430
+// ignore-powerpc
+// ignore-powerpc64
+// ignore-sparc
+// ignore-systemz
+// ignore-x86
+// ignore-xcore
+
+#![feature(asm)]
+
+fn main() {
+    let mut x: u64 = 0;
+    unsafe {
+        asm_operands!("", foo(reg) x); //~ ERROR expected one of `in`, `out`, `lateout`, `inout`
+        asm_operands!("{}", in(greg) x); //~ ERROR invalid register class `greg`
+        asm_operands!("", in("rsp") x);
+        //~^ ERROR invalid register `rsp`: the stack pointer cannot be used as an operand
+        asm_operands!("", in("r16") x); //~ ERROR invalid register `r16`
+        asm_operands!("{}", sym main); //~ ERROR `sym` operands are not supported
+        asm_operands!("", in("eax") x, in("al") x);
+        //~^ ERROR register `al` conflicts with register `eax`
+        asm_operands!("{}", out(reg) _); //~ ERROR `_` can't be the output of register class `reg`
+        asm_operands!("{a} {}", a = in(reg) x, in(reg) x);
+        //~^ ERROR positional operands cannot follow named operands
+        asm_operands!("{a}", a = in(reg) x, a = in(reg) x); //~ ERROR duplicate argument named `a`
+
+        asm_operands!("{} {1}", in(reg) x);
+        //~^ ERROR invalid reference to argument `1` (there is 1 argument)
+        asm_operands!("{} {b}", in(reg) x); //~ ERROR there is no argument named `b`
+        asm_operands!("{:q}", in(reg) x); //~ ERROR invalid modifier `q` for register class `reg`
+        asm_operands!("{:>5}", in(reg) x);
+        //~^ ERROR asm template placeholders can only have a modifier
+        asm_operands!("{:e}", const 1); //~ ERROR invalid modifier `e`
+        asm_operands!("{}", in("eax") x); //~ ERROR explicit registers can't be used in the template
+        asm_operands!("nop", in(reg) x); //~ ERROR argument never used
+        asm_operands!("{", in(reg) x); //~ ERROR invalid asm template
+
+        asm_operands!("mov {}, 1", out(reg) x, options(pure));
+        //~^ ERROR the `pure` option must be combined with `nomem` or `readonly`
+        asm_operands!("nop", options(pure, nomem));
+        //~^ ERROR asm with the `pure` option must have at least one output
+        asm_operands!("nop", options(nomem, readonly));
+        //~^ ERROR the `nomem` and `readonly` options are mutually exclusive
+        asm_operands!("nop", options(volatile)); //~ ERROR unknown option `volatile`
+        asm_operands!("nop", options(nomem), in(reg) x); //~ ERROR operands can't follow the options
+    }
+}
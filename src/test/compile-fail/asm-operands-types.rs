@@ -0,0 +1,71 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-aarch64
+// ignore-arm
+// ignore-asmjs
+// ignore-hexagon
+// ignore-mips
+// ignore-msp430
+// ignore-powerpc
+// ignore-powerpc64
+// ignore-sparc
+// ignore-systemz
+// ignore-x86
+// ignore-xcore
+
+#![feature(asm, repr_simd)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct F32x4(f32, f32, f32, f32);
+
+#[derive(Copy, Clone)]
+struct Pair(u64, u64);
+
+fn main() {
+    let x: u64 = 0;
+    let b: u8 = 0;
+    let v = F32x4(0.0, 0.0, 0.0, 0.0);
+    let p = Pair(0, 0);
+    let s: &[u64] = &[0];
+    unsafe {
+        // These are fine.
+        asm_operands!("{}", in(reg) x);
+        asm_operands!("{}", in(reg) &x);
+        asm_operands!("{}", in(reg_byte) b);
+        asm_operands!("{}", in(xmm_reg) v);
+        asm_operands!("{}", in(xmm_reg) 1.0f64);
+        asm_operands!("", in("al") b);
+
+        asm_operands!("{}", in(xmm_reg) x);
+        //~^ ERROR type `u64` cannot be used with register class `xmm_reg`
+        asm_operands!("{}", in(reg) p);
+        //~^ ERROR type `Pair` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) (x, x));
+        //~^ ERROR type `(u64, u64)` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) [x, x]);
+        //~^ ERROR type `[u64; 2]` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) v);
+        //~^ ERROR type `F32x4` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) b);
+        //~^ ERROR type `u8` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) true);
+        //~^ ERROR type `bool` cannot be used with register class `reg`
+        asm_operands!("{}", in(reg) s);
+        //~^ ERROR type `&[u64]` cannot be used with register class `reg`
+        asm_operands!("", in("eax") p);
+        //~^ ERROR type `Pair` cannot be used with register class `reg`
+        asm_operands!("", in("xmm0") x);
+        //~^ ERROR type `u64` cannot be used with register class `xmm_reg`
+        asm_operands!("{}", const 1.0f64);
+        //~^ ERROR type `f64` cannot be used as a `const` operand
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Moves out of an array by pattern are tracked per element, but indexing
+// and subslices still see the array as a whole.
+
+fn moved_twice(arr: [String; 2]) {
+    let [a, _] = arr;
+    let [_, b] = arr;
+    let [c, _] = arr; //~ ERROR use of moved value: `arr[0]`
+}
+
+fn index_after_move(arr: [String; 2]) {
+    let [a, _] = arr;
+    let b = &arr[1]; //~ ERROR use of moved value: `arr`
+}
+
+fn move_with_borrowed_rest(arr: [String; 3]) {
+    let [a, rest..] = arr; //~ ERROR cannot move out of
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(param_attrs)]
+
+fn f(#[inline] x: u8) {} //~ ERROR only `cfg` attributes are allowed on parameters
+
+#[cfg(all)] //~ ERROR expected a list of cfg-patterns after `all`
+fn g() {}
+
+fn main() {}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --check-cfg names(foo) -Z unstable-options
+
+// Unexpected `cfg`s are reported at the item containing them, so they can be
+// allowed there.
+
+#![deny(unexpected_cfgs)]
+
+#[allow(unexpected_cfgs)]
+mod allowed {
+    #[cfg(fooo)]
+    fn f() {}
+}
+
+#[allow(unexpected_cfgs)]
+fn g() -> bool {
+    cfg!(barr)
+}
+
+#[cfg(bazz)] //~ ERROR unexpected `cfg` condition name `bazz`
+fn h() {}
+
+fn main() {
+    g();
+}
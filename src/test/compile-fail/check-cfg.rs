@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --check-cfg names(foo) --check-cfg values(feature="a") -Z unstable-options
+
+#![deny(unexpected_cfgs)]
+
+#[cfg(fooo)] //~ ERROR unexpected `cfg` condition name `fooo`
+fn f() {}
+
+#[cfg(feature = "b")] //~ ERROR unexpected `cfg` condition value `b` for `feature`
+fn g() {}
+
+#[cfg(any(foo, windows, feature = "a"))]
+fn h() {}
+
+fn main() {
+    cfg!(barr); //~ ERROR unexpected `cfg` condition name `barr`
+}
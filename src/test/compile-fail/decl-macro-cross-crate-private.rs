@@ -1,4 +1,4 @@
-// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
 // file at the top-level directory of this distribution and at
 // http://rust-lang.org/COPYRIGHT.
 //
@@ -8,11 +8,12 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-// Test that slice pattern syntax is gated by `slice_patterns` feature gate
+// aux-build:decl_macro_export.rs
+
+#![feature(decl_macro)]
+
+extern crate decl_macro_export;
 
 fn main() {
-    let x = [1, 2, 3, 4, 5];
-    match x {
-        [1, 2, xs..] => {} //~ ERROR slice pattern syntax is experimental
-    }
+    decl_macro_export::inner::private!(); //~ ERROR unresolved macro path
 }
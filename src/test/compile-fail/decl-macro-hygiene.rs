@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(decl_macro)]
+
+// Fields introduced by the body of a `macro` can't be named outside of it.
+
+macro make_struct($name:ident) {
+    struct $name { field: u32 }
+
+    impl $name {
+        fn new() -> $name {
+            $name { field: 0 }
+        }
+
+        fn get(&self) -> u32 {
+            self.field
+        }
+    }
+}
+
+make_struct!(S);
+
+fn main() {
+    assert_eq!(S::new().get(), 0);
+    let _ = S { field: 0 };
+    //~^ ERROR structure `S` has no field named `field`
+    //~| ERROR missing field `field` in initializer of `S`
+}
@@ -0,0 +1,31 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `pub(restricted)` macros can only be named from inside the module they are
+// restricted to.
+
+#![feature(decl_macro, pub_restricted)]
+
+mod a {
+    pub mod b {
+        pub(a) macro in_a() { 1 }
+        pub(crate) macro in_crate() { 2 }
+    }
+
+    pub fn f() -> i32 {
+        b::in_a!() + b::in_crate!()
+    }
+}
+
+fn main() {
+    a::f();
+    a::b::in_crate!();
+    a::b::in_a!(); //~ ERROR macro `in_a` is private
+}
@@ -0,0 +1,31 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(default_type_parameter_fallback)]
+
+fn foo<F:Default=usize>() -> F { F::default() }
+//~^ NOTE: a default was defined here...
+
+fn baz<B:Default=isize>() -> B { B::default() }
+//~^ NOTE: a second default was defined here...
+
+fn same<T>(_: &mut T, _: &mut T) {}
+
+fn main() {
+    // `&mut T` is invariant, so both type variables are equated and
+    // the defaults of `F` and `B` now apply to the same variable.
+    let mut a = foo();
+    //~^ ERROR: mismatched types
+    //~| NOTE: conflicting type parameter defaults `usize` and `isize`
+    //~| NOTE: ...that was applied to an unconstrained type variable here
+    let mut b = baz();
+    //~^ NOTE: ...that also applies to the same type variable here
+    same(&mut a, &mut b);
+}
@@ -0,0 +1,23 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+
+#![feature(default_type_parameter_fallback)]
+
+use std::fmt::Debug;
+
+fn show<T:Debug=String>(t: T) { println!("{:?}", t); }
+//~^ NOTE: the default was defined here
+
+fn main() {
+    show(1);
+    //~^ ERROR: the type parameter default `std::string::String` cannot be applied to a type variable inferred to be an integer literal
+}
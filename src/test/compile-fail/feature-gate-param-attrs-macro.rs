@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Parameters generated by macros are gated too, when the check before
+// expansion is rerun after it, whether or not they survive cfg stripping.
+
+macro_rules! generate {
+    () => (fn f(#[cfg(foo)] x: u8) {}) //~ ERROR attributes on parameters are experimental
+}
+
+macro_rules! generate_kept {
+    () => (fn g(#[allow(unused_variables)] y: u8) {})
+    //~^ ERROR attributes on parameters are experimental
+}
+
+generate!();
+generate_kept!();
+
+fn main() {}
@@ -0,0 +1,21 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Each unused argument is pointed at by a single error.
+
+fn main() {
+    format!("{}",
+            1,
+            2, //~ ERROR multiple unused formatting arguments
+            "x", //~ ERROR multiple unused formatting arguments
+            foo = 3); //~ ERROR multiple unused formatting arguments
+
+    format!("{1}", 1, 2); //~ ERROR argument never used
+}
@@ -0,0 +1,54 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+// aux-build:import_candidates_dep.rs
+// aux-build:import_candidates.rs
+
+extern crate import_candidates;
+
+mod shapes {
+    mod circle {
+        pub struct Circle;
+    }
+
+    pub use self::circle::Circle;
+}
+
+fn main() {
+    let _: Circle;
+    //~^ ERROR type name `Circle` is undefined or not in scope
+    //~| HELP you can import several candidates into scope (`use ...;`):
+    //~| HELP `use shapes::Circle;`
+    //~| HELP `use shapes::circle::Circle;`
+
+    let _: Widget;
+    //~^ ERROR type name `Widget` is undefined or not in scope
+    //~| HELP you can import it into scope: `use import_candidates::widgets::Widget;`.
+
+    make_widget();
+    //~^ ERROR unresolved name `make_widget`
+    //~| HELP you can import it into scope: `use import_candidates::widgets::make_widget;`.
+
+    // reachable through a re-export, so the path in the dependency isn't suggested
+    helper();
+    //~^ ERROR unresolved name `helper`
+    //~| HELP you can import it into scope: `use import_candidates::helper;`.
+
+    // only reachable once the dependency is linked explicitly
+    let _: Hidden;
+    //~^ ERROR type name `Hidden` is undefined or not in scope
+    //~| HELP you can import it into scope: `use import_candidates_dep::util::Hidden;` (after adding `extern crate import_candidates_dep;`).
+
+    // the crates behind the standard library are unstable, so nothing is suggested from them
+    let _: NonZero<u8>;
+    //~^ ERROR type name `NonZero` is undefined or not in scope
+    //~| HELP no candidates by the name of `NonZero` found in your project
+}
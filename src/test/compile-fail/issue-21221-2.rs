@@ -15,16 +15,19 @@ pub mod foo {
         // decide to make it public based on the suggestion ...
         pub trait T {}
     }
-    // imports should be ignored:
+    // private imports should be ignored:
     use self::bar::T;
 }
 
 pub mod baz {
     pub use foo;
+    // ... but re-exports should be suggested
     pub use std::ops::{Mul as T};
 }
 
 struct Foo;
 impl T for Foo { }
 //~^ ERROR trait `T` is not in scope
-//~| HELP you can import it into scope: `use foo::bar::T;`.
+//~| HELP you can import several candidates into scope (`use ...;`):
+//~| HELP `use baz::T;`
+//~| HELP `use foo::bar::T;`
@@ -0,0 +1,110 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(correctness)]
+#![allow(dead_code)]
+
+use std::f64::NAN;
+use std::mem;
+
+fn float_cmp(x: f64) -> bool {
+    x == 1.5
+    //~^ ERROR strict comparison of floating point values
+    //~| HELP consider comparing them within some error:
+    //~| SUGGESTION (x - 1.5).abs() < ::std::f64::EPSILON
+}
+
+fn float_cmp_ne(x: f32, y: f32) -> bool {
+    x != y
+    //~^ ERROR strict comparison of floating point values
+    //~| HELP consider comparing them within some error:
+    //~| SUGGESTION (x - y).abs() >= ::std::f32::EPSILON
+}
+
+fn float_cmp_compound(x: f64, y: f64) -> bool {
+    x + 1.0 == y * 2.0
+    //~^ ERROR strict comparison of floating point values
+    //~| HELP consider comparing them within some error:
+    //~| SUGGESTION ((x + 1.0) - (y * 2.0)).abs() < ::std::f64::EPSILON
+}
+
+fn float_cmp_zero(x: f64) -> bool {
+    x == 0.0 || x != std::f64::INFINITY
+}
+
+fn cmp_nan(x: f64) -> bool {
+    let a = x == NAN;
+    //~^ ERROR comparison with NaN using `==` is always false
+    //~| HELP use `is_nan` instead:
+    //~| SUGGESTION x.is_nan()
+    let b = std::f64::NAN != x;
+    //~^ ERROR comparison with NaN using `!=` is always true
+    //~| HELP use `is_nan` instead:
+    //~| SUGGESTION !x.is_nan()
+    let c = x < NAN; //~ ERROR comparison with NaN is always false
+    a || b || c
+}
+
+fn cmp_nan_compound(x: f64) -> bool {
+    x * 2.0 != NAN
+    //~^ ERROR comparison with NaN using `!=` is always true
+    //~| HELP use `is_nan` instead:
+    //~| SUGGESTION !(x * 2.0).is_nan()
+}
+
+fn iter_count(v: &Vec<u8>, a: [u8; 4]) -> usize {
+    v.iter().count()
+    //~^ ERROR called `.count()` on the iterator of a slice
+    //~| HELP use `len` instead:
+    //~| SUGGESTION v.len()
+    + a.iter().count()
+    //~^ ERROR called `.count()` on the iterator of a slice
+    //~| HELP use `len` instead:
+    //~| SUGGESTION a.len()
+    + v.iter().filter(|&&b| b > 0).count()
+}
+
+fn forget_in_loop(v: Vec<String>) {
+    for s in v {
+        mem::forget(s);
+        //~^ ERROR with a destructor is forgotten on every iteration of this loop
+    }
+    loop {
+        // no destructor
+        mem::forget(0);
+        break;
+    }
+    mem::forget(String::new());
+}
+
+fn divide_by_zero(x: u32, mut y: i64) -> (u32, i64) {
+    y /= 0; //~ ERROR this division by zero will panic at runtime
+    (x % 0, y) //~ ERROR this remainder by zero will panic at runtime
+}
+
+fn if_same_then_else(c: bool) -> u32 {
+    let x = if c { 1 + 2 } else { 3 };
+    if c {
+        x + 1
+    } else { //~ ERROR this `if` has identical `then` and `else` blocks
+        x + 1
+    }
+}
+
+struct Point { x: i32, y: i32 }
+
+fn self_assignment(mut a: i32, mut p: Point) -> i32 {
+    a = a; //~ ERROR self-assignment of `a` has no effect
+    p.x = p.x; //~ ERROR self-assignment of `p.x` has no effect
+    p.y = p.x;
+    a + p.y
+}
+
+fn main() {}
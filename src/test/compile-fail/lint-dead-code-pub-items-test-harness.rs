@@ -0,0 +1,27 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --test
+
+// The test harness is an executable, but the crate it's built from is used
+// by others, so its public API is still live.
+
+#![deny(dead_code)]
+
+pub fn api() {}
+
+pub struct Api {
+    pub field: u32,
+}
+
+fn unused_private() {} //~ ERROR function is never used: `unused_private`
+
+#[test]
+fn it_works() {}
@@ -0,0 +1,53 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// In an executable nothing can be used from other crates, so `pub` items
+// are only live if they are used.
+
+#![deny(dead_code)]
+
+pub fn unused_pub_fn() {} //~ ERROR function is never used: `unused_pub_fn`
+
+pub fn used_pub_fn() {}
+
+pub static UNUSED_PUB_STATIC: u32 = 0; //~ ERROR static item is never used: `UNUSED_PUB_STATIC`
+
+pub enum Choice {
+    Used,
+    Unused, //~ ERROR variant is never used: `Unused`
+}
+
+mod private {
+    pub struct Point {
+        pub x: i32,
+        pub y: i32, //~ ERROR struct field is never used: `y`
+    }
+
+    pub fn origin() -> Point {
+        Point { x: 0, y: 0 }
+    }
+
+    pub fn unused_in_private_mod() {} //~ ERROR function is never used: `unused_in_private_mod`
+}
+
+pub use private::unused_in_private_mod;
+
+// Exported symbols can be called from outside the executable.
+#[no_mangle]
+pub extern fn exported_no_mangle() {}
+
+#[export_name = "exported_by_name"]
+extern fn exported_by_name() {}
+
+fn main() {
+    used_pub_fn();
+    let _ = Choice::Used;
+    let _ = private::origin().x;
+}
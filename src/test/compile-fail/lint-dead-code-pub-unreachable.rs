@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Items in a library are only live because they are `pub` if they can be
+// named from other crates.
+
+#![crate_type = "lib"]
+#![deny(dead_code)]
+
+pub fn exported() -> private::Point {
+    private::origin()
+}
+
+pub use private::reexported;
+
+mod private {
+    // fields of types used in public interfaces can be used by other crates
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    pub fn origin() -> Point {
+        Point { x: 0, y: 0 }
+    }
+
+    pub fn reexported() {}
+
+    pub fn unreachable() {} //~ ERROR function is never used: `unreachable`
+
+    pub struct Unreachable { //~ ERROR struct is never used: `Unreachable`
+        pub field: i32,
+    }
+}
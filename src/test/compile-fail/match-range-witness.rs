@@ -0,0 +1,35 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    match 0u8 {
+    //~^ ERROR non-exhaustive patterns: `10 ...19` not covered
+        0...9 => {}
+        20...255 => {}
+    }
+
+    match 0i8 {
+    //~^ ERROR non-exhaustive patterns: `-128`, `0` and `127` not covered
+        -127...-1 => {}
+        1...126 => {}
+    }
+
+    match 'a' {
+    //~^ ERROR non-exhaustive patterns: `'b'` not covered
+        '\0'...'a' => {}
+        'c'...'\u{10FFFF}' => {}
+    }
+
+    match (true, 0u8) {
+    //~^ ERROR non-exhaustive patterns: `(true, 0)` and `(false, 128 ...255)` not covered
+        (true, 1...255) => {}
+        (false, 0...127) => {}
+    }
+}
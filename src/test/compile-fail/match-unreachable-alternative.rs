@@ -0,0 +1,28 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+fn main() {
+    match 0u8 {
+        0...5 | 3 => {}
+        //~^ ERROR unreachable pattern
+        //~| NOTE this alternative of the `|` pattern is already covered
+        6...9 | 10...19 => {}
+        0...19 => {}
+        //~^ ERROR unreachable pattern
+        20 | 21...255 => {}
+    }
+
+    match Some(1u8) {
+        Some(0...127) => {}
+        None | Some(128...255) | Some(200) => {}
+        //~^ ERROR unreachable pattern
+        //~| NOTE this alternative of the `|` pattern is already covered
+    }
+}
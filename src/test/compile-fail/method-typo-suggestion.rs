@@ -0,0 +1,49 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+
+mod shapes {
+    pub struct Circle;
+
+    impl Circle {
+        pub fn radius(&self) -> u32 { 1 }
+        fn private_area(&self) -> u32 { 3 }
+    }
+
+    pub trait Draw {
+        fn draw_outline(&self) {}
+    }
+
+    impl Draw for Circle {}
+}
+
+use shapes::{Circle, Draw};
+
+fn main() {
+    let c = Circle;
+
+    c.raduis();
+    //~^ ERROR no method named `raduis` found for type `shapes::Circle` in the current scope
+    //~| HELP there is a method with a similar name: `shapes::Circle::radius`
+
+    c.draw_outlin();
+    //~^ ERROR no method named `draw_outlin` found for type `shapes::Circle` in the current scope
+    //~| HELP there is a method with a similar name: `shapes::Draw::draw_outline`
+
+    // private methods are not suggested
+    c.private_aera();
+    //~^ ERROR no method named `private_aera` found for type `shapes::Circle` in the current scope
+
+    // paths to associated items get suggestions too
+    Circle::radus();
+    //~^ ERROR no associated item named `radus` found for type `shapes::Circle` in the current scope
+    //~| HELP there is an associated item with a similar name: `shapes::Circle::radius`
+}
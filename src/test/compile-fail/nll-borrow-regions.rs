@@ -0,0 +1,44 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Under `-Z nll` a borrow ends after the last use of the reference, not
+// at the end of its lexical scope, but it is still checked while in use.
+
+// revisions: lexical nll
+//[nll] compile-flags: -Z nll
+
+fn ends_early() {
+    let mut x = 0;
+    let r = &mut x;
+    *r += 1;
+    x += 1; //[lexical]~ ERROR cannot assign to `x` because it is borrowed
+}
+
+fn still_live() {
+    let mut x = 0;
+    let r = &mut x;
+    x += 1; //[lexical]~ ERROR cannot assign to `x` because it is borrowed
+    //[nll]~^ ERROR cannot assign to `x` because it is borrowed
+    *r += 1;
+}
+
+fn still_live_shared() {
+    let mut v = vec![1, 2, 3];
+    let first = &v[0];
+    v.push(4); //[lexical]~ ERROR cannot borrow `v` as mutable
+    //[nll]~^ ERROR cannot borrow `v` as mutable
+    println!("{}", first);
+}
+
+fn main() {
+    ends_early();
+    still_live();
+    still_live_shared();
+}
@@ -0,0 +1,38 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A borrow through a reference keeps the original borrow in effect for as
+// long as the new one is used.
+
+// revisions: lexical nll
+//[nll] compile-flags: -Z nll
+
+fn reborrow_mut() {
+    let mut x = 0;
+    let r = &mut x;
+    let s = &mut *r;
+    x += 1; //[lexical]~ ERROR cannot assign to `x` because it is borrowed
+    //[nll]~^ ERROR cannot assign to `x` because it is borrowed
+    *s += 1;
+}
+
+fn reborrow_shared() {
+    let mut x = 0;
+    let r = &x;
+    let s = &*r;
+    x += 1; //[lexical]~ ERROR cannot assign to `x` because it is borrowed
+    //[nll]~^ ERROR cannot assign to `x` because it is borrowed
+    println!("{}", s);
+}
+
+fn main() {
+    reborrow_mut();
+    reborrow_shared();
+}
@@ -8,7 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#![feature(slice_patterns)]
+// ignore-tidy-linelength
 
 enum t { a, b, }
 
@@ -21,10 +21,10 @@ fn main() {
     match Some(10) { //~ ERROR non-exhaustive patterns: `Some(_)` not covered
       None => {}
     }
-    match (2, 3, 4) { //~ ERROR non-exhaustive patterns: `(_, _, _)` not covered
+    match (2, 3, 4) { //~ ERROR non-exhaustive patterns: `(_, _, -2147483648 ...3)` and `(_, _, 5 ...2147483647)`
       (_, _, 4) => {}
     }
-    match (t::a, t::a) { //~ ERROR non-exhaustive patterns: `(a, a)` not covered
+    match (t::a, t::a) { //~ ERROR non-exhaustive patterns: `(a, a)` and `(b, b)` not covered
       (t::a, t::b) => {}
       (t::b, t::a) => {}
     }
@@ -39,7 +39,7 @@ fn main() {
     }
     let vec = vec!(Some(42), None, Some(21));
     let vec: &[Option<isize>] = &vec;
-    match vec { //~ ERROR non-exhaustive patterns: `[]` not covered
+    match vec { //~ ERROR non-exhaustive patterns: `[]`, `[Some(_)]`, `[None, _]` and 1 more not covered
         [Some(..), None, tail..] => {}
         [Some(..), Some(..), tail..] => {}
         [None] => {}
@@ -52,7 +52,7 @@ fn main() {
     }
     let vec = vec!(0.5f32);
     let vec: &[f32] = &vec;
-    match vec { //~ ERROR non-exhaustive patterns: `[_, _, _, _]` not covered
+    match vec { //~ ERROR non-exhaustive patterns: `[_]`, `[_, _]`, `[_, _, _]` and 1 more not covered
         [0.1, 0.2, 0.3] => (),
         [0.1, 0.2] => (),
         [0.1] => (),
@@ -8,8 +8,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-#![feature(advanced_slice_patterns)]
-#![feature(slice_patterns)]
+// ignore-tidy-linelength
 
 struct Foo {
     first: bool,
@@ -18,7 +17,7 @@ struct Foo {
 
 fn struct_with_a_nested_enum_and_vector() {
     match (Foo { first: true, second: None }) {
-//~^ ERROR non-exhaustive patterns: `Foo { first: false, second: Some([_, _, _, _]) }` not covered
+//~^ ERROR `Foo { first: false, second: Some([0, _, _, _]) }` and `Foo { first: false, second: Some([2 ...
         Foo { first: true, second: None } => (),
         Foo { first: true, second: Some(_) } => (),
         Foo { first: false, second: None } => (),
@@ -0,0 +1,32 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z continue-parse-after-error
+
+// Test that the parser recovers from errors in items, impl items and
+// expressions, and reports each of them.
+
+struct S {
+    x: i32,
+}
+
+fn f() -> i32 {
+    let x = ; //~ ERROR unexpected token: `;`
+    x
+}
+
+fn g(a i32) {} //~ ERROR found `i32`
+
+impl S {
+    fn h(&self) -> i32 { self.x }
+    fn 1() {} //~ ERROR expected identifier, found `1`
+}
+
+fn main() {}
@@ -0,0 +1,36 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: -Z continue-parse-after-error
+
+// With recovery, the parser reports every typo in items and expressions,
+// not only the first one.
+
+struct S {
+    x: i32,
+}
+
+fn missing_colon(a i32) {} //~ ERROR found `i32`
+
+impl S {
+    fn get(&self) -> i32 { self.x }
+    fn (&self) {} //~ ERROR expected identifier, found `(`
+}
+
+fn missing_operand() -> i32 {
+    let a = 1 +; //~ ERROR unexpected token: `;`
+    a
+}
+
+fn main() {
+    let s = S { x: 1 };
+    let b = s.get() * ; //~ ERROR unexpected token: `;`
+    missing_colon(, b); //~ ERROR unexpected token: `,`
+}
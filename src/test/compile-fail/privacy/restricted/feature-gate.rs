@@ -17,6 +17,7 @@ mod bar {}
 struct S {
     pub(self) x: i32, //~ ERROR experimental
 }
+struct T(pub(crate) i32); //~ ERROR experimental
 impl S {
     pub(self) fn f() {} //~ ERROR experimental
 }
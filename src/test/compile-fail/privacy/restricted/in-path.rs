@@ -0,0 +1,51 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(pub_restricted)]
+#![allow(warnings)]
+
+mod a {
+    pub mod b {
+        pub(in a) fn f() {}
+        pub(in a::b) fn g() {}
+        pub struct S(pub(crate) i32, pub(super) i32, pub(in a::b) i32, pub (u8, u8));
+
+        pub fn new() -> S {
+            S(0, 0, 0, (0, 0))
+        }
+
+        pub trait Tr {}
+
+        pub(in a::c) fn h() {} //~ ERROR visibilities can only be restricted to ancestor modules
+        pub(in a::b::Tr) fn i() {}
+        //~^ ERROR visibilities can only be restricted to ancestor modules
+        pub(in std) fn j() {} //~ ERROR visibilities can only be restricted to ancestor modules
+    }
+
+    pub mod c {}
+
+    fn f() {
+        b::f(); // ok
+        b::g(); //~ ERROR private
+        let s = b::new();
+        let _ = s.0; // ok
+        let _ = s.1; // ok
+        let _ = s.2; //~ ERROR private
+        let _ = s.3; // ok
+    }
+}
+
+fn main() {
+    a::b::f(); //~ ERROR private
+    let s = a::b::new();
+    let _ = s.0; // ok
+    let _ = s.1; //~ ERROR private
+    let _ = (s.3).0; // ok
+}
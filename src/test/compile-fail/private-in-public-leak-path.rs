@@ -0,0 +1,53 @@
+// Copyright 2015 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Errors about private items in public interfaces explain how the private item is reached.
+
+#![feature(pub_restricted)]
+#![allow(dead_code)]
+
+struct Priv; //~ NOTE struct `Priv` is declared private here
+trait PrivTr1 {} //~ NOTE trait `PrivTr1` is declared private here
+trait PrivTr2 {} //~ NOTE trait `PrivTr2` is declared private here
+trait PrivTr3 { type A; } //~ NOTE trait `PrivTr3` is declared private here
+type PrivAlias = u8; //~ NOTE type alias `PrivAlias` is declared private here
+pub(crate) struct CrateVisible; //~ NOTE struct `CrateVisible` is declared with restricted
+pub struct Pub;
+impl PrivTr3 for Pub { type A = u8; }
+
+pub struct S { //~ NOTE `Priv` leaks through the interface of struct `S`
+    pub field: Priv, //~ ERROR private type in public interface
+    //~^ NOTE via field `field`
+}
+
+pub fn f<T: PrivTr1>(_: T) {} //~ ERROR private trait in public interface
+//~^ NOTE `PrivTr1` leaks through the interface of function `f`
+//~| NOTE via type parameter `T`
+
+pub trait PubTr: PrivTr2 {} //~ ERROR private trait in public interface
+//~^ NOTE `PrivTr2` leaks through the interface of trait `PubTr`
+//~| NOTE via the supertraits of `PubTr`
+
+pub fn g() -> <Pub as PrivTr3>::A { 0 } //~ ERROR private type in public interface
+//~^ NOTE `PrivTr3` leaks through the interface of function `g`
+//~| NOTE via associated type `A` of trait `PrivTr3`
+
+impl Pub { //~ NOTE `PrivAlias` leaks through the interface of the inherent impl for `Pub`
+    pub fn h(&self) -> PrivAlias { 0 } //~ ERROR private type in public interface
+    //~^ NOTE via method `h`
+}
+
+pub enum E { //~ NOTE `CrateVisible` leaks through the interface of enum `E`
+    V(CrateVisible), //~ ERROR private type in public interface
+    //~^ NOTE via variant `V`
+    //~| NOTE via field `0`
+}
+
+fn main() {}
@@ -9,10 +9,13 @@
 // except according to those terms.
 
 
-fn func((1, (Some(1), 2...3)): (isize, (Option<isize>, isize))) { }
-//~^ ERROR refutable pattern in function argument: `(_, _)` not covered
+// The argument is `i32` rather than `isize` so that the witness doesn't
+// depend on the target's pointer width.
+
+fn func((1, (Some(1), 2...3)): (i32, (Option<i32>, i32))) { }
+//~^ ERROR refutable pattern in function argument: `(-2147483648 ...0, _)` not covered
 
 fn main() {
     let (1, (Some(1), 2...3)) = (1, (None, 2));
-    //~^ ERROR refutable pattern in local binding: `(_, _)` not covered
+    //~^ ERROR refutable pattern in local binding: `(-2147483648 ...0, _)` not covered
 }
@@ -21,4 +21,10 @@ fn main() {
     let x = foo.baa;//~ ERROR attempted access of field `baa` on type `BuildData`
     //~^ HELP did you mean `bar`?
     println!("{}", x);
+
+    // fields are also suggested through autoderef
+    let boxed = Box::new(&foo);
+    let y = boxed.fo;//~ ERROR attempted access of field `fo` on type `Box<&BuildData>`
+    //~^ HELP did you mean `foo`?
+    println!("{}", y);
 }
@@ -0,0 +1,25 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(question_mark)]
+
+fn operand() -> Result<u32, ()> {
+    let x = 5u32?;
+    //~^ ERROR the trait bound `u32: std::ops::Carrier` is not satisfied
+    Ok(x)
+}
+
+fn return_type() {
+    let x: Result<u32, ()> = Ok(1);
+    x?;
+    //~^ ERROR the trait bound `(): std::ops::Carrier` is not satisfied
+}
+
+fn main() {}
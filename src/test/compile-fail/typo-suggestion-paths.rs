@@ -0,0 +1,38 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-tidy-linelength
+
+mod outer {
+    pub mod inner {
+        pub fn function() {}
+        pub struct Thing;
+        fn private_function() {}
+    }
+}
+
+fn main() {
+    outer::inner::functoin();
+    //~^ ERROR unresolved name `outer::inner::functoin`. Did you mean `outer::inner::function`?
+
+    ::outer::inner::functoin();
+    //~^ ERROR unresolved name `outer::inner::functoin`. Did you mean `::outer::inner::function`?
+
+    outer::inner::private_functoin();
+    //~^ ERROR unresolved name `outer::inner::private_functoin`
+
+    outer::iner::function();
+    //~^ ERROR failed to resolve. Could not find `iner` in `outer`. Did you mean `outer::inner`?
+
+    let _: outer::inner::Thang;
+    //~^ ERROR type name `outer::inner::Thang` is undefined or not in scope
+    //~| HELP did you mean `outer::inner::Thing`?
+    //~| HELP no candidates by the name of `Thang` found
+}
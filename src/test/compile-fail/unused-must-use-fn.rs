@@ -0,0 +1,52 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![deny(unused_must_use)]
+
+#[must_use]
+fn need() -> i32 { 0 }
+
+#[must_use = "the flag has to be checked"]
+fn need_msg() -> bool { true }
+
+fn dont_need() -> i32 { 0 }
+
+struct S;
+
+impl S {
+    #[must_use]
+    fn method(&self) -> u8 { 0 }
+}
+
+trait T {
+    #[must_use = "trait reason"]
+    fn get(&self) -> u32;
+}
+
+impl T for S {
+    fn get(&self) -> u32 { 0 }
+}
+
+fn main() {
+    need(); //~ ERROR unused return value of `need` which must be used
+    need_msg(); //~ ERROR `need_msg` which must be used: the flag has to be checked
+    dont_need();
+    let _ = need();
+
+    S.method(); //~ ERROR method` which must be used
+    S::method(&S); //~ ERROR method` which must be used
+    S.get(); //~ ERROR get` which must be used: trait reason
+
+    let v = vec![1, 2, 3];
+    v.len(); //~ ERROR len` which must be used
+    v.is_empty(); //~ ERROR is_empty` which must be used
+    v.iter().map(|x| x + 1); //~ ERROR which must be used: iterator adaptors are lazy
+    1u8.checked_add(1); //~ ERROR which must be used: this returns the result of the operation
+}
@@ -0,0 +1,33 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// A changed environment variable read by `env!` must invalidate the item
+// that reads it, and only that item.
+
+// revisions: rpass1 rpass2
+//[rpass1] rustc-env:INCR_TEST_ENV_VAR=one
+//[rpass2] rustc-env:INCR_TEST_ENV_VAR=two
+
+#![feature(rustc_attrs)]
+
+#[rustc_dirty(label="TypeckItemBody", cfg="rpass2")]
+fn value() -> &'static str {
+    env!("INCR_TEST_ENV_VAR")
+}
+
+#[rustc_clean(label="TypeckItemBody", cfg="rpass2")]
+fn unrelated() -> u32 {
+    1
+}
+
+fn main() {
+    assert_eq!(value(), if cfg!(rpass1) { "one" } else { "two" });
+    assert_eq!(unrelated(), 1);
+}
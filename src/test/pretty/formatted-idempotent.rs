@@ -0,0 +1,28 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --crate-type=lib -Z pretty-width=40
+// pretty-mode:formatted
+
+// No `pp-exact`: the formatted output is formatted a second time and the
+// two must be identical.
+
+fn add(first: i32, second: i32) -> i32 { first + second }
+
+fn long_call() -> i32 { add(add(1000000, 2000000), add(3000000, 4000000)) }
+
+fn chain(s: &str) -> usize { s.trim().to_lowercase().to_uppercase().to_lowercase().len() }
+
+fn commented(x: i32) -> i32 {
+    let y = x * /* scale */ 2; // doubled
+    add(y, // the value
+        1)
+    // done
+}
@@ -0,0 +1,66 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// pp-exact
+// compile-flags: --crate-type=lib -Z pretty-width=40
+// pretty-mode:formatted
+
+// Argument lists that don't fit on a line are block indented, method
+// call chains are broken before a `.`, and comments inside expressions
+// and at the ends of blocks stay where they were written.
+
+fn add(
+    first: i32, // the first operand
+    second: i32
+) -> i32 {
+    first + second
+}
+
+fn long_call() -> i32 {
+    add(
+        add(1000000, 2000000),
+        add(3000000, 4000000)
+    )
+}
+
+fn commented_call() -> i32 {
+    add(
+        1, // the first operand
+        // the second operand follows
+        2
+        // nothing else
+    )
+}
+
+fn in_macro() -> Vec<i32> {
+    vec!(
+        42 // the answer
+    )
+}
+
+fn between_tokens(x: i32) -> i32 {
+    let y = x * /* scale */ 2;
+    add(/* lhs */ y, 1)
+}
+
+fn end_of_block(x: i32) -> i32 {
+    if x > 0 {
+        x
+        // positive
+    } else {
+        0 // clamped
+    }
+    // nothing after the `if`
+}
+
+fn chain(s: &str) -> usize {
+    s.trim().to_lowercase()
+        .to_uppercase().len()
+}
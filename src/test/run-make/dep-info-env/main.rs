@@ -0,0 +1,19 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+include!("included.rs");
+
+const CONFIG: &'static str = include_str!("config.txt");
+const DATA: &'static [u8] = include_bytes!("data.bin");
+
+fn main() {
+    println!("{} {:?} {}", env!("DEP_INFO_ENV_SET"), option_env!("DEP_INFO_ENV_UNSET"), included());
+    println!("{} {:?}", CONFIG, DATA);
+}
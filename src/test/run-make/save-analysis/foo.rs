@@ -73,6 +73,7 @@ mod sub {
         use std::io::Write;
         pub mod sub3 {
             use std::io::Write;
+            /// Says hello from module 3.
             pub fn hello() {
                 ::println("hello from module 3");
             }
@@ -435,4 +436,8 @@ fn test_format_args() {
     print!("Hello {0}", name);
     print!("{0} + {} = {}", x, y);
     print!("x is {}, y is {1}, name is {n}", x, y, n = name);
-}
\ No newline at end of file
+}
+pub trait SelfRefTrait {}
+pub struct SelfRefTarget;
+impl<'a> SelfRefTrait for &'a SelfRefTarget {}
+impl SelfRefTrait for (u8, u8) {}
@@ -0,0 +1,26 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:proc_macro_derive_double.rs
+// ignore-stage1
+
+#[macro_use]
+extern crate proc_macro_derive_double;
+
+trait Double {
+    fn double(&self) -> Self;
+}
+
+#[derive(Double, Debug, PartialEq)]
+struct A(u32);
+
+fn main() {
+    assert_eq!(A(3).double(), A(6));
+}
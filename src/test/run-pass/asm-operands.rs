@@ -0,0 +1,66 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// ignore-aarch64
+// ignore-arm
+// ignore-asmjs
+// ignore-hexagon
+// ignore-mips
+// ignore-msp430
+// ignore-powerpc
+// ignore-powerpc64
+// ignore-sparc
+// ignore-systemz
+// ignore-x86
+// ignore-xcore
+
+#![feature(asm)]
+
+fn add(a: u64, b: u64) -> u64 {
+    let mut x = a;
+    unsafe {
+        asm_operands!("add {0}, {step}", inout(reg) x, step = in(reg) b,
+                      options(pure, nomem, nostack));
+    }
+    x
+}
+
+fn low_byte(a: u64) -> u64 {
+    let x: u64;
+    unsafe {
+        asm_operands!("movzx {0:e}, {1:l}", out(reg) x, in(reg_abcd) a, options(pure, nomem));
+    }
+    x
+}
+
+fn explicit(a: u32) -> u32 {
+    let x: u32;
+    unsafe {
+        asm_operands!("mov eax, ecx", out("eax") x, in("ecx") a, options(pure, nomem));
+    }
+    x
+}
+
+fn split(a: u64) -> (u64, u64) {
+    let b: u64;
+    unsafe {
+        asm_operands!("shl {0}, {shift}", inout(reg) a => b, shift = const 3);
+        // The result is discarded, and `rcx` is only clobbered.
+        asm_operands!("mov {0}, 1", inout(reg) a => _, out("rcx") _);
+    }
+    (a, b)
+}
+
+pub fn main() {
+    assert_eq!(add(1, 2), 3);
+    assert_eq!(low_byte(0x1234), 0x34);
+    assert_eq!(explicit(7), 7);
+    assert_eq!(split(5), (5, 40));
+}
@@ -0,0 +1,57 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// compile-flags: --cfg yes
+
+#![feature(param_attrs)]
+
+fn sum(#[cfg(yes)] a: i32, #[cfg(no)] b: i32, #[cfg(not(no))] c: i32) -> i32 {
+    a + c
+}
+
+fn pick<#[cfg(no)] T, #[cfg(yes)] U>(u: U) -> U {
+    u
+}
+
+// Where-clause predicates that name a stripped parameter are stripped too.
+fn bounded<#[cfg(no)] T, #[cfg(yes)] U>(u: U) -> U where T: Clone, Vec<T>: Default, U: Clone {
+    u.clone()
+}
+
+struct Borrowed<#[cfg(no)] 'a, #[cfg(yes)] 'b>(&'b i32) where 'a: 'b;
+
+struct Wrapper<#[cfg(no)] 'a, #[cfg_attr(yes, cfg(no))] 'b, #[cfg(yes)] T>(T);
+
+trait Sum {
+    fn sum(&self, #[cfg(no)] x: i32, #[cfg_attr(no, cfg(no))] y: i32) -> i32;
+}
+
+impl Sum for i32 {
+    fn sum(&self, #[cfg(no)] x: i32, y: i32) -> i32 {
+        *self + y
+    }
+}
+
+#[cfg_attr(yes, cfg_attr(yes, derive(Debug)))]
+struct Nested;
+
+pub fn main() {
+    assert_eq!(sum(1, 2), 3);
+    assert_eq!(pick(4), 4);
+    assert_eq!(bounded(7), 7);
+    assert_eq!(*Borrowed(&8).0, 8);
+    assert_eq!(Wrapper(5).0, 5);
+    assert_eq!(1.sum(2), 3);
+
+    let f = |#[cfg(yes)] x: i32, #[cfg(no)] y: i32| x;
+    assert_eq!(f(6), 6);
+
+    assert_eq!(format!("{:?}", Nested), "Nested");
+}
@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// aux-build:decl_macro_export.rs
+
+// `pub` `macro` items can be named from other crates, and paths in their
+// bodies resolve in the crate that defines them.
+
+#![feature(decl_macro)]
+
+extern crate decl_macro_export;
+
+use decl_macro_export::inner::two;
+
+mod nested {
+    extern crate decl_macro_export as renamed;
+
+    pub fn forty_two() -> i32 {
+        renamed::add_helper!(2)
+    }
+}
+
+// Not the `helper` the macro calls.
+fn helper() -> i32 {
+    0
+}
+
+fn main() {
+    assert_eq!(decl_macro_export::add_helper!(1), 41);
+    assert_eq!(two!(), 2);
+    assert_eq!(nested::forty_two(), 42);
+    assert_eq!(decl_macro_export::inner::zero(), 0);
+    assert_eq!(helper(), 0);
+}
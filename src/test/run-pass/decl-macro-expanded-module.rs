@@ -0,0 +1,33 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `macro` items can be defined in modules that are the result of macro
+// expansion, and are resolved through them.
+
+#![feature(decl_macro)]
+
+macro_rules! module {
+    ($name:ident) => {
+        pub mod $name {
+            pub macro answer() {
+                42
+            }
+        }
+    }
+}
+
+module!(generated);
+
+use generated::answer;
+
+fn main() {
+    assert_eq!(generated::answer!(), 42);
+    assert_eq!(answer!(), 42);
+}
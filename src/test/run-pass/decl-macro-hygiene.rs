@@ -0,0 +1,58 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Paths in the body of a `macro` are resolved at its definition site, and
+// the items it introduces don't clash with the ones at the invocation site.
+
+#![feature(decl_macro)]
+
+mod helpers {
+    use std::collections::HashMap;
+
+    // still has to be visible from the invocation site, as privacy is
+    // checked there; but it needs no `$crate` prefix or import there
+    pub fn helper(x: i32) -> i32 {
+        x + 1
+    }
+
+    pub macro call_helper($e:expr) {
+        helper($e)
+    }
+
+    pub macro new_map() {
+        HashMap::<u32, u32>::new()
+    }
+
+    pub macro define_counter($name:ident) {
+        struct Counter { count: u32 }
+
+        fn $name() -> u32 {
+            let c = Counter { count: 3 };
+            c.count
+        }
+    }
+}
+
+use helpers::{call_helper, new_map, define_counter};
+
+fn helper(_: i32) -> i32 {
+    panic!("the invocation site's helper was called")
+}
+
+struct Counter;
+
+define_counter!(three);
+
+fn main() {
+    assert_eq!(call_helper!(41), 42);
+    assert!(new_map!().is_empty());
+    let _ = Counter;
+    assert_eq!(three(), 3);
+}
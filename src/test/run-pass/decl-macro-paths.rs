@@ -0,0 +1,63 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// `macro` items are scoped like other items: they can be invoked by path
+// and imported with `use`.
+
+#![feature(decl_macro)]
+
+mod m {
+    pub macro double($e:expr) {
+        $e * 2
+    }
+
+    pub mod inner {
+        pub macro triple {
+            ($e:expr) => { $e * 3 },
+            () => { 0 },
+        }
+
+        pub fn six() -> i32 {
+            super::double!(3)
+        }
+    }
+
+    macro private($e:expr) { $e + 1 }
+
+    pub fn seven() -> i32 {
+        private!(6)
+    }
+
+    pub fn nine() -> i32 {
+        self::inner::triple!(3)
+    }
+}
+
+mod n {
+    use m::double;
+    use m::inner::{triple, six};
+
+    pub fn eight() -> i32 {
+        double!(4)
+    }
+
+    pub fn twelve() -> i32 {
+        triple!(4) + triple!() + six() - 6
+    }
+}
+
+fn main() {
+    assert_eq!(m::double!(5), 10);
+    assert_eq!(m::inner::six(), 6);
+    assert_eq!(m::seven(), 7);
+    assert_eq!(n::eight(), 8);
+    assert_eq!(m::nine(), 9);
+    assert_eq!(n::twelve(), 12);
+}
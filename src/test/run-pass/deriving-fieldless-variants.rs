@@ -0,0 +1,71 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Deriving shares one body between the fieldless variants of an enum, which
+// must still compare and hash like separate variants.
+
+#![feature(hash_default)]
+
+use std::cmp::Ordering::*;
+use std::hash::{Hash, SipHasher, Hasher};
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum Mixed {
+    A,
+    B(u8),
+    C,
+    D { x: u8, y: u8 },
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum CLike {
+    High = 10,
+    Low = 1,
+    Middle = 5,
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+enum Single {
+    Only,
+}
+
+fn hash<T: Hash>(t: &T) -> u64 {
+    let mut s = SipHasher::new_with_keys(0, 0);
+    t.hash(&mut s);
+    s.finish()
+}
+
+fn main() {
+    use Mixed::*;
+    use CLike::*;
+
+    let mixed = [A, B(1), B(2), C, D { x: 1, y: 2 }, D { x: 2, y: 1 }];
+    for (i, a) in mixed.iter().enumerate() {
+        for (j, b) in mixed.iter().enumerate() {
+            assert_eq!(a == b, i == j);
+            assert_eq!(a.cmp(b), i.cmp(&j));
+            assert_eq!(a.partial_cmp(b), Some(i.cmp(&j)));
+            assert_eq!(a < b, i < j);
+            assert_eq!(a >= b, i >= j);
+            assert_eq!(hash(a) == hash(b), i == j);
+        }
+    }
+
+    // C-like enums are ordered by their discriminants.
+    assert_eq!(High.cmp(&Low), Greater);
+    assert_eq!(Low.partial_cmp(&Middle), Some(Less));
+    assert_eq!(Middle.cmp(&Middle), Equal);
+    assert!(High != Middle);
+    assert!(hash(&High) != hash(&Low));
+
+    assert_eq!(Single::Only, Single::Only);
+    assert_eq!(Single::Only.cmp(&Single::Only), Equal);
+    assert_eq!(hash(&Single::Only), hash(&Single::Only));
+}
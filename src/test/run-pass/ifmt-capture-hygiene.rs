@@ -0,0 +1,53 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Captured variables are resolved where the format string was written.
+
+#![feature(format_args_capture)]
+
+macro_rules! own_x {
+    () => {{
+        let x = "macro";
+        format!("{x}")
+    }}
+}
+
+macro_rules! callers_x {
+    ($fmt:expr) => {{
+        let x = "macro";
+        format!($fmt)
+    }}
+}
+
+macro_rules! print_own_x {
+    ($s:expr) => {{
+        let x = "macro";
+        writeln!($s, "{x}").unwrap();
+    }}
+}
+
+fn arg(x: i32) -> String {
+    format!("{x}")
+}
+
+fn main() {
+    use std::fmt::Write;
+
+    let x = "call site";
+    assert_eq!(own_x!(), "macro");
+    assert_eq!(callers_x!("{x}"), "call site");
+    assert_eq!(format!("{x}"), "call site");
+
+    let mut s = String::new();
+    print_own_x!(s);
+    assert_eq!(s, "macro\n");
+
+    assert_eq!(arg(1), "1");
+}
@@ -0,0 +1,34 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Placeholders naming no argument use the variable of that name.
+
+#![feature(format_args_capture)]
+
+use std::fmt::Write;
+
+fn main() {
+    let x = 42;
+    let width = 6;
+    let name = "foo";
+    assert_eq!(format!("{x}"), "42");
+    assert_eq!(format!("{x} {x}"), "42 42");
+    assert_eq!(format!("{x:x}"), "2a");
+    assert_eq!(format!("{name:>width$}|{x:<width$}|"), "   foo|42    |");
+    assert_eq!(format!("{:.*} {name}", 2, 1.5), "1.50 foo");
+
+    // An explicit argument takes precedence over a variable.
+    assert_eq!(format!("{x}", x = 1), "1");
+
+    // Format strings built by macros can capture too.
+    let mut s = String::new();
+    writeln!(s, "{name}={x}").unwrap();
+    assert_eq!(s, "foo=42\n");
+}
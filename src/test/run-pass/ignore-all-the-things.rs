@@ -10,9 +10,6 @@
 
 // pretty-expanded FIXME #23616
 
-#![feature(advanced_slice_patterns)]
-#![feature(slice_patterns)]
-
 struct Foo(isize, isize, isize, isize);
 struct Bar{a: isize, b: isize, c: isize, d: isize}
 
@@ -17,4 +17,7 @@ pub enum Foo {
     }
 }
 
-fn main() { }
+fn main() {
+    let Foo::Bar { baz } = Foo::Bar { baz: 0 };
+    assert_eq!(baz, 0);
+}
@@ -8,9 +8,6 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-
-#![feature(slice_patterns)]
-
 fn main() {
     let mut x: &[_] = &[1, 2, 3, 4];
 
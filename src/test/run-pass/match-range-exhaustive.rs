@@ -0,0 +1,65 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Matches covering every value of an integral or `char` type with ranges
+// and literals are exhaustive without a wildcard arm.
+
+fn classify(b: u8) -> &'static str {
+    match b {
+        0 => "nul",
+        1...0x1f | 0x7f => "control",
+        0x20...0x7e => "printable",
+        0x80...0xff => "high",
+    }
+}
+
+fn sign(x: i8) -> i8 {
+    match x {
+        -128...-1 => -1,
+        0 => 0,
+        1...127 => 1,
+    }
+}
+
+fn is_lower(c: char) -> bool {
+    match c {
+        '\0'...'`' => false,
+        'a'...'z' => true,
+        '{'...'\u{D7FF}' => false,
+        '\u{E000}'...'\u{10FFFF}' => false,
+    }
+}
+
+fn pair(p: (bool, u16)) -> u32 {
+    match p {
+        (true, _) => 0,
+        (false, 0...0x7fff) => 1,
+        (false, 0x8000...0xffff) => 2,
+    }
+}
+
+pub fn main() {
+    assert_eq!(classify(0), "nul");
+    assert_eq!(classify(0x7f), "control");
+    assert_eq!(classify(b'a'), "printable");
+    assert_eq!(classify(0xff), "high");
+
+    assert_eq!(sign(-128), -1);
+    assert_eq!(sign(0), 0);
+    assert_eq!(sign(127), 1);
+
+    assert!(is_lower('q'));
+    assert!(!is_lower('Q'));
+    assert!(!is_lower('\u{10FFFF}'));
+
+    assert_eq!(pair((true, 3)), 0);
+    assert_eq!(pair((false, 3)), 1);
+    assert_eq!(pair((false, 0x8000)), 2);
+}
@@ -0,0 +1,41 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Check that computing non-lexical regions from MIR liveness copes
+// with borrows across loops, calls and branches, and that a borrow ends
+// after its last use: `f` is still in scope when `v` is pushed to.
+
+// compile-flags: -Z nll
+
+#![feature(rustc_attrs)]
+
+fn first<'a>(v: &'a [u32]) -> &'a u32 {
+    &v[0]
+}
+
+#[rustc_mir_borrowck]
+fn sum(v: &mut Vec<u32>) -> u32 {
+    let mut total = 0;
+    for x in v.iter() {
+        total += *x;
+    }
+    let f = first(v);
+    if *f > 10 {
+        total += *f;
+    }
+    v.push(total);
+    total
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    assert_eq!(sum(&mut v), 6);
+    assert_eq!(v, [1, 2, 3, 6]);
+}
@@ -10,8 +10,7 @@
 
 // pretty-expanded FIXME #23616
 
-#![feature(advanced_slice_patterns,)]
-#![feature(slice_patterns)]
+#![allow(dead_code,)]
 
 fn f<T,>(_: T,) {}
 
@@ -0,0 +1,73 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+#![feature(question_mark, question_mark_carrier)]
+
+use std::ops::Carrier;
+
+#[derive(Debug, PartialEq)]
+enum Poll<T, E> {
+    Ready(T),
+    NotReady,
+    Failed(E),
+}
+
+impl<T, E> Carrier for Poll<T, E> {
+    type Success = Option<T>;
+    type Error = E;
+
+    fn from_success(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Poll::Ready(value),
+            None => Poll::NotReady,
+        }
+    }
+
+    fn from_error(error: E) -> Self {
+        Poll::Failed(error)
+    }
+
+    fn translate<C>(self) -> C where C: Carrier<Success=Option<T>, Error=E> {
+        match self {
+            Poll::Ready(value) => C::from_success(Some(value)),
+            Poll::NotReady => C::from_success(None),
+            Poll::Failed(error) => C::from_error(error),
+        }
+    }
+}
+
+fn double(p: Poll<u32, String>) -> Poll<u32, String> {
+    match p? {
+        Some(value) => Poll::Ready(value * 2),
+        None => Poll::NotReady,
+    }
+}
+
+fn poll_from_result(r: Result<u32, String>) -> Poll<u32, String> {
+    Poll::Ready(r? + 1)
+}
+
+fn first_even(v: &[u32]) -> Option<u32> {
+    let first = *v.first()?;
+    if first % 2 == 0 { Some(first) } else { None }
+}
+
+fn main() {
+    assert_eq!(double(Poll::Ready(2)), Poll::Ready(4));
+    assert_eq!(double(Poll::NotReady), Poll::NotReady);
+    assert_eq!(double(Poll::Failed("x".to_string())), Poll::Failed("x".to_string()));
+
+    assert_eq!(poll_from_result(Ok(1)), Poll::Ready(2));
+    assert_eq!(poll_from_result(Err("y".to_string())), Poll::Failed("y".to_string()));
+
+    assert_eq!(first_even(&[]), None);
+    assert_eq!(first_even(&[1]), None);
+    assert_eq!(first_even(&[2, 3]), Some(2));
+}
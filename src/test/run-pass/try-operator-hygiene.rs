@@ -10,9 +10,9 @@
 
 // `expr?` expands to:
 //
-// match expr {
+// match Carrier::translate(expr) {
 //     Ok(val) => val,
-//     Err(err) => return Err(From::from(err)),
+//     Err(err) => return Carrier::from_error(From::from(err)),
 // }
 //
 // This test verifies that the expansion is hygienic, i.e. it's not affected by other `val` and
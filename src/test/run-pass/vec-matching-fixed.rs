@@ -8,10 +8,6 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-
-#![feature(advanced_slice_patterns)]
-#![feature(slice_patterns)]
-
 fn a() {
     let x = [1, 2, 3];
     match x {
@@ -0,0 +1,48 @@
+// Copyright 2016 The Rust Project Developers. See the COPYRIGHT
+// file at the top-level directory of this distribution and at
+// http://rust-lang.org/COPYRIGHT.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+// Slice patterns are stable, and moving elements out of an array by
+// pattern only moves the elements that are actually bound.
+
+fn split3(arr: [String; 3]) -> (String, String, String) {
+    let [a, b, c] = arr;
+    (a, b, c)
+}
+
+fn ends(arr: [Box<u32>; 4]) -> (Box<u32>, Box<u32>) {
+    match arr {
+        [first, .., last] => (first, last),
+    }
+}
+
+fn moved_separately(arr: [String; 2]) -> String {
+    let [a, _] = arr;
+    let [_, b] = arr;
+    a + &b
+}
+
+fn tail_sum(v: &[u32]) -> u32 {
+    match v {
+        [] => 0,
+        [x, rest..] => x + tail_sum(rest),
+    }
+}
+
+fn main() {
+    let (a, b, c) = split3(["a".to_string(), "b".to_string(), "c".to_string()]);
+    assert_eq!((&a[..], &b[..], &c[..]), ("a", "b", "c"));
+
+    let (first, last) = ends([Box::new(1), Box::new(2), Box::new(3), Box::new(4)]);
+    assert_eq!((*first, *last), (1, 4));
+
+    assert_eq!(moved_separately(["foo".to_string(), "bar".to_string()]), "foobar");
+
+    assert_eq!(tail_sum(&[1, 2, 3, 4]), 10);
+}
@@ -8,10 +8,6 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-
-
-#![feature(slice_patterns)]
-
 struct Foo {
     string: String
 }